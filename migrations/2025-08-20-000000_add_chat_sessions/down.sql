@@ -0,0 +1,7 @@
+DROP TRIGGER IF EXISTS trigger_chat_sessions_updated_at ON chat_sessions;
+
+DROP INDEX IF EXISTS idx_chat_messages_session;
+DROP INDEX IF EXISTS idx_chat_sessions_user;
+
+DROP TABLE IF EXISTS chat_messages;
+DROP TABLE IF EXISTS chat_sessions;
@@ -0,0 +1,7 @@
+-- questions asked through the public ask page, per user per day
+CREATE TABLE rag_usage (
+    user_id INT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    day DATE NOT NULL DEFAULT CURRENT_DATE,
+    questions INT NOT NULL DEFAULT 0,
+    PRIMARY KEY (user_id, day)
+);
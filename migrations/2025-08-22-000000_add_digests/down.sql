@@ -0,0 +1,5 @@
+DROP TRIGGER IF EXISTS trigger_digests_updated_at ON digests;
+
+DROP INDEX IF EXISTS idx_digests_starts_on;
+
+DROP TABLE IF EXISTS digests;
@@ -0,0 +1,23 @@
+-- generated editorial summaries of the posts published in a window
+CREATE TABLE digests (
+    id SERIAL PRIMARY KEY,
+    period VARCHAR(10) NOT NULL, -- 'daily', 'weekly'
+    grouped_by VARCHAR(10) NOT NULL, -- 'company', 'topic'
+    starts_on DATE NOT NULL,
+    ends_on DATE NOT NULL, -- exclusive
+    title VARCHAR(255) NOT NULL,
+    markdown TEXT NOT NULL,
+    html TEXT NOT NULL,
+    post_count INT NOT NULL,
+    sources JSONB NOT NULL DEFAULT '[]', -- numbered posts the [n] citations refer to
+    created_at TIMESTAMP DEFAULT NOW(),
+    updated_at TIMESTAMP DEFAULT NOW(),
+    UNIQUE (period, grouped_by, starts_on)
+);
+
+CREATE INDEX idx_digests_starts_on ON digests(starts_on DESC);
+
+CREATE TRIGGER trigger_digests_updated_at
+    BEFORE UPDATE ON digests
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at();
@@ -0,0 +1,43 @@
+-- searches a user wants to hear about when new posts are ingested
+CREATE TABLE saved_searches (
+    id SERIAL PRIMARY KEY,
+    user_id INT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    kind VARCHAR(10) NOT NULL CHECK (kind IN ('keyword', 'company', 'semantic')),
+    query TEXT NOT NULL,
+    min_similarity INT NOT NULL DEFAULT 50, -- percent, semantic searches only
+    webhook_url TEXT NOT NULL,
+    webhook_secret VARCHAR(64) NOT NULL, -- hmac-sha256 key for the X-BB-Signature header
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    last_matched_at TIMESTAMP,
+    created_at TIMESTAMP DEFAULT NOW(),
+    updated_at TIMESTAMP DEFAULT NOW()
+);
+
+-- one row per post sent to a saved search's webhook
+CREATE TABLE webhook_deliveries (
+    id SERIAL PRIMARY KEY,
+    saved_search_id INT NOT NULL REFERENCES saved_searches(id) ON DELETE CASCADE,
+    post_link TEXT NOT NULL,
+    status VARCHAR(10) NOT NULL DEFAULT 'pending', -- 'pending', 'delivered', 'failed'
+    attempts INT NOT NULL DEFAULT 0,
+    response_status INT,
+    error TEXT,
+    payload JSONB NOT NULL,
+    created_at TIMESTAMP DEFAULT NOW(),
+    updated_at TIMESTAMP DEFAULT NOW(),
+    UNIQUE (saved_search_id, post_link)
+);
+
+CREATE INDEX idx_saved_searches_user ON saved_searches(user_id);
+CREATE INDEX idx_webhook_deliveries_search ON webhook_deliveries(saved_search_id, created_at DESC);
+
+CREATE TRIGGER trigger_saved_searches_updated_at
+    BEFORE UPDATE ON saved_searches
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at();
+
+CREATE TRIGGER trigger_webhook_deliveries_updated_at
+    BEFORE UPDATE ON webhook_deliveries
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at();
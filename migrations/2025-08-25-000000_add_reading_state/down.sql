@@ -0,0 +1,4 @@
+DROP TABLE IF EXISTS reading_list_items;
+DROP TABLE IF EXISTS reading_lists;
+DROP TABLE IF EXISTS post_reads;
+DROP TABLE IF EXISTS post_bookmarks;
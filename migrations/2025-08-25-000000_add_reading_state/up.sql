@@ -0,0 +1,45 @@
+-- post_id columns hold poasts.id from supabase, so they can't be foreign keys
+
+-- posts a user saved for later
+CREATE TABLE post_bookmarks (
+    id SERIAL PRIMARY KEY,
+    user_id INT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    post_id INT NOT NULL,
+    created_at TIMESTAMP DEFAULT NOW(),
+    UNIQUE (user_id, post_id)
+);
+
+-- posts a user has marked as read
+CREATE TABLE post_reads (
+    id SERIAL PRIMARY KEY,
+    user_id INT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    post_id INT NOT NULL,
+    created_at TIMESTAMP DEFAULT NOW(),
+    UNIQUE (user_id, post_id)
+);
+
+-- named collections of posts
+CREATE TABLE reading_lists (
+    id SERIAL PRIMARY KEY,
+    user_id INT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    description TEXT,
+    created_at TIMESTAMP DEFAULT NOW(),
+    updated_at TIMESTAMP DEFAULT NOW(),
+    UNIQUE (user_id, name)
+);
+
+CREATE TABLE reading_list_items (
+    id SERIAL PRIMARY KEY,
+    reading_list_id INT NOT NULL REFERENCES reading_lists(id) ON DELETE CASCADE,
+    post_id INT NOT NULL,
+    created_at TIMESTAMP DEFAULT NOW(),
+    UNIQUE (reading_list_id, post_id)
+);
+
+CREATE INDEX idx_reading_lists_user ON reading_lists(user_id);
+
+CREATE TRIGGER trigger_reading_lists_updated_at
+    BEFORE UPDATE ON reading_lists
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at();
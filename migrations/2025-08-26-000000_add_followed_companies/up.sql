@@ -0,0 +1,7 @@
+-- companies a user follows, boosted in their "for you" feed
+CREATE TABLE followed_companies (
+    user_id INT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    company VARCHAR(255) NOT NULL,
+    created_at TIMESTAMP DEFAULT NOW(),
+    PRIMARY KEY (user_id, company)
+);
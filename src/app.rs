@@ -11,12 +11,19 @@ use uuid::Uuid;
 
 use crate::auth::auth_components::{AdminLogin, ProtectedAdminPanel};
 use crate::auth::context::AuthProvider;
+use crate::components::api_keys::ApiKeys;
+use crate::components::ask::Ask;
 use crate::components::auth_nav::AuthNav;
+use crate::components::digest::Digests;
 use crate::components::drawing::DrawingPage;
 use crate::components::footer::Footer;
 use crate::components::poasts::Poasts;
+use crate::components::post_detail::PostArticle;
+use crate::components::reading_list::{ReadingLists, ReadingStateProvider};
 use crate::components::room_browser::RoomBrowser;
 use crate::components::room_page::DrawingRoomPage;
+use crate::components::saved_searches::SavedSearches;
+use crate::components::trends::Trends;
 
 pub fn shell(options: LeptosOptions) -> impl IntoView {
     view! {
@@ -32,6 +39,9 @@ pub fn shell(options: LeptosOptions) -> impl IntoView {
                 <HydrationScripts options />
                 <link rel="stylesheet" id="leptos" href="/pkg/bb.css" />
                 <link rel="shortcut icon" type="image/ico" href="/favicon.ico" />
+                <link rel="alternate" type="application/atom+xml" title="bryptoblogs" href="/feed.xml" />
+                <link rel="alternate" type="application/rss+xml" title="bryptoblogs" href="/rss.xml" />
+                <link rel="alternate" type="application/feed+json" title="bryptoblogs" href="/feed.json" />
                 <MetaTags />
             </head>
             <body>
@@ -57,16 +67,25 @@ pub fn App() -> impl IntoView {
     view! {
         <QueryDevtools client=client/>
         <AuthProvider>
-            <Router>
-                <FlatRoutes fallback=|| "page not found.">
-                    <Route path=path!("") view=HomePage />
-                    <Route path=path!("admin") view=AdminLogin />
-                    <Route path=path!("admin-panel") view=ProtectedAdminPanel />
-                    <Route path=path!("draw") view=DrawingPage />
-                    <Route path=path!("rooms") view=RoomsPage />
-                    <Route path=path!("room/:room_id") view=RoomPage />
-                </FlatRoutes>
-            </Router>
+            <ReadingStateProvider>
+                <Router>
+                    <FlatRoutes fallback=|| "page not found.">
+                        <Route path=path!("") view=HomePage />
+                        <Route path=path!("admin") view=AdminLogin />
+                        <Route path=path!("admin-panel") view=ProtectedAdminPanel />
+                        <Route path=path!("draw") view=DrawingPage />
+                        <Route path=path!("rooms") view=RoomsPage />
+                        <Route path=path!("room/:room_id") view=RoomPage />
+                        <Route path=path!("post/:id") view=PostDetailPage />
+                        <Route path=path!("trends") view=TrendsPage />
+                        <Route path=path!("ask") view=AskPage />
+                        <Route path=path!("digest") view=DigestPage />
+                        <Route path=path!("saved-searches") view=SavedSearchesPage />
+                        <Route path=path!("reading-list") view=ReadingListPage />
+                        <Route path=path!("api-keys") view=ApiKeysPage />
+                    </FlatRoutes>
+                </Router>
+            </ReadingStateProvider>
         </AuthProvider>
     }
 }
@@ -93,6 +112,36 @@ fn HomePage() -> impl IntoView {
                 >
                     "drawing rooms"
                 </a>
+                <a
+                    href="/trends"
+                    class="ml-2 bg-seafoam-500 hover:bg-seafoam-600 text-white font-bold py-2 px-4 sm:py-3 sm:px-6 rounded transition-colors text-sm sm:text-base touch-manipulation active:bg-seafoam-700"
+                >
+                    "trends"
+                </a>
+                <a
+                    href="/ask"
+                    class="ml-2 bg-seafoam-500 hover:bg-seafoam-600 text-white font-bold py-2 px-4 sm:py-3 sm:px-6 rounded transition-colors text-sm sm:text-base touch-manipulation active:bg-seafoam-700"
+                >
+                    "ask"
+                </a>
+                <a
+                    href="/digest"
+                    class="ml-2 bg-seafoam-500 hover:bg-seafoam-600 text-white font-bold py-2 px-4 sm:py-3 sm:px-6 rounded transition-colors text-sm sm:text-base touch-manipulation active:bg-seafoam-700"
+                >
+                    "digest"
+                </a>
+                <a
+                    href="/saved-searches"
+                    class="ml-2 bg-seafoam-500 hover:bg-seafoam-600 text-white font-bold py-2 px-4 sm:py-3 sm:px-6 rounded transition-colors text-sm sm:text-base touch-manipulation active:bg-seafoam-700"
+                >
+                    "alerts"
+                </a>
+                <a
+                    href="/reading-list"
+                    class="ml-2 bg-seafoam-500 hover:bg-seafoam-600 text-white font-bold py-2 px-4 sm:py-3 sm:px-6 rounded transition-colors text-sm sm:text-base touch-manipulation active:bg-seafoam-700"
+                >
+                    "reading list"
+                </a>
            </div>
 
             <div class="px-2 sm:px-0">
@@ -125,6 +174,162 @@ fn RoomsPage() -> impl IntoView {
     }
 }
 
+#[component]
+fn TrendsPage() -> impl IntoView {
+    view! {
+        <div class="w-full min-h-screen bg-gray-100 dark:bg-teal-900">
+            <div class="flex justify-between items-center p-4">
+                <a
+                    href="/"
+                    class="text-3xl text-left text-seafoam-600 dark:text-mint-400 font-bold"
+                >
+                    "bryptoblogs"
+                </a>
+                <AuthNav />
+            </div>
+
+            <Trends />
+
+            <Footer />
+        </div>
+    }
+}
+
+#[component]
+fn AskPage() -> impl IntoView {
+    view! {
+        <div class="w-full min-h-screen bg-gray-100 dark:bg-teal-900">
+            <div class="flex justify-between items-center p-4">
+                <a
+                    href="/"
+                    class="text-3xl text-left text-seafoam-600 dark:text-mint-400 font-bold"
+                >
+                    "bryptoblogs"
+                </a>
+                <AuthNav />
+            </div>
+
+            <Ask />
+
+            <Footer />
+        </div>
+    }
+}
+
+#[component]
+fn DigestPage() -> impl IntoView {
+    view! {
+        <div class="w-full min-h-screen bg-gray-100 dark:bg-teal-900">
+            <div class="flex justify-between items-center p-4">
+                <a
+                    href="/"
+                    class="text-3xl text-left text-seafoam-600 dark:text-mint-400 font-bold"
+                >
+                    "bryptoblogs"
+                </a>
+                <AuthNav />
+            </div>
+
+            <Digests />
+
+            <Footer />
+        </div>
+    }
+}
+
+#[component]
+fn SavedSearchesPage() -> impl IntoView {
+    view! {
+        <div class="w-full min-h-screen bg-gray-100 dark:bg-teal-900">
+            <div class="flex justify-between items-center p-4">
+                <a
+                    href="/"
+                    class="text-3xl text-left text-seafoam-600 dark:text-mint-400 font-bold"
+                >
+                    "bryptoblogs"
+                </a>
+                <AuthNav />
+            </div>
+
+            <SavedSearches />
+
+            <Footer />
+        </div>
+    }
+}
+
+#[component]
+fn ReadingListPage() -> impl IntoView {
+    view! {
+        <div class="w-full min-h-screen bg-gray-100 dark:bg-teal-900">
+            <div class="flex justify-between items-center p-4">
+                <a
+                    href="/"
+                    class="text-3xl text-left text-seafoam-600 dark:text-mint-400 font-bold"
+                >
+                    "bryptoblogs"
+                </a>
+                <AuthNav />
+            </div>
+
+            <ReadingLists />
+
+            <Footer />
+        </div>
+    }
+}
+
+#[component]
+fn ApiKeysPage() -> impl IntoView {
+    view! {
+        <div class="w-full min-h-screen bg-gray-100 dark:bg-teal-900">
+            <div class="flex justify-between items-center p-4">
+                <a
+                    href="/"
+                    class="text-3xl text-left text-seafoam-600 dark:text-mint-400 font-bold"
+                >
+                    "bryptoblogs"
+                </a>
+                <AuthNav />
+            </div>
+
+            <ApiKeys />
+
+            <Footer />
+        </div>
+    }
+}
+
+#[component]
+fn PostDetailPage() -> impl IntoView {
+    let params = use_params_map();
+
+    let post_id = Memo::new(move |_| {
+        params
+            .get()
+            .get("id")
+            .and_then(|id_str| id_str.parse::<i32>().ok())
+    });
+
+    view! {
+        <div class="w-full min-h-screen bg-gray-100 dark:bg-teal-900">
+            <div class="flex justify-between items-center p-4">
+                <a
+                    href="/"
+                    class="text-3xl text-left text-seafoam-600 dark:text-mint-400 font-bold"
+                >
+                    "bryptoblogs"
+                </a>
+                <AuthNav />
+            </div>
+
+            <PostArticle post_id=post_id />
+
+            <Footer />
+        </div>
+    }
+}
+
 #[component]
 fn RoomPage() -> impl IntoView {
     let params = use_params_map();
@@ -4,7 +4,8 @@ use leptos_router::hooks::use_navigate;
 use crate::auth::{context::AuthContext, get_current_user, Logout};
 use crate::components::batch_processor::BatchProcessor;
 use crate::components::dark_mode_toggle::DarkModeToggle;
-use crate::components::embeddings::EmbeddingsProcessor;
+use crate::components::embeddings::{EmbeddingStatusPanel, EmbeddingsProcessor};
+use crate::components::feed_sources::{FeedSources, OpmlTransfer};
 use crate::components::local_embeddings::LocalEmbeddingsProcessor;
 use crate::components::rag_chat::RagChat;
 use crate::components::rss_test::RssTest;
@@ -21,6 +22,16 @@ fn is_admin_user(user: &crate::models::users::UserView) -> bool {
         .is_some_and(|email| email == ADMIN_EMAIL)
 }
 
+/// Server-side guard for admin-only server functions; hiding the panel isn't enough.
+#[cfg(feature = "ssr")]
+pub async fn ensure_admin() -> Result<UserView, ServerFnError> {
+    match get_current_user().await? {
+        Some(user) if is_admin_user(&user) => Ok(user),
+        Some(_) => Err(ServerFnError::new("Admin access required")),
+        None => Err(ServerFnError::new("Not authenticated")),
+    }
+}
+
 #[component]
 pub fn AdminLogin() -> impl IntoView {
     view! {
@@ -154,6 +165,24 @@ pub fn ProtectedAdminPanel() -> impl IntoView {
                                                             </h2>
                                                             <RssTest />
                                                         </div>
+                                                        <div class="bg-gray-100 dark:bg-teal-900 rounded-lg shadow-lg dark:shadow-teal-highlight p-4 sm:p-6">
+                                                            <h2 class="text-xl sm:text-2xl font-bold text-gray-800 dark:text-gray-200 mb-4">
+                                                                "Companies & Feeds"
+                                                            </h2>
+                                                            <FeedSources />
+                                                        </div>
+                                                        <div class="bg-gray-100 dark:bg-teal-900 rounded-lg shadow-lg dark:shadow-teal-highlight p-4 sm:p-6">
+                                                            <h2 class="text-xl sm:text-2xl font-bold text-gray-800 dark:text-gray-200 mb-4">
+                                                                "Import / Export Feeds"
+                                                            </h2>
+                                                            <OpmlTransfer />
+                                                        </div>
+                                                        <div class="bg-gray-100 dark:bg-teal-900 rounded-lg shadow-lg dark:shadow-teal-highlight p-4 sm:p-6">
+                                                            <h2 class="text-xl sm:text-2xl font-bold text-gray-800 dark:text-gray-200 mb-4">
+                                                                "Embedding Status"
+                                                            </h2>
+                                                            <EmbeddingStatusPanel />
+                                                        </div>
                                                         <div class="bg-gray-100 dark:bg-teal-900 rounded-lg shadow-lg dark:shadow-teal-highlight p-4 sm:p-6">
                                                             <h2 class="text-xl sm:text-2xl font-bold text-gray-800 dark:text-gray-200 mb-4">
                                                                 "Generate Embeddings"
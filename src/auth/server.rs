@@ -63,6 +63,59 @@ pub mod middleware {
             }
         }
     }
+
+    /// Middleware for `/api/v1`: requires an API key in `Authorization: Bearer` or `X-API-Key`,
+    /// applies the per-key rate limit and adds the `ApiKey` to request extensions.
+    pub async fn require_api_key(
+        State(app_state): State<AppState>,
+        request: Request,
+        next: Next,
+    ) -> Response {
+        use crate::handlers::ApiError;
+        use crate::models::ApiKey;
+
+        let headers = request.headers();
+        let key = headers
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .or_else(|| headers.get("x-api-key").and_then(|value| value.to_str().ok()))
+            .map(|key| key.trim().to_string());
+
+        let Some(key) = key.filter(|key| !key.is_empty()) else {
+            return ApiError::Unauthorized("An API key is required".to_string()).into_response();
+        };
+
+        let mut conn = match app_state.pool.get().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                warn!("API auth - pool error: {e}");
+                return ApiError::Internal.into_response();
+            }
+        };
+
+        let api_key = match ApiKey::find_active(&key, &mut conn).await {
+            Ok(Some(api_key)) => api_key,
+            Ok(None) => return ApiError::Unauthorized("Invalid or revoked API key".to_string()).into_response(),
+            Err(e) => {
+                warn!("API auth - key lookup failed: {e}");
+                return ApiError::Internal.into_response();
+            }
+        };
+
+        if let Err(wait) = app_state.api_limiter.check(&api_key.id.to_string()) {
+            return ApiError::RateLimited(wait).into_response();
+        }
+
+        if let Err(e) = api_key.touch(&mut conn).await {
+            debug!("API auth - failed to record key use: {e}");
+        }
+        drop(conn);
+
+        let mut request = request;
+        request.extensions_mut().insert(api_key);
+        next.run(request).await
+    }
 }
 
 cfg_if! {
@@ -0,0 +1,32 @@
+use dotenv::dotenv;
+
+#[cfg(feature = "ssr")]
+#[tokio::main]
+async fn main() {
+    use bb::trends_service::trends::{run_topic_clustering, ClusteringOptions};
+    use tokio_util::sync::CancellationToken;
+
+    dotenv().ok();
+    env_logger::init_from_env(env_logger::Env::default().default_filter_or("info"));
+
+    // optional first argument overrides the number of clusters
+    let options = ClusteringOptions {
+        k: std::env::args().nth(1).and_then(|k| k.parse().ok()),
+        ..Default::default()
+    };
+
+    log::info!("Starting topic clustering...");
+    match run_topic_clustering(options, CancellationToken::new()).await {
+        Ok(summary) => log::info!("Clustered {} posts into {} topics", summary.posts, summary.clusters),
+        Err(e) => {
+            log::error!("Topic clustering failed: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(not(feature = "ssr"))]
+fn main() {
+    eprintln!("This binary requires the 'ssr' feature");
+    std::process::exit(1);
+}
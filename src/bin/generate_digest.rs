@@ -0,0 +1,51 @@
+use dotenv::dotenv;
+
+#[cfg(feature = "ssr")]
+#[tokio::main]
+async fn main() {
+    use bb::database::db::establish_connection;
+    use bb::digest_service::digest::{generate_digest, DigestOptions};
+
+    dotenv().ok();
+    env_logger::init_from_env(env_logger::Env::default().default_filter_or("info"));
+
+    // usage: generate_digest [daily|weekly] [company|topic] [YYYY-MM-DD end date, exclusive]
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut options = DigestOptions::default();
+    let parsed = (|| -> Result<(), String> {
+        if let Some(period) = args.first() {
+            options.period = period.parse()?;
+        }
+        if let Some(grouping) = args.get(1) {
+            options.grouping = grouping.parse()?;
+        }
+        if let Some(ends_on) = args.get(2) {
+            options.ends_on = Some(
+                chrono::NaiveDate::parse_from_str(ends_on, "%Y-%m-%d")
+                    .map_err(|e| format!("invalid date '{}': {}", ends_on, e))?,
+            );
+        }
+        Ok(())
+    })();
+    if let Err(e) = parsed {
+        eprintln!("{}\nusage: generate_digest [daily|weekly] [company|topic] [YYYY-MM-DD]", e);
+        std::process::exit(2);
+    }
+
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = establish_connection(&database_url).expect("failed to create database pool");
+
+    match generate_digest(options, &pool).await {
+        Ok(digest) => log::info!("Wrote digest {} '{}' from {} posts", digest.id, digest.title, digest.post_count),
+        Err(e) => {
+            log::error!("Digest generation failed: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(not(feature = "ssr"))]
+fn main() {
+    eprintln!("This binary requires the 'ssr' feature");
+    std::process::exit(1);
+}
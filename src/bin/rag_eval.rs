@@ -0,0 +1,282 @@
+use dotenv::dotenv;
+
+/// Runs the golden questions in `eval/golden.json` through the RAG pipeline against the fixture
+/// corpus and prints a plain-text report meant to be diffed between runs.
+///
+/// Flags: `--golden PATH`, `--corpus PATH`, `--k N`, `--out PATH`, and `--retrieval-only`, which
+/// skips the LLM entirely and scores MiniLM retrieval alone. Otherwise the chat model comes from
+/// `RAG_EVAL_API_BASE` (any OpenAI-compatible server) and `RAG_EVAL_MODEL`, defaulting to OpenAI.
+#[cfg(feature = "ssr")]
+#[tokio::main]
+async fn main() {
+    use async_openai::{config::OpenAIConfig, Client};
+    use bb::components::poasts::PostFilter;
+    use bb::components::search::SearchType;
+    use bb::rag_service::fixture::fixture::FixtureCorpus;
+    use bb::rag_service::rag::rag::{citation_markers, EnhancedRagService};
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    dotenv().ok();
+    env_logger::init_from_env(env_logger::Env::default().default_filter_or("warn"));
+
+    let mut golden_path = PathBuf::from("eval/golden.json");
+    let mut corpus_path = PathBuf::from("eval/fixtures/posts.json");
+    let mut out_path: Option<PathBuf> = None;
+    let mut k = 5usize;
+    let mut retrieval_only = false;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--golden" => golden_path = args.next().map(PathBuf::from).unwrap_or(golden_path),
+            "--corpus" => corpus_path = args.next().map(PathBuf::from).unwrap_or(corpus_path),
+            "--out" => out_path = args.next().map(PathBuf::from),
+            "--k" => k = args.next().and_then(|v| v.parse().ok()).unwrap_or(k).max(1),
+            "--retrieval-only" => retrieval_only = true,
+            other => {
+                eprintln!("Unknown argument: {}", other);
+                std::process::exit(2);
+            }
+        }
+    }
+
+    let golden: Vec<GoldenQuestion> = match std::fs::read_to_string(&golden_path)
+        .map_err(|e| e.to_string())
+        .and_then(|body| serde_json::from_str(&body).map_err(|e| e.to_string()))
+    {
+        Ok(golden) => golden,
+        Err(e) => {
+            log::error!("Failed to load {}: {}", golden_path.display(), e);
+            std::process::exit(1);
+        }
+    };
+
+    let corpus = match FixtureCorpus::load(&corpus_path) {
+        Ok(corpus) => Arc::new(corpus),
+        Err(e) => {
+            log::error!("Failed to load corpus: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let model = std::env::var("RAG_EVAL_MODEL").unwrap_or_else(|_| "gpt-4o-mini".to_string());
+    let service = match std::env::var("RAG_EVAL_API_BASE") {
+        Ok(api_base) => {
+            // local servers usually ignore the key but the client always sends one
+            let api_key = std::env::var("OPENAI_API_KEY").unwrap_or_else(|_| "local".to_string());
+            let config = OpenAIConfig::new().with_api_base(api_base).with_api_key(api_key);
+            EnhancedRagService::with_client(Client::with_config(config), model.clone())
+        }
+        Err(_) => EnhancedRagService::with_client(Client::new(), model.clone()),
+    }
+    .with_posts(corpus.clone());
+
+    let mut outcomes = Vec::new();
+    for question in &golden {
+        log::info!("Evaluating {}", question.id);
+
+        let outcome = if retrieval_only {
+            let filter = PostFilter {
+                limit: Some(k),
+                ..Default::default()
+            };
+            match corpus.semantic(&question.question, &filter) {
+                Ok(posts) => Outcome {
+                    retrieved: posts.into_iter().map(|p| p.link).collect(),
+                    ..Default::default()
+                },
+                Err(e) => Outcome {
+                    error: Some(e.to_string()),
+                    ..Default::default()
+                },
+            }
+        } else {
+            // the pipeline streams SSE events as it goes; nobody is listening here
+            let (tx, mut rx) = tokio::sync::mpsc::channel(100);
+            let drain = tokio::spawn(async move { while rx.recv().await.is_some() {} });
+            let result = service
+                .process_query(question.question.clone(), SearchType::LocalSemantic, &[], tx)
+                .await;
+            drain.await.ok();
+
+            match result {
+                Ok(Some(message)) => {
+                    let citations = message.citations.unwrap_or_default();
+                    let mut markers = citation_markers(&message.content);
+                    markers.sort();
+                    markers.dedup();
+                    Outcome {
+                        retrieved: citations.iter().map(|c| c.link.clone()).collect(),
+                        cited: Some(
+                            markers
+                                .into_iter()
+                                .filter_map(|n| citations.get(n.wrapping_sub(1)).map(|c| c.link.clone()))
+                                .collect(),
+                        ),
+                        answer: Some(message.content),
+                        error: None,
+                    }
+                }
+                Ok(None) => Outcome {
+                    error: Some("no answer generated".to_string()),
+                    ..Default::default()
+                },
+                Err(e) => Outcome {
+                    error: Some(e.to_string()),
+                    ..Default::default()
+                },
+            }
+        };
+        outcomes.push(outcome);
+    }
+
+    let mode = if retrieval_only {
+        "retrieval only".to_string()
+    } else {
+        format!("full ({})", model)
+    };
+    let report = render_report(&golden, &outcomes, k, &mode, &corpus_path.display().to_string(), corpus.len());
+
+    match out_path {
+        Some(path) => {
+            if let Err(e) = std::fs::write(&path, &report) {
+                log::error!("Failed to write {}: {}", path.display(), e);
+                std::process::exit(1);
+            }
+            log::info!("Wrote report to {}", path.display());
+        }
+        None => print!("{}", report),
+    }
+}
+
+#[cfg(feature = "ssr")]
+#[derive(serde::Deserialize)]
+struct GoldenQuestion {
+    id: String,
+    question: String,
+    /// Links of the posts a good answer is built from.
+    #[serde(default)]
+    expected_links: Vec<String>,
+    /// Case-insensitive substrings the answer must contain.
+    #[serde(default)]
+    answer_contains: Vec<String>,
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Default)]
+struct Outcome {
+    /// Links in the order the pipeline ranked them.
+    retrieved: Vec<String>,
+    /// Links the answer actually cited with `[n]` markers.
+    cited: Option<Vec<String>>,
+    answer: Option<String>,
+    error: Option<String>,
+}
+
+#[cfg(feature = "ssr")]
+fn render_report(
+    golden: &[GoldenQuestion],
+    outcomes: &[Outcome],
+    k: usize,
+    mode: &str,
+    corpus: &str,
+    corpus_size: usize,
+) -> String {
+    use std::fmt::Write;
+
+    fn ratio(value: Option<f32>) -> String {
+        value.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "-".to_string())
+    }
+
+    fn mean(values: &[f32]) -> Option<f32> {
+        (!values.is_empty()).then(|| values.iter().sum::<f32>() / values.len() as f32)
+    }
+
+    let mut report = String::new();
+    let mut details = String::new();
+    let mut recalls = Vec::new();
+    let mut precisions = Vec::new();
+    let (mut checks_passed, mut checks_total) = (0, 0);
+
+    let _ = writeln!(report, "# rag_eval");
+    let _ = writeln!(report, "mode: {}", mode);
+    let _ = writeln!(report, "corpus: {} ({} posts)", corpus, corpus_size);
+    let _ = writeln!(report, "questions: {}", golden.len());
+    let _ = writeln!(report);
+    let recall_header = format!("recall@{}", k);
+    let _ = writeln!(report, "{:<28} {:>10} {:>10} {:>9}", "id", recall_header, "cite_prec", "contains");
+
+    for (question, outcome) in golden.iter().zip(outcomes) {
+        if let Some(error) = &outcome.error {
+            let _ = writeln!(report, "{:<28} error: {}", question.id, error);
+            continue;
+        }
+
+        let top_k = &outcome.retrieved[..outcome.retrieved.len().min(k)];
+        let missed: Vec<&String> = question.expected_links.iter().filter(|l| !top_k.contains(l)).collect();
+        let recall = (!question.expected_links.is_empty())
+            .then(|| 1.0 - missed.len() as f32 / question.expected_links.len() as f32);
+        recalls.extend(recall);
+
+        let precision = outcome.cited.as_ref().filter(|cited| !cited.is_empty()).map(|cited| {
+            cited.iter().filter(|l| question.expected_links.contains(l)).count() as f32 / cited.len() as f32
+        });
+        precisions.extend(precision);
+
+        let contains = match &outcome.answer {
+            Some(answer) if !question.answer_contains.is_empty() => {
+                let answer = answer.to_lowercase();
+                let failed: Vec<&String> = question
+                    .answer_contains
+                    .iter()
+                    .filter(|s| !answer.contains(&s.to_lowercase()))
+                    .collect();
+                let passed = question.answer_contains.len() - failed.len();
+                checks_passed += passed;
+                checks_total += question.answer_contains.len();
+                for text in failed {
+                    let _ = writeln!(details, "{}: answer lacks \"{}\"", question.id, text);
+                }
+                format!("{}/{}", passed, question.answer_contains.len())
+            }
+            _ => "-".to_string(),
+        };
+
+        for link in missed {
+            let _ = writeln!(details, "{}: missed {}", question.id, link);
+        }
+
+        let _ = writeln!(
+            report,
+            "{:<28} {:>10} {:>10} {:>9}",
+            question.id,
+            ratio(recall),
+            ratio(precision),
+            contains
+        );
+    }
+
+    let _ = writeln!(report);
+    let _ = writeln!(report, "mean recall@{}: {}", k, ratio(mean(&recalls)));
+    let _ = writeln!(report, "mean citation precision: {}", ratio(mean(&precisions)));
+    if checks_total > 0 {
+        let _ = writeln!(report, "answer checks passed: {}/{}", checks_passed, checks_total);
+    } else {
+        let _ = writeln!(report, "answer checks passed: -");
+    }
+
+    if !details.is_empty() {
+        let _ = writeln!(report);
+        let _ = writeln!(report, "## misses");
+        report.push_str(&details);
+    }
+
+    report
+}
+
+#[cfg(not(feature = "ssr"))]
+fn main() {
+    eprintln!("This binary requires the 'ssr' feature");
+    std::process::exit(1);
+}
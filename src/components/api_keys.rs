@@ -0,0 +1,238 @@
+use leptos::prelude::*;
+use server_fn::codec::{GetUrl, PostUrl};
+
+use crate::auth::context::AuthContext;
+use crate::models::{ApiKeyView, CreatedApiKeyView};
+
+/// The signed-in user's id and a pooled connection.
+#[cfg(feature = "ssr")]
+async fn api_key_user_connection() -> Result<
+    (i32, diesel_async::pooled_connection::deadpool::Object<diesel_async::AsyncPgConnection>),
+    ServerFnError,
+> {
+    use crate::components::drawing_rooms::get_authenticated_user_id;
+    use crate::state::AppState;
+
+    let user_id = get_authenticated_user_id()
+        .await
+        .map_err(|_| ServerFnError::new("Not authenticated"))?;
+
+    let app_state = use_context::<AppState>()
+        .expect("Failed to get AppState from context");
+    let conn = app_state.pool
+        .get()
+        .await
+        .map_err(|e| ServerFnError::new(format!("Pool error: {}", e)))?;
+
+    Ok((user_id, conn))
+}
+
+#[server(
+    prefix = "/api",
+    endpoint = "list_api_keys",
+    input = GetUrl
+)]
+pub async fn list_api_keys() -> Result<Vec<ApiKeyView>, ServerFnError> {
+    use crate::models::ApiKey;
+
+    let (user_id, mut conn) = api_key_user_connection().await?;
+    let keys = ApiKey::list_for_user(user_id, &mut conn)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+
+    Ok(keys.into_iter().map(ApiKeyView::from).collect())
+}
+
+#[server(
+    prefix = "/api",
+    endpoint = "create_api_key",
+    input = PostUrl
+)]
+pub async fn create_api_key(name: String) -> Result<CreatedApiKeyView, ServerFnError> {
+    use crate::models::{ApiKey, MAX_API_KEYS};
+
+    let name: String = name.trim().chars().take(100).collect();
+    if name.is_empty() {
+        return Err(ServerFnError::new("Give the key a name"));
+    }
+
+    let (user_id, mut conn) = api_key_user_connection().await?;
+    let active = ApiKey::count_active_for_user(user_id, &mut conn)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+    if active >= MAX_API_KEYS {
+        return Err(ServerFnError::new(format!("You can have at most {} active keys", MAX_API_KEYS)));
+    }
+
+    let (api_key, key) = ApiKey::create(user_id, name, &mut conn)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+
+    Ok(CreatedApiKeyView {
+        key,
+        api_key: api_key.into(),
+    })
+}
+
+#[server(
+    prefix = "/api",
+    endpoint = "revoke_api_key",
+    input = PostUrl
+)]
+pub async fn revoke_api_key(id: i32) -> Result<(), ServerFnError> {
+    use crate::models::ApiKey;
+
+    let (user_id, mut conn) = api_key_user_connection().await?;
+    let revoked = ApiKey::revoke_owned(id, user_id, &mut conn)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+    if revoked == 0 {
+        return Err(ServerFnError::new("API key not found"));
+    }
+    Ok(())
+}
+
+#[component]
+pub fn ApiKeys() -> impl IntoView {
+    let auth = use_context::<AuthContext>();
+    let is_authenticated = move || auth.as_ref().is_some_and(|a| a.is_authenticated.get());
+
+    let (version, set_version) = signal(0u32);
+    let keys = Resource::new(move || version.get(), |_| list_api_keys());
+
+    let (name, set_name) = signal(String::new());
+    let (created_key, set_created_key) = signal(Option::<String>::None);
+    let (error, set_error) = signal(Option::<String>::None);
+
+    let create = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        set_error(None);
+        let key_name = name.get_untracked();
+        wasm_bindgen_futures::spawn_local(async move {
+            match create_api_key(key_name).await {
+                Ok(created) => {
+                    set_created_key(Some(created.key));
+                    set_name(String::new());
+                    set_version.update(|v| *v += 1);
+                }
+                Err(e) => set_error(Some(e.to_string())),
+            }
+        });
+    };
+
+    let revoke = move |id: i32| {
+        wasm_bindgen_futures::spawn_local(async move {
+            match revoke_api_key(id).await {
+                Ok(()) => set_version.update(|v| *v += 1),
+                Err(e) => set_error(Some(e.to_string())),
+            }
+        });
+    };
+
+    view! {
+        <div class="container mx-auto px-4 pb-8 max-w-3xl space-y-4">
+            <div class="bg-white dark:bg-teal-800 rounded-lg p-4 text-sm text-gray-600 dark:text-gray-300 space-y-1">
+                <p>
+                    "The REST API lives under "<code>"/api/v1"</code>" ("
+                    <a href="/api/v1/openapi.json" class="underline text-teal-600 dark:text-aqua-400">"OpenAPI document"</a>
+                    "). Send a key as "<code>"Authorization: Bearer <key>"</code>"."
+                </p>
+            </div>
+            {move || {
+                if !is_authenticated() {
+                    return view! {
+                        <div class="text-center text-gray-600 dark:text-gray-300 p-6 bg-white dark:bg-teal-800 rounded-lg">
+                            <a href="/admin" class="text-teal-600 dark:text-aqua-400 underline">"Log in"</a>
+                            " to create API keys."
+                        </div>
+                    }
+                        .into_any();
+                }
+
+                view! {
+                    <div class="space-y-4">
+                        <form class="flex gap-2" on:submit=create>
+                            <input
+                                class="flex-1 p-2 rounded-md bg-gray-100 dark:bg-teal-700 text-gray-800 dark:text-gray-200 border border-teal-500 dark:border-seafoam-500"
+                                placeholder="Key name, e.g. nightly export script"
+                                prop:value=name
+                                on:input=move |ev| set_name(event_target_value(&ev))
+                            />
+                            <button
+                                type="submit"
+                                class="px-4 py-2 bg-seafoam-600 dark:bg-seafoam-500 text-white rounded-lg hover:bg-seafoam-700"
+                            >
+                                "Create key"
+                            </button>
+                        </form>
+                        {move || error.get().map(|e| view! { <p class="text-sm text-salmon-500">{e}</p> })}
+                        {move || {
+                            created_key
+                                .get()
+                                .map(|key| {
+                                    view! {
+                                        <div class="p-3 rounded-lg bg-seafoam-100 dark:bg-teal-700 text-sm space-y-1">
+                                            <p class="text-gray-700 dark:text-gray-200">
+                                                "Copy this key now, it won't be shown again:"
+                                            </p>
+                                            <code class="block break-all font-mono">{key}</code>
+                                        </div>
+                                    }
+                                })
+                        }}
+                        <Suspense fallback=|| ()>
+                            {move || {
+                                keys.get()
+                                    .and_then(|result| result.ok())
+                                    .map(|keys| {
+                                        view! {
+                                            <ul class="space-y-2">
+                                                {keys
+                                                    .into_iter()
+                                                    .map(|key| {
+                                                        let id = key.id;
+                                                        view! {
+                                                            <li class="bg-white dark:bg-teal-800 rounded-lg p-3 flex justify-between items-center text-sm">
+                                                                <div>
+                                                                    <span class="font-semibold text-teal-600 dark:text-mint-400">
+                                                                        {key.name}
+                                                                    </span>
+                                                                    <span class="ml-2 font-mono text-gray-500 dark:text-gray-400">
+                                                                        {format!("{}…", key.prefix)}
+                                                                    </span>
+                                                                    <p class="text-xs text-gray-500 dark:text-gray-400">
+                                                                        {match key.last_used_at {
+                                                                            Some(used) => format!("last used {}", used.format("%Y-%m-%d %H:%M")),
+                                                                            None => "never used".to_string(),
+                                                                        }}
+                                                                    </p>
+                                                                </div>
+                                                                {if key.revoked {
+                                                                    view! { <span class="text-xs text-gray-400">"revoked"</span> }.into_any()
+                                                                } else {
+                                                                    view! {
+                                                                        <button
+                                                                            class="text-xs px-2 py-1 rounded bg-salmon-500 text-white"
+                                                                            on:click=move |_| revoke(id)
+                                                                        >
+                                                                            "Revoke"
+                                                                        </button>
+                                                                    }
+                                                                        .into_any()
+                                                                }}
+                                                            </li>
+                                                        }
+                                                    })
+                                                    .collect_view()}
+                                            </ul>
+                                        }
+                                    })
+                            }}
+                        </Suspense>
+                    </div>
+                }
+                    .into_any()
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,339 @@
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+use server_fn::codec::GetUrl;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{ErrorEvent, EventSource, MessageEvent};
+
+use crate::auth::context::AuthContext;
+use crate::components::markdown::MarkdownRenderer;
+use crate::components::rag_chat::{
+    link_citation_markers, AnswerVerification, Citation, ContextUsage, MessageBubble, RagMessage, RagResponse,
+};
+use crate::types::StreamResponse;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AskQuota {
+    pub used: i32,
+    pub limit: i32,
+    pub max_question_chars: usize,
+}
+
+/// How many questions the signed-in user has asked today.
+#[server(
+    prefix = "/api",
+    endpoint = "get_ask_quota",
+    input = GetUrl
+)]
+pub async fn get_ask_quota() -> Result<AskQuota, ServerFnError> {
+    use crate::components::drawing_rooms::get_authenticated_user_id;
+    use crate::models::RagUsage;
+    use crate::rate_limit::{ASK_DAILY_QUOTA, ASK_MAX_QUESTION_CHARS};
+    use crate::state::AppState;
+
+    let user_id = get_authenticated_user_id()
+        .await
+        .map_err(|_| ServerFnError::new("Not authenticated"))?;
+
+    let app_state = use_context::<AppState>()
+        .expect("Failed to get AppState from context");
+    let mut conn = app_state.pool
+        .get()
+        .await
+        .map_err(|e| ServerFnError::new(format!("Pool error: {}", e)))?;
+
+    let used = RagUsage::used_on(user_id, chrono::Utc::now().date_naive(), &mut conn)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+
+    Ok(AskQuota {
+        used,
+        limit: ASK_DAILY_QUOTA,
+        max_question_chars: ASK_MAX_QUESTION_CHARS,
+    })
+}
+
+fn now_timestamp() -> String {
+    js_sys::Date::new_0().to_iso_string().as_string().unwrap_or_default()
+}
+
+/// Single-question "ask the blogs" page for any signed-in user. Answers aren't saved. Given a
+/// `post_id`, questions are answered from that post only.
+#[component]
+pub fn Ask(#[prop(optional)] post_id: Option<i32>) -> impl IntoView {
+    let auth = use_context::<AuthContext>();
+    let is_authenticated = move || auth.as_ref().is_some_and(|a| a.is_authenticated.get());
+
+    let (messages, set_messages) = signal::<Vec<RagMessage>>(Vec::new());
+    let (question, set_question) = signal(String::new());
+    let (is_loading, set_is_loading) = signal(false);
+    let (current_response, set_current_response) = signal(String::new());
+    let (current_citations, set_current_citations) = signal::<Vec<Citation>>(Vec::new());
+    let (current_verification, set_current_verification) = signal(Option::<AnswerVerification>::None);
+    let (current_context_usage, set_current_context_usage) = signal(Option::<ContextUsage>::None);
+    let (status_message, set_status_message) = signal(String::new());
+    let (quota_version, set_quota_version) = signal(0u32);
+    let quota = Resource::new(move || quota_version.get(), |_| get_ask_quota());
+
+    let finish = move || {
+        set_current_response(String::new());
+        set_current_citations(Vec::new());
+        set_current_verification(None);
+        set_current_context_usage(None);
+        set_status_message(String::new());
+        set_is_loading(false);
+        set_quota_version.update(|v| *v += 1);
+    };
+
+    let push_error = move |error: String| {
+        set_messages.update(|msgs| {
+            msgs.push(RagMessage {
+                role: "assistant".to_string(),
+                content: format!("Sorry, I couldn't answer that: {}", error),
+                citations: None,
+                timestamp: now_timestamp(),
+                verification: None,
+                context_usage: None,
+            })
+        });
+    };
+
+    let ask = move || {
+        let query = question.get().trim().to_string();
+        if query.is_empty() || is_loading.get() {
+            return;
+        }
+
+        set_is_loading(true);
+        set_messages.update(|msgs| {
+            msgs.push(RagMessage {
+                role: "user".to_string(),
+                content: query.clone(),
+                citations: None,
+                timestamp: now_timestamp(),
+                verification: None,
+                context_usage: None,
+            })
+        });
+        set_question(String::new());
+
+        let window = web_sys::window().unwrap();
+
+        wasm_bindgen_futures::spawn_local(async move {
+            let stream_id = match JsFuture::from(window.fetch_with_str("/api/create-stream")).await {
+                Ok(resp) => {
+                    let resp = resp.unchecked_into::<web_sys::Response>();
+                    match resp.json().map(JsFuture::from) {
+                        Ok(json) => json
+                            .await
+                            .ok()
+                            .and_then(|json| serde_wasm_bindgen::from_value::<StreamResponse>(json).ok())
+                            .map(|stream| stream.stream_id),
+                        Err(_) => None,
+                    }
+                }
+                Err(_) => None,
+            };
+            let Some(stream_id) = stream_id else {
+                push_error("couldn't reach the server".to_string());
+                finish();
+                return;
+            };
+
+            let mut url = format!(
+                "/api/ask-query?stream_id={}&query={}",
+                stream_id,
+                urlencoding::encode(&query)
+            );
+            if let Some(post_id) = post_id {
+                url.push_str(&format!("&post_id={}", post_id));
+            }
+            let event_source = match EventSource::new(&url) {
+                Ok(source) => source,
+                Err(_) => {
+                    push_error("couldn't reach the server".to_string());
+                    finish();
+                    return;
+                }
+            };
+            let event_source_clone = event_source.clone();
+
+            let on_message = Closure::wrap(Box::new(move |event: MessageEvent| {
+                let Some(data) = event.data().as_string() else { return };
+                let response = match serde_json::from_str::<RagResponse>(&data) {
+                    Ok(response) => response,
+                    Err(e) => {
+                        log::error!("Failed to parse ask response: {}", e);
+                        return;
+                    }
+                };
+
+                match response.message_type.as_str() {
+                    "status" => set_status_message(response.content.unwrap_or_default()),
+                    "citations" => set_current_citations(response.citations.unwrap_or_default()),
+                    "content" => {
+                        if let Some(content) = response.content {
+                            set_current_response.update(|resp| resp.push_str(&content));
+                        }
+                    }
+                    "verification" => set_current_verification(response.verification),
+                    "context_usage" => set_current_context_usage(response.context_usage),
+                    "error" => {
+                        push_error(response.content.unwrap_or_default());
+                        finish();
+                        event_source_clone.close();
+                    }
+                    "done" => {
+                        let citations = current_citations.get_untracked();
+                        set_messages.update(|msgs| {
+                            msgs.push(RagMessage {
+                                role: "assistant".to_string(),
+                                content: current_response.get_untracked(),
+                                citations: (!citations.is_empty()).then_some(citations),
+                                timestamp: now_timestamp(),
+                                verification: current_verification.get_untracked(),
+                                context_usage: current_context_usage.get_untracked(),
+                            })
+                        });
+                        finish();
+                        event_source_clone.close();
+                    }
+                    _ => {}
+                }
+            }) as Box<dyn FnMut(_)>);
+
+            let event_source_error = event_source.clone();
+            let on_error = Closure::wrap(Box::new(move |_: ErrorEvent| {
+                if is_loading.get_untracked() {
+                    push_error("the connection was lost".to_string());
+                    finish();
+                }
+                event_source_error.close();
+            }) as Box<dyn FnMut(_)>);
+
+            event_source.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+            event_source.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+            on_message.forget();
+            on_error.forget();
+        });
+    };
+
+    view! {
+        <div class="container mx-auto px-4 pb-8 max-w-4xl space-y-4">
+            {move || {
+                if !is_authenticated() {
+                    return view! {
+                        <div class="text-center text-gray-600 dark:text-gray-300 p-6 bg-white dark:bg-teal-800 rounded-lg">
+                            <a href="/admin" class="text-teal-600 dark:text-aqua-400 underline">"Log in"</a>
+                            {if post_id.is_some() {
+                                " to ask questions about this post."
+                            } else {
+                                " to ask questions about the blog posts."
+                            }}
+                        </div>
+                    }
+                        .into_any();
+                }
+
+                view! {
+                    <div class="space-y-4">
+                        <div class="space-y-3">
+                            <For
+                                each=move || messages.get()
+                                key=|msg| format!("{}_{}", msg.timestamp, msg.role)
+                                children=move |message| view! { <MessageBubble message=message /> }
+                            />
+                            {move || {
+                                let status = status_message.get();
+                                let current = current_response.get();
+                                let citations = current_citations.get();
+                                is_loading
+                                    .get()
+                                    .then(|| {
+                                        view! {
+                                            <div class="rounded-lg p-4 bg-gray-200 dark:bg-teal-900 border-l-4 border-aqua-500 dark:border-seafoam-400">
+                                                {(!status.is_empty())
+                                                    .then(|| {
+                                                        view! {
+                                                            <div class="text-sm text-seafoam-600 dark:text-mint-400 italic mb-2">
+                                                                {status}
+                                                            </div>
+                                                        }
+                                                    })}
+                                                <div class="prose prose-sm dark:prose-invert max-w-none">
+                                                    <MarkdownRenderer
+                                                        content=link_citation_markers(&current, &citations)
+                                                        class=""
+                                                    />
+                                                </div>
+                                            </div>
+                                        }
+                                    })
+                            }}
+                        </div>
+
+                        <div class="bg-white dark:bg-teal-800 rounded-lg p-4 space-y-2">
+                            <textarea
+                                class="w-full p-3 border border-seafoam-300 dark:border-mint-600 rounded-lg
+                                bg-seafoam-50 dark:bg-teal-700 text-seafoam-800 dark:text-mint-600
+                                focus:outline-none focus:ring-2 focus:ring-seafoam-500 dark:focus:ring-aqua-400 resize-none"
+                                placeholder=if post_id.is_some() {
+                                    "Ask a question about this post..."
+                                } else {
+                                    "Ask a question about the blog posts..."
+                                }
+                                rows="3"
+                                maxlength=move || {
+                                    quota
+                                        .get()
+                                        .and_then(|q| q.ok())
+                                        .map(|q| q.max_question_chars.to_string())
+                                }
+                                prop:value=question
+                                prop:disabled=is_loading
+                                on:input=move |ev| set_question(event_target_value(&ev))
+                                on:keydown=move |ev: web_sys::KeyboardEvent| {
+                                    if ev.key() == "Enter" && !ev.shift_key() {
+                                        ev.prevent_default();
+                                        ask();
+                                    }
+                                }
+                            ></textarea>
+                            <div class="flex items-center justify-between">
+                                <Suspense fallback=|| ()>
+                                    {move || {
+                                        quota
+                                            .get()
+                                            .and_then(|q| q.ok())
+                                            .map(|q| {
+                                                view! {
+                                                    <span class="text-xs text-gray-500 dark:text-gray-400">
+                                                        {format!(
+                                                            "{} of {} questions left today",
+                                                            (q.limit - q.used).max(0),
+                                                            q.limit,
+                                                        )}
+                                                    </span>
+                                                }
+                                            })
+                                    }}
+                                </Suspense>
+                                <button
+                                    class="px-6 py-2 bg-seafoam-600 dark:bg-seafoam-500 text-white rounded-lg
+                                    hover:bg-seafoam-700 dark:hover:bg-seafoam-600 transition-colors
+                                    disabled:bg-gray-400 dark:disabled:bg-gray-600 disabled:cursor-not-allowed"
+                                    on:click=move |_| ask()
+                                    prop:disabled=move || is_loading.get() || question.get().trim().is_empty()
+                                >
+                                    {move || if is_loading.get() { "Asking..." } else { "Ask" }}
+                                </button>
+                            </div>
+                        </div>
+                    </div>
+                }
+                    .into_any()
+            }}
+        </div>
+    }
+}
@@ -39,6 +39,12 @@ pub fn AuthNav() -> impl IntoView {
                 >
                     "github"
                 </a>
+                <a
+                    href="/api-keys"
+                    class="text-teal-600 dark:text-aqua-400 hover:text-teal-700 dark:hover:text-aqua-300 transition-colors duration-200"
+                >
+                    "api"
+                </a>
                 <ThemeSelector />
                 <DarkModeToggle />
                 {move || {
@@ -176,6 +182,13 @@ pub fn AuthNav() -> impl IntoView {
                                         </svg>
                                         <span class="font-medium">"GitHub"</span>
                                     </a>
+                                    <a
+                                        href="/api-keys"
+                                        class="flex items-center space-x-3 p-3 text-teal-600 dark:text-aqua-400 hover:text-teal-700 dark:hover:text-aqua-300 hover:bg-gray-100 dark:hover:bg-teal-700 rounded-md transition-colors duration-200 touch-manipulation"
+                                        on:click=move |_| set_mobile_menu_open.set(false)
+                                    >
+                                        <span class="font-medium">"API"</span>
+                                    </a>
 
                                     // Theme controls section
                                     <div class="border-t border-gray-200 dark:border-teal-700 pt-4">
@@ -0,0 +1,157 @@
+use leptos::prelude::*;
+use leptos_router::hooks::use_query_map;
+use server_fn::codec::GetUrl;
+
+use crate::models::{DigestSummaryView, DigestView};
+
+/// Most recent digests, newest window first.
+#[server(
+    prefix = "/api",
+    endpoint = "list_digests",
+    input = GetUrl
+)]
+pub async fn list_digests() -> Result<Vec<DigestSummaryView>, ServerFnError> {
+    use crate::models::Digest;
+    use crate::state::AppState;
+
+    let app_state = use_context::<AppState>()
+        .expect("Failed to get AppState from context");
+    let mut conn = app_state.pool
+        .get()
+        .await
+        .map_err(|e| ServerFnError::new(format!("Pool error: {}", e)))?;
+
+    let digests = Digest::list_recent(30, &mut conn)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+
+    Ok(digests.into_iter().map(DigestSummaryView::from).collect())
+}
+
+/// One digest by id, or the latest one when no id is given.
+#[server(
+    prefix = "/api",
+    endpoint = "get_digest",
+    input = GetUrl
+)]
+pub async fn get_digest(id: Option<i32>) -> Result<Option<DigestView>, ServerFnError> {
+    use crate::models::Digest;
+    use crate::state::AppState;
+
+    let app_state = use_context::<AppState>()
+        .expect("Failed to get AppState from context");
+    let mut conn = app_state.pool
+        .get()
+        .await
+        .map_err(|e| ServerFnError::new(format!("Pool error: {}", e)))?;
+
+    let digest = match id {
+        Some(id) => Digest::find(id, &mut conn).await,
+        None => Digest::latest(&mut conn).await,
+    }
+    .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+
+    Ok(digest.map(DigestView::from))
+}
+
+#[component]
+pub fn Digests() -> impl IntoView {
+    let query = use_query_map();
+    let selected = Memo::new(move |_| query.get().get("id").and_then(|id| id.parse::<i32>().ok()));
+
+    let digests = Resource::new(|| (), |_| list_digests());
+    let digest = Resource::new(move || selected.get(), get_digest);
+
+    view! {
+        <div class="container mx-auto px-4 pb-8 flex flex-col lg:flex-row gap-4">
+            <aside class="lg:w-64 shrink-0">
+                <Suspense fallback=|| ()>
+                    {move || {
+                        digests
+                            .get()
+                            .and_then(|result| result.ok())
+                            .map(|digests| {
+                                view! {
+                                    <ul class="space-y-1">
+                                        {digests
+                                            .into_iter()
+                                            .map(|summary| {
+                                                let id = summary.id;
+                                                let active = move || {
+                                                    digest
+                                                        .get()
+                                                        .and_then(|d| d.ok().flatten())
+                                                        .is_some_and(|d| d.id == id)
+                                                };
+                                                view! {
+                                                    <li>
+                                                        <a
+                                                            href=format!("/digest?id={}", id)
+                                                            class="block p-2 rounded-md text-sm hover:bg-white dark:hover:bg-teal-800"
+                                                            class=("bg-white", active)
+                                                            class=("dark:bg-teal-800", active)
+                                                        >
+                                                            <span class="block text-teal-600 dark:text-mint-400 font-medium">
+                                                                {summary.title}
+                                                            </span>
+                                                            <span class="block text-xs text-gray-500 dark:text-gray-400">
+                                                                {format!(
+                                                                    "{} posts · by {}",
+                                                                    summary.post_count,
+                                                                    summary.grouped_by,
+                                                                )}
+                                                            </span>
+                                                        </a>
+                                                    </li>
+                                                }
+                                            })
+                                            .collect_view()}
+                                    </ul>
+                                }
+                            })
+                    }}
+                </Suspense>
+            </aside>
+
+            <main class="flex-1 min-w-0">
+                <Suspense fallback=|| {
+                    view! { <p class="text-center text-teal-600 dark:text-aqua-400">"Loading..."</p> }
+                }>
+                    {move || {
+                        digest
+                            .get()
+                            .map(|result| match result {
+                                Ok(Some(digest)) => {
+                                    view! {
+                                        <article class="bg-white dark:bg-teal-800 p-6 rounded-lg shadow-md">
+                                            <div
+                                                class="prose dark:prose-invert max-w-none"
+                                                inner_html=digest.html
+                                            ></div>
+                                        </article>
+                                    }
+                                        .into_any()
+                                }
+                                Ok(None) => {
+                                    view! {
+                                        <div class="text-center text-gray-500 dark:text-gray-400">
+                                            "No digests yet. Run the generate_digest job first."
+                                        </div>
+                                    }
+                                        .into_any()
+                                }
+                                Err(e) => {
+                                    view! {
+                                        <div class="text-center text-salmon-500">
+                                            {format!("Error loading digest: {}", e)}
+                                        </div>
+                                    }
+                                        .into_any()
+                                }
+                            })
+                    }}
+                </Suspense>
+            </main>
+        </div>
+    }
+}
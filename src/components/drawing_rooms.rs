@@ -14,7 +14,7 @@ pub struct RoomListItem {
 }
 
 #[cfg(feature = "ssr")]
-async fn get_authenticated_user_id() -> Result<i32, crate::auth::AuthError> {
+pub(crate) async fn get_authenticated_user_id() -> Result<i32, crate::auth::AuthError> {
     use axum_extra::extract::cookie::CookieJar;
     use leptos_axum::extract;
     use crate::auth::verify_jwt_token;
@@ -1,4 +1,6 @@
 use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+use server_fn::codec::GetUrl;
 use wasm_bindgen::closure::Closure;
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::JsFuture;
@@ -10,6 +12,139 @@ use crate::{
     server_fn::RssProgressUpdate
 };
 
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct EmbeddingCounts {
+    pub current: usize,
+    pub stale: usize,
+    /// Vectors from before hashes were recorded; re-embedded on the next run.
+    pub untracked: usize,
+    pub missing: usize,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct EmbeddingStatus {
+    pub total_posts: usize,
+    pub openai: EmbeddingCounts,
+    pub minilm: EmbeddingCounts,
+}
+
+#[server(
+    prefix = "/api",
+    endpoint = "get_embedding_status",
+    input = GetUrl
+)]
+pub async fn get_embedding_status() -> Result<EmbeddingStatus, ServerFnError> {
+    use tokio_util::sync::CancellationToken;
+    use crate::embedding_service::embeddings::{
+        content_hash, embedding_state, embedding_text, fetch_all_posts, fetch_stored_embeddings,
+        EmbeddingColumn, EmbeddingState,
+    };
+
+    let posts = fetch_all_posts(&CancellationToken::new())
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to fetch posts: {}", e)))?
+        .unwrap_or_default();
+
+    let openai_stored = fetch_stored_embeddings(EmbeddingColumn::OpenAI)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to fetch embeddings: {}", e)))?;
+    let minilm_stored = fetch_stored_embeddings(EmbeddingColumn::MiniLm)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to fetch embeddings: {}", e)))?;
+
+    let mut status = EmbeddingStatus {
+        total_posts: posts.len(),
+        ..Default::default()
+    };
+
+    for post in &posts {
+        let hash = content_hash(&embedding_text(post));
+        for (column, stored, counts) in [
+            (EmbeddingColumn::OpenAI, &openai_stored, &mut status.openai),
+            (EmbeddingColumn::MiniLm, &minilm_stored, &mut status.minilm),
+        ] {
+            match embedding_state(stored.get(&post.link), &hash, column) {
+                EmbeddingState::Current => counts.current += 1,
+                EmbeddingState::Stale => counts.stale += 1,
+                EmbeddingState::Untracked => counts.untracked += 1,
+                EmbeddingState::Missing => counts.missing += 1,
+            }
+        }
+    }
+
+    Ok(status)
+}
+
+#[component]
+pub fn EmbeddingStatusPanel() -> impl IntoView {
+    let (refresh, set_refresh) = signal(0u32);
+    let status = Resource::new(move || refresh.get(), |_| get_embedding_status());
+
+    let row = |label: &'static str, counts: EmbeddingCounts| {
+        view! {
+            <div class="grid grid-cols-5 text-sm text-gray-600 dark:text-gray-300">
+                <span class="font-medium text-gray-800 dark:text-gray-200">{label}</span>
+                <span class="text-right">{counts.current}</span>
+                <span class=format!(
+                    "text-right {}",
+                    if counts.stale > 0 { "text-salmon-500 font-medium" } else { "" },
+                )>{counts.stale}</span>
+                <span class=format!(
+                    "text-right {}",
+                    if counts.untracked > 0 { "text-salmon-500 font-medium" } else { "" },
+                )>{counts.untracked}</span>
+                <span class="text-right">{counts.missing}</span>
+            </div>
+        }
+    };
+
+    view! {
+        <div class="p-4 space-y-4">
+            <div class="flex items-center justify-between">
+                <button
+                    class="px-4 py-2 bg-seafoam-500 dark:bg-seafoam-600 text-white rounded
+                    hover:bg-seafoam-400 dark:hover:bg-seafoam-500 transition-colors"
+                    on:click=move |_| set_refresh.update(|r| *r += 1)
+                >
+                    "Refresh"
+                </button>
+            </div>
+            <Suspense fallback=|| view! { <p class="text-gray-500 dark:text-gray-400">"Loading..."</p> }>
+                {move || {
+                    status
+                        .get()
+                        .map(|result| match result {
+                            Ok(status) => {
+                                view! {
+                                    <div class="p-4 rounded-lg border-l-4 bg-gray-100 dark:bg-teal-800 border-seafoam-500 dark:border-mint-400 space-y-2">
+                                        <div class="grid grid-cols-5 text-xs uppercase tracking-wide text-gray-500 dark:text-gray-400">
+                                            <span>{format!("{} posts", status.total_posts)}</span>
+                                            <span class="text-right">"Up to date"</span>
+                                            <span class="text-right">"Stale embeddings"</span>
+                                            <span class="text-right">"Untracked"</span>
+                                            <span class="text-right">"Missing"</span>
+                                        </div>
+                                        {row("OpenAI", status.openai)}
+                                        {row("MiniLM", status.minilm)}
+                                    </div>
+                                }
+                                    .into_any()
+                            }
+                            Err(e) => {
+                                view! {
+                                    <div class="text-salmon-500">
+                                        {format!("Failed to load embedding status: {}", e)}
+                                    </div>
+                                }
+                                    .into_any()
+                            }
+                        })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
 
 #[component]
 pub fn EmbeddingsProcessor() -> impl IntoView {
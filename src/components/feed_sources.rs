@@ -0,0 +1,928 @@
+use chrono::{DateTime, Utc};
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+use server_fn::codec::{GetUrl, PostUrl};
+
+// will need to run this in supabase. renaming or deleting a company goes through these functions
+// so its posts, embeddings and feed row change in one transaction
+#[cfg(feature = "ssr")]
+const _MIGRATION_SQL: &str = r#"
+ALTER TABLE links ADD COLUMN IF NOT EXISTS enabled boolean NOT NULL DEFAULT true;
+ALTER TABLE links ADD COLUMN IF NOT EXISTS entry_limit int;
+
+CREATE TABLE IF NOT EXISTS archived_poasts (LIKE poasts INCLUDING DEFAULTS);
+ALTER TABLE archived_poasts ADD COLUMN IF NOT EXISTS archived_at timestamptz NOT NULL DEFAULT now();
+
+CREATE OR REPLACE FUNCTION delete_company(company_name text, archive boolean)
+RETURNS int
+LANGUAGE plpgsql AS $$
+DECLARE
+    removed int;
+BEGIN
+    IF archive THEN
+        INSERT INTO archived_poasts SELECT p.*, now() FROM poasts p WHERE p.company = company_name;
+    END IF;
+    DELETE FROM post_embeddings WHERE link IN (SELECT link FROM poasts WHERE company = company_name);
+    DELETE FROM poasts WHERE company = company_name;
+    GET DIAGNOSTICS removed = ROW_COUNT;
+    DELETE FROM links WHERE company = company_name;
+    RETURN removed;
+END;
+$$;
+
+-- lets rename_company move the feed row and its posts in either order
+ALTER TABLE poasts ALTER CONSTRAINT posts_company_fkey DEFERRABLE INITIALLY IMMEDIATE;
+
+CREATE OR REPLACE FUNCTION rename_company(old_name text, new_name text)
+RETURNS int
+LANGUAGE plpgsql AS $$
+DECLARE
+    renamed int;
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM links WHERE company = old_name) THEN
+        RAISE EXCEPTION '% not found', old_name;
+    END IF;
+    IF EXISTS (SELECT 1 FROM links WHERE lower(company) = lower(new_name) AND company <> old_name) THEN
+        RAISE EXCEPTION '% already exists', new_name;
+    END IF;
+    SET CONSTRAINTS posts_company_fkey DEFERRED;
+    UPDATE links SET company = new_name WHERE company = old_name;
+    UPDATE poasts SET company = new_name WHERE company = old_name;
+    GET DIAGNOSTICS renamed = ROW_COUNT;
+    UPDATE archived_poasts SET company = new_name WHERE company = old_name;
+    RETURN renamed;
+END;
+$$;
+"#;
+
+/// Largest OPML document accepted for import.
+#[cfg(feature = "ssr")]
+const MAX_OPML_BYTES: usize = 1_000_000;
+/// Highest per-feed entry limit the panel accepts.
+pub const MAX_ENTRY_LIMIT: i32 = 100;
+#[cfg(feature = "ssr")]
+const FEED_FETCH_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(15);
+
+/// A company and its feed as edited in the admin panel. The company name is the key posts
+/// reference, so renaming goes through `rename_feed_source` rather than `update_feed_source`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct FeedSource {
+    pub company: String,
+    pub link: String,
+    pub logo_url: Option<String>,
+    pub enabled: bool,
+    /// Entries processed per ingestion run; `None` keeps the standard limits.
+    pub entry_limit: Option<i32>,
+    pub last_processed: Option<DateTime<Utc>>,
+}
+
+#[cfg(feature = "ssr")]
+impl From<crate::rss_service::server::FeedLink> for FeedSource {
+    fn from(feed: crate::rss_service::server::FeedLink) -> Self {
+        FeedSource {
+            company: feed.company,
+            link: feed.link,
+            logo_url: feed.logo_url,
+            enabled: feed.enabled,
+            entry_limit: feed.entry_limit,
+            last_processed: feed.last_processed,
+        }
+    }
+}
+
+/// What happens to a company's posts when it is deleted.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub enum DeleteMode {
+    /// Move them to `archived_poasts`.
+    Archive,
+    /// Delete them along with their embeddings.
+    Cascade,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct OpmlImportResult {
+    /// Companies created.
+    pub added: Vec<String>,
+    /// Outlines left out because their company or feed URL is already in the list.
+    pub skipped: Vec<String>,
+}
+
+/// Every row of the `links` table.
+#[cfg(feature = "ssr")]
+async fn fetch_feed_links() -> Result<Vec<crate::rss_service::server::FeedLink>, ServerFnError> {
+    let response = crate::supabase::get_client()
+        .from("links")
+        .select(crate::rss_service::server::FEED_LINK_COLUMNS)
+        .order("company")
+        .execute()
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    let text = response.text()
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    serde_json::from_str(&text)
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))
+}
+
+/// Create a feed for every outline in an OPML document whose company and feed URL aren't
+/// already in the list. Company names and URLs are compared case-insensitively.
+#[server(
+    prefix = "/api",
+    endpoint = "import_opml",
+    input = PostUrl
+)]
+pub async fn import_opml(opml: String) -> Result<OpmlImportResult, ServerFnError> {
+    use crate::auth::ensure_admin;
+    use crate::opml_service::opml::parse_opml;
+    use crate::server_fn::cache::COMPANIES_CACHE;
+    use std::collections::HashSet;
+
+    ensure_admin().await?;
+
+    if opml.len() > MAX_OPML_BYTES {
+        return Err(ServerFnError::new("OPML document is too large"));
+    }
+    let feeds = parse_opml(&opml).map_err(|e| ServerFnError::new(format!("Invalid OPML: {}", e)))?;
+    if feeds.is_empty() {
+        return Err(ServerFnError::new("No feeds found in the OPML document"));
+    }
+
+    let existing = fetch_feed_links().await?;
+    let mut companies: HashSet<String> = existing.iter().map(|feed| feed.company.to_lowercase()).collect();
+    let mut links: HashSet<String> = existing.iter().map(|feed| feed.link.trim().to_lowercase()).collect();
+
+    let mut result = OpmlImportResult { added: Vec::new(), skipped: Vec::new() };
+    let mut rows = Vec::new();
+    for feed in feeds {
+        // both sets are updated as we go, so duplicates within the document are skipped too
+        let new_company = companies.insert(feed.company.to_lowercase());
+        let new_link = links.insert(feed.link.trim().to_lowercase());
+        if new_company && new_link {
+            rows.push(serde_json::json!({ "company": feed.company, "link": feed.link }));
+            result.added.push(feed.company);
+        } else {
+            result.skipped.push(feed.company);
+        }
+    }
+
+    if !rows.is_empty() {
+        let response = crate::supabase::get_client()
+            .from("links")
+            .insert(serde_json::to_string(&rows)?)
+            .execute()
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        if !response.status().is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(ServerFnError::new(format!("Failed to add feeds: {}", body)));
+        }
+        COMPANIES_CACHE.clear();
+    }
+
+    log::info!("OPML import added {} feeds, skipped {}", result.added.len(), result.skipped.len());
+    Ok(result)
+}
+
+/// The current feed list as an OPML document.
+#[server(
+    prefix = "/api",
+    endpoint = "export_opml",
+    input = GetUrl
+)]
+pub async fn export_opml() -> Result<String, ServerFnError> {
+    use crate::auth::ensure_admin;
+    use crate::opml_service::opml::render_opml;
+
+    ensure_admin().await?;
+    let feeds = fetch_feed_links().await?;
+    Ok(render_opml(&feeds))
+}
+
+/// Trimmed copy of `source` with its URLs and entry limit checked.
+#[cfg(feature = "ssr")]
+fn normalized(source: FeedSource) -> Result<FeedSource, ServerFnError> {
+    let is_http = |url: &str| url.starts_with("https://") || url.starts_with("http://");
+
+    let company: String = source.company.trim().chars().take(255).collect();
+    if company.is_empty() {
+        return Err(ServerFnError::new("Company name is required"));
+    }
+    let link = source.link.trim().to_string();
+    if !is_http(&link) {
+        return Err(ServerFnError::new("Feed URL must start with http:// or https://"));
+    }
+    let logo_url = source.logo_url.map(|url| url.trim().to_string()).filter(|url| !url.is_empty());
+    if logo_url.as_deref().is_some_and(|url| !is_http(url)) {
+        return Err(ServerFnError::new("Logo URL must start with http:// or https://"));
+    }
+    if source.entry_limit.is_some_and(|limit| !(1..=MAX_ENTRY_LIMIT).contains(&limit)) {
+        return Err(ServerFnError::new(format!("Entry limit must be between 1 and {}", MAX_ENTRY_LIMIT)));
+    }
+
+    Ok(FeedSource { company, link, logo_url, ..source })
+}
+
+/// Fetch and parse the feed the way ingestion does, returning how many entries it has.
+#[cfg(feature = "ssr")]
+async fn validate_feed(link: &str) -> Result<usize, ServerFnError> {
+    let response = reqwest::Client::new()
+        .get(link)
+        .header("User-Agent", "Mozilla/5.0 (compatible; BlogBot/1.0)")
+        .timeout(FEED_FETCH_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| ServerFnError::new(format!("Couldn't fetch feed: {}", e)))?;
+    if !response.status().is_success() {
+        return Err(ServerFnError::new(format!("Couldn't fetch feed: HTTP {}", response.status())));
+    }
+
+    let content = response.bytes()
+        .await
+        .map_err(|e| ServerFnError::new(format!("Couldn't read feed: {}", e)))?;
+    let feed = feed_rs::parser::parse(&content[..])
+        .map_err(|e| ServerFnError::new(format!("Not a valid RSS or Atom feed: {}", e)))?;
+
+    Ok(feed.entries.len())
+}
+
+/// Drop everything cached about `company`, including the company list.
+#[cfg(feature = "ssr")]
+async fn invalidate_company(company: &str) -> Result<(), ServerFnError> {
+    use crate::server_fn::cache::COMPANIES_CACHE;
+    use crate::server_fn::invalidate_poasts_cache;
+
+    COMPANIES_CACHE.clear();
+    invalidate_poasts_cache(Some(company.to_string())).await
+}
+
+/// Ids and links of every post `company` has on the site, paged past PostgREST's row cap.
+#[cfg(feature = "ssr")]
+async fn company_posts(company: &str) -> Result<(Vec<i32>, Vec<String>), ServerFnError> {
+    #[derive(Deserialize)]
+    struct PostRef {
+        id: i32,
+        link: String,
+    }
+
+    const PAGE: usize = 1000;
+    let (mut ids, mut links) = (Vec::new(), Vec::new());
+    loop {
+        let response = crate::supabase::get_client()
+            .from("poasts")
+            .select("id,link")
+            .eq("company", company)
+            .order("id.asc")
+            .range(ids.len(), ids.len() + PAGE - 1)
+            .execute()
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        let text = response.text()
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        let page: Vec<PostRef> = serde_json::from_str(&text)
+            .map_err(|e| ServerFnError::ServerError(e.to_string()))?;
+
+        let full = page.len() == PAGE;
+        for post in page {
+            ids.push(post.id);
+            links.push(post.link);
+        }
+        if !full {
+            return Ok((ids, links));
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+async fn local_connection() -> Result<diesel_async::pooled_connection::deadpool::Object<diesel_async::AsyncPgConnection>, ServerFnError> {
+    use crate::state::AppState;
+
+    let app_state = use_context::<AppState>()
+        .expect("Failed to get AppState from context");
+    app_state.pool
+        .get()
+        .await
+        .map_err(|e| ServerFnError::new(format!("Pool error: {}", e)))
+}
+
+/// Bookmarks, reads, reading list entries, webhook deliveries and follows that pointed at a
+/// deleted company's posts. These live in our database, so the Supabase delete can't reach them.
+#[cfg(feature = "ssr")]
+async fn remove_local_rows(company: &str, post_ids: &[i32], links: &[String]) -> Result<usize, ServerFnError> {
+    use crate::schema::{followed_companies, post_bookmarks, post_reads, reading_list_items, webhook_deliveries};
+    use diesel::prelude::*;
+    use diesel_async::RunQueryDsl;
+
+    let mut conn = local_connection().await?;
+    let db_error = |e: diesel::result::Error| ServerFnError::new(format!("Database error: {}", e));
+
+    let mut removed = diesel::delete(post_bookmarks::table.filter(post_bookmarks::post_id.eq_any(post_ids)))
+        .execute(&mut conn)
+        .await
+        .map_err(db_error)?;
+    removed += diesel::delete(post_reads::table.filter(post_reads::post_id.eq_any(post_ids)))
+        .execute(&mut conn)
+        .await
+        .map_err(db_error)?;
+    removed += diesel::delete(reading_list_items::table.filter(reading_list_items::post_id.eq_any(post_ids)))
+        .execute(&mut conn)
+        .await
+        .map_err(db_error)?;
+    removed += diesel::delete(webhook_deliveries::table.filter(webhook_deliveries::post_link.eq_any(links)))
+        .execute(&mut conn)
+        .await
+        .map_err(db_error)?;
+    removed += diesel::delete(followed_companies::table.filter(followed_companies::company.eq(company)))
+        .execute(&mut conn)
+        .await
+        .map_err(db_error)?;
+    Ok(removed)
+}
+
+/// Point follows and company saved searches at the new name.
+#[cfg(feature = "ssr")]
+async fn rename_local_rows(old_name: &str, new_name: &str) -> Result<(), ServerFnError> {
+    use crate::schema::{followed_companies, saved_searches};
+    use diesel::prelude::*;
+    use diesel_async::RunQueryDsl;
+
+    let mut conn = local_connection().await?;
+    let db_error = |e: diesel::result::Error| ServerFnError::new(format!("Database error: {}", e));
+
+    diesel::update(followed_companies::table.filter(followed_companies::company.eq(old_name)))
+        .set(followed_companies::company.eq(new_name))
+        .execute(&mut conn)
+        .await
+        .map_err(db_error)?;
+
+    // company searches match case-insensitively, so find them the same way
+    let searches: Vec<(i32, String)> = saved_searches::table
+        .filter(saved_searches::kind.eq("company"))
+        .select((saved_searches::id, saved_searches::query))
+        .load(&mut conn)
+        .await
+        .map_err(db_error)?;
+    let ids: Vec<i32> = searches
+        .into_iter()
+        .filter(|(_, query)| query.trim().eq_ignore_ascii_case(old_name.trim()))
+        .map(|(id, _)| id)
+        .collect();
+    diesel::update(saved_searches::table.filter(saved_searches::id.eq_any(&ids)))
+        .set(saved_searches::query.eq(new_name))
+        .execute(&mut conn)
+        .await
+        .map_err(db_error)?;
+    Ok(())
+}
+
+#[server(
+    prefix = "/api",
+    endpoint = "list_feed_sources",
+    input = GetUrl
+)]
+pub async fn list_feed_sources() -> Result<Vec<FeedSource>, ServerFnError> {
+    use crate::auth::ensure_admin;
+
+    ensure_admin().await?;
+    let feeds = fetch_feed_links().await?;
+    Ok(feeds.into_iter().map(FeedSource::from).collect())
+}
+
+/// Add a company and its feed after checking the feed parses. Returns the feed's entry count.
+#[server(
+    prefix = "/api",
+    endpoint = "create_feed_source",
+    input = PostUrl
+)]
+pub async fn create_feed_source(source: FeedSource) -> Result<usize, ServerFnError> {
+    use crate::auth::ensure_admin;
+
+    ensure_admin().await?;
+    let source = normalized(source)?;
+
+    let existing = fetch_feed_links().await?;
+    if existing.iter().any(|feed| feed.company.eq_ignore_ascii_case(&source.company)) {
+        return Err(ServerFnError::new(format!("{} already exists", source.company)));
+    }
+    if let Some(feed) = existing.iter().find(|feed| feed.link.trim().eq_ignore_ascii_case(&source.link)) {
+        return Err(ServerFnError::new(format!("That feed already belongs to {}", feed.company)));
+    }
+
+    let entries = validate_feed(&source.link).await?;
+
+    let row = serde_json::json!({
+        "company": source.company,
+        "link": source.link,
+        "logo_url": source.logo_url,
+        "enabled": source.enabled,
+        "entry_limit": source.entry_limit,
+    });
+    let response = crate::supabase::get_client()
+        .from("links")
+        .insert(row.to_string())
+        .execute()
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    if !response.status().is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(ServerFnError::new(format!("Failed to add feed: {}", body)));
+    }
+
+    invalidate_company(&source.company).await?;
+    log::info!("Added feed for {} ({} entries)", source.company, entries);
+    Ok(entries)
+}
+
+/// Update a company's feed URL, logo, enabled flag and entry limit. The feed is re-validated
+/// when its URL changes.
+#[server(
+    prefix = "/api",
+    endpoint = "update_feed_source",
+    input = PostUrl
+)]
+pub async fn update_feed_source(source: FeedSource) -> Result<(), ServerFnError> {
+    use crate::auth::ensure_admin;
+
+    ensure_admin().await?;
+    let source = normalized(source)?;
+
+    let existing = fetch_feed_links().await?;
+    let Some(current) = existing.iter().find(|feed| feed.company == source.company) else {
+        return Err(ServerFnError::new(format!("{} not found", source.company)));
+    };
+    if current.link != source.link {
+        if let Some(feed) = existing.iter().find(|feed| {
+            feed.company != source.company && feed.link.trim().eq_ignore_ascii_case(&source.link)
+        }) {
+            return Err(ServerFnError::new(format!("That feed already belongs to {}", feed.company)));
+        }
+        validate_feed(&source.link).await?;
+    }
+
+    let changes = serde_json::json!({
+        "link": source.link,
+        "logo_url": source.logo_url,
+        "enabled": source.enabled,
+        "entry_limit": source.entry_limit,
+    });
+    let response = crate::supabase::get_client()
+        .from("links")
+        .update(changes.to_string())
+        .eq("company", &source.company)
+        .execute()
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    if !response.status().is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(ServerFnError::new(format!("Failed to update feed: {}", body)));
+    }
+
+    invalidate_company(&source.company).await
+}
+
+/// Rename a company across its feed row and posts. Returns how many posts moved to the new name.
+#[server(
+    prefix = "/api",
+    endpoint = "rename_feed_source",
+    input = PostUrl
+)]
+pub async fn rename_feed_source(company: String, new_name: String) -> Result<i64, ServerFnError> {
+    use crate::auth::ensure_admin;
+
+    ensure_admin().await?;
+
+    let new_name: String = new_name.trim().chars().take(255).collect();
+    if new_name.is_empty() {
+        return Err(ServerFnError::new("Company name is required"));
+    }
+    if new_name == company {
+        return Ok(0);
+    }
+
+    let response = crate::supabase::get_client()
+        .rpc("rename_company", serde_json::json!({
+            "old_name": company,
+            "new_name": new_name,
+        }).to_string())
+        .execute()
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    let status = response.status();
+    let body = response.text()
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    if !status.is_success() {
+        return Err(ServerFnError::new(format!("Failed to rename {}: {}", company, body)));
+    }
+    let renamed: i64 = serde_json::from_str(&body)
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?;
+
+    invalidate_company(&company).await?;
+    invalidate_company(&new_name).await?;
+    rename_local_rows(&company, &new_name).await?;
+    log::info!("Renamed {} to {}, {} posts moved", company, new_name, renamed);
+    Ok(renamed)
+}
+
+/// Remove a company and its feed, archiving or deleting its posts. Returns how many posts
+/// were removed from the site.
+#[server(
+    prefix = "/api",
+    endpoint = "delete_feed_source",
+    input = PostUrl
+)]
+pub async fn delete_feed_source(company: String, mode: DeleteMode) -> Result<i64, ServerFnError> {
+    use crate::auth::ensure_admin;
+
+    ensure_admin().await?;
+
+    // gathered first, since the rows that say which local ids were this company's are about to go
+    let (post_ids, links) = company_posts(&company).await?;
+
+    let response = crate::supabase::get_client()
+        .rpc("delete_company", serde_json::json!({
+            "company_name": company,
+            "archive": mode == DeleteMode::Archive,
+        }).to_string())
+        .execute()
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    let status = response.status();
+    let body = response.text()
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    if !status.is_success() {
+        return Err(ServerFnError::new(format!("Failed to delete {}: {}", company, body)));
+    }
+    let removed: i64 = serde_json::from_str(&body)
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?;
+
+    invalidate_company(&company).await?;
+    let local = remove_local_rows(&company, &post_ids, &links).await?;
+    tokio::spawn(async {
+        use crate::vector_search_service::vector_search::reconcile_ann_indexes;
+
+        if let Err(e) = reconcile_ann_indexes().await {
+            log::error!("Failed to reconcile ANN indexes after a company delete: {}", e);
+        }
+    });
+    log::info!("Deleted {} ({:?}), {} posts and {} local rows removed", company, mode, removed, local);
+    Ok(removed)
+}
+
+/// Import feeds from another reader's OPML export, or export ours.
+#[component]
+pub fn OpmlTransfer() -> impl IntoView {
+    let (opml, set_opml) = signal(String::new());
+    let (importing, set_importing) = signal(false);
+    let (import_result, set_import_result) = signal(Option::<OpmlImportResult>::None);
+    let (export_url, set_export_url) = signal(Option::<String>::None);
+    let (error, set_error) = signal(Option::<String>::None);
+
+    let import = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        set_error(None);
+        set_import_result(None);
+        set_importing(true);
+        let document = opml.get_untracked();
+        wasm_bindgen_futures::spawn_local(async move {
+            match import_opml(document).await {
+                Ok(result) => {
+                    set_import_result(Some(result));
+                    set_opml(String::new());
+                }
+                Err(e) => set_error(Some(e.to_string())),
+            }
+            set_importing(false);
+        });
+    };
+
+    let export = move |_| {
+        set_error(None);
+        wasm_bindgen_futures::spawn_local(async move {
+            match export_opml().await {
+                Ok(document) => set_export_url(Some(format!(
+                    "data:text/x-opml;charset=utf-8,{}",
+                    urlencoding::encode(&document)
+                ))),
+                Err(e) => set_error(Some(e.to_string())),
+            }
+        });
+    };
+
+    view! {
+        <div class="space-y-4">
+            <div class="flex flex-wrap items-center gap-3">
+                <button
+                    class="px-4 py-2 bg-seafoam-600 dark:bg-seafoam-500 text-white rounded-lg hover:bg-seafoam-700"
+                    on:click=export
+                >
+                    "Export OPML"
+                </button>
+                {move || {
+                    export_url
+                        .get()
+                        .map(|url| {
+                            view! {
+                                <a href=url download="feeds.opml" class="text-sm underline text-teal-600 dark:text-aqua-400">
+                                    "Download feeds.opml"
+                                </a>
+                            }
+                        })
+                }}
+            </div>
+
+            <form class="space-y-2" on:submit=import>
+                <textarea
+                    class="w-full h-40 p-2 rounded-md font-mono text-xs bg-gray-100 dark:bg-teal-700 text-gray-800 dark:text-gray-200 border border-teal-500 dark:border-seafoam-500"
+                    placeholder="Paste an OPML document to import its feeds"
+                    prop:value=opml
+                    on:input=move |ev| set_opml(event_target_value(&ev))
+                ></textarea>
+                <button
+                    type="submit"
+                    disabled=move || importing.get() || opml.get().trim().is_empty()
+                    class="px-4 py-2 bg-seafoam-600 dark:bg-seafoam-500 text-white rounded-lg hover:bg-seafoam-700 disabled:opacity-50"
+                >
+                    {move || if importing.get() { "Importing..." } else { "Import OPML" }}
+                </button>
+            </form>
+
+            {move || error.get().map(|e| view! { <p class="text-sm text-salmon-500">{e}</p> })}
+            {move || {
+                import_result
+                    .get()
+                    .map(|result| {
+                        view! {
+                            <div class="text-sm text-gray-700 dark:text-gray-200 space-y-1">
+                                <p>{format!("Added {} feeds, skipped {} already in the list.", result.added.len(), result.skipped.len())}</p>
+                                {(!result.added.is_empty())
+                                    .then(|| {
+                                        view! { <p class="text-xs text-gray-500 dark:text-gray-400">{format!("Added: {}", result.added.join(", "))}</p> }
+                                    })}
+                                {(!result.skipped.is_empty())
+                                    .then(|| {
+                                        view! { <p class="text-xs text-gray-500 dark:text-gray-400">{format!("Skipped: {}", result.skipped.join(", "))}</p> }
+                                    })}
+                            </div>
+                        }
+                    })
+            }}
+        </div>
+    }
+}
+
+/// Add, edit, disable and delete companies and their feeds.
+#[component]
+pub fn FeedSources() -> impl IntoView {
+    let (version, set_version) = signal(0u32);
+    let sources = Resource::new(move || version.get(), |_| list_feed_sources());
+    let refresh = Callback::new(move |_: ()| set_version.update(|v| *v += 1));
+
+    let (draft, set_draft) = signal(FeedSource { enabled: true, ..Default::default() });
+    let (saving, set_saving) = signal(false);
+    let (message, set_message) = signal(Option::<String>::None);
+    let (error, set_error) = signal(Option::<String>::None);
+
+    let create = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        set_error(None);
+        set_message(None);
+        set_saving(true);
+        let source = draft.get_untracked();
+        wasm_bindgen_futures::spawn_local(async move {
+            match create_feed_source(source.clone()).await {
+                Ok(entries) => {
+                    set_message(Some(format!("Added {} ({} entries in the feed)", source.company, entries)));
+                    set_draft(FeedSource { enabled: true, ..Default::default() });
+                    refresh.run(());
+                }
+                Err(e) => set_error(Some(e.to_string())),
+            }
+            set_saving(false);
+        });
+    };
+
+    let input_class = "p-2 rounded-md bg-gray-100 dark:bg-teal-700 text-gray-800 dark:text-gray-200 border border-teal-500 dark:border-seafoam-500";
+
+    view! {
+        <div class="space-y-4">
+            <form class="flex flex-wrap items-center gap-2" on:submit=create>
+                <input
+                    class=format!("w-40 {}", input_class)
+                    placeholder="Company"
+                    prop:value=move || draft.get().company
+                    on:input=move |ev| set_draft.update(|d| d.company = event_target_value(&ev))
+                />
+                <input
+                    class=format!("flex-1 min-w-64 {}", input_class)
+                    placeholder="Feed URL"
+                    prop:value=move || draft.get().link
+                    on:input=move |ev| set_draft.update(|d| d.link = event_target_value(&ev))
+                />
+                <input
+                    class=format!("w-56 {}", input_class)
+                    placeholder="Logo URL (optional)"
+                    prop:value=move || draft.get().logo_url.unwrap_or_default()
+                    on:input=move |ev| set_draft.update(|d| d.logo_url = Some(event_target_value(&ev)))
+                />
+                <input
+                    type="number"
+                    min="1"
+                    max=MAX_ENTRY_LIMIT
+                    class=format!("w-28 {}", input_class)
+                    placeholder="Entry limit"
+                    prop:value=move || draft.get().entry_limit.map(|l| l.to_string()).unwrap_or_default()
+                    on:input=move |ev| set_draft.update(|d| d.entry_limit = event_target_value(&ev).parse().ok())
+                />
+                <label class="flex items-center gap-1 text-sm text-gray-600 dark:text-gray-300">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || draft.get().enabled
+                        on:change=move |ev| set_draft.update(|d| d.enabled = event_target_checked(&ev))
+                    />
+                    "Enabled"
+                </label>
+                <button
+                    type="submit"
+                    disabled=saving
+                    class="px-4 py-2 bg-seafoam-600 dark:bg-seafoam-500 text-white rounded-lg hover:bg-seafoam-700 disabled:opacity-50"
+                >
+                    {move || if saving.get() { "Checking feed..." } else { "Add feed" }}
+                </button>
+            </form>
+            {move || message.get().map(|m| view! { <p class="text-sm text-seafoam-600 dark:text-mint-400">{m}</p> })}
+            {move || error.get().map(|e| view! { <p class="text-sm text-salmon-500">{e}</p> })}
+
+            <Suspense fallback=|| view! { <p class="text-sm text-gray-500">"Loading feeds..."</p> }>
+                {move || {
+                    sources
+                        .get()
+                        .map(|result| match result {
+                            Ok(sources) => {
+                                view! {
+                                    <ul class="space-y-2">
+                                        {sources
+                                            .into_iter()
+                                            .map(|source| view! { <FeedSourceRow source=source on_change=refresh /> })
+                                            .collect_view()}
+                                    </ul>
+                                }
+                                    .into_any()
+                            }
+                            Err(e) => view! { <p class="text-sm text-salmon-500">{e.to_string()}</p> }.into_any(),
+                        })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+fn FeedSourceRow(source: FeedSource, on_change: Callback<()>) -> impl IntoView {
+    let company = source.company.clone();
+    let last_processed = source.last_processed;
+    let (edited, set_edited) = signal(source);
+    let (name, set_name) = signal(company.clone());
+    let (busy, set_busy) = signal(false);
+    let (error, set_error) = signal(Option::<String>::None);
+
+    let save = move |_| {
+        set_error(None);
+        set_busy(true);
+        let source = edited.get_untracked();
+        wasm_bindgen_futures::spawn_local(async move {
+            match update_feed_source(source).await {
+                Ok(()) => on_change.run(()),
+                Err(e) => set_error(Some(e.to_string())),
+            }
+            set_busy(false);
+        });
+    };
+
+    let renamed = {
+        let company = company.clone();
+        move || name.get().trim() != company
+    };
+    let rename = {
+        let company = company.clone();
+        move |_| {
+            set_error(None);
+            set_busy(true);
+            let company = company.clone();
+            let new_name = name.get_untracked();
+            wasm_bindgen_futures::spawn_local(async move {
+                match rename_feed_source(company, new_name).await {
+                    Ok(_) => on_change.run(()),
+                    Err(e) => set_error(Some(e.to_string())),
+                }
+                set_busy(false);
+            });
+        }
+    };
+
+    let delete = {
+        let company = company.clone();
+        move |mode: DeleteMode| {
+            let prompt = match mode {
+                DeleteMode::Archive => format!("Delete {} and archive its posts?", company),
+                DeleteMode::Cascade => format!("Delete {} and permanently delete all of its posts?", company),
+            };
+            let confirmed = web_sys::window()
+                .and_then(|window| window.confirm_with_message(&prompt).ok())
+                .unwrap_or(false);
+            if !confirmed {
+                return;
+            }
+
+            set_error(None);
+            set_busy(true);
+            let company = company.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match delete_feed_source(company, mode).await {
+                    Ok(_) => on_change.run(()),
+                    Err(e) => set_error(Some(e.to_string())),
+                }
+                set_busy(false);
+            });
+        }
+    };
+    let archive = {
+        let delete = delete.clone();
+        move |_| delete(DeleteMode::Archive)
+    };
+    let cascade = move |_| delete(DeleteMode::Cascade);
+
+    let input_class = "p-1 rounded-md text-sm bg-gray-100 dark:bg-teal-700 text-gray-800 dark:text-gray-200 border border-teal-500 dark:border-seafoam-500";
+
+    view! {
+        <li class="bg-white dark:bg-teal-800 rounded-lg p-3 space-y-2">
+            <div class="flex flex-wrap items-center gap-2">
+                <input
+                    class=format!("w-40 font-semibold {}", input_class)
+                    prop:value=name
+                    on:input=move |ev| set_name(event_target_value(&ev))
+                />
+                <input
+                    class=format!("flex-1 min-w-64 {}", input_class)
+                    prop:value=move || edited.get().link
+                    on:input=move |ev| set_edited.update(|s| s.link = event_target_value(&ev))
+                />
+                <input
+                    class=format!("w-56 {}", input_class)
+                    placeholder="Logo URL"
+                    prop:value=move || edited.get().logo_url.unwrap_or_default()
+                    on:input=move |ev| set_edited.update(|s| s.logo_url = Some(event_target_value(&ev)))
+                />
+                <input
+                    type="number"
+                    min="1"
+                    max=MAX_ENTRY_LIMIT
+                    class=format!("w-24 {}", input_class)
+                    placeholder="Limit"
+                    prop:value=move || edited.get().entry_limit.map(|l| l.to_string()).unwrap_or_default()
+                    on:input=move |ev| set_edited.update(|s| s.entry_limit = event_target_value(&ev).parse().ok())
+                />
+                <label class="flex items-center gap-1 text-sm text-gray-600 dark:text-gray-300">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || edited.get().enabled
+                        on:change=move |ev| set_edited.update(|s| s.enabled = event_target_checked(&ev))
+                    />
+                    "Enabled"
+                </label>
+            </div>
+            <div class="flex flex-wrap items-center gap-2 text-xs">
+                <span class="text-gray-500 dark:text-gray-400">
+                    {match last_processed {
+                        Some(at) => format!("last processed {}", at.format("%Y-%m-%d %H:%M")),
+                        None => "never processed".to_string(),
+                    }}
+                </span>
+                <button
+                    class="px-2 py-1 rounded bg-seafoam-600 dark:bg-seafoam-500 text-white disabled:opacity-50"
+                    disabled=busy
+                    on:click=save
+                >
+                    "Save"
+                </button>
+                <button
+                    class="px-2 py-1 rounded bg-seafoam-600 dark:bg-seafoam-500 text-white disabled:opacity-50"
+                    disabled=move || busy.get() || !renamed()
+                    on:click=rename
+                >
+                    "Rename"
+                </button>
+                <button class="px-2 py-1 rounded bg-gray-500 text-white disabled:opacity-50" disabled=busy on:click=archive>
+                    "Delete, archive posts"
+                </button>
+                <button class="px-2 py-1 rounded bg-salmon-500 text-white disabled:opacity-50" disabled=busy on:click=cascade>
+                    "Delete with posts"
+                </button>
+                {move || error.get().map(|e| view! { <span class="text-salmon-500">{e}</span> })}
+            </div>
+        </li>
+    }
+}
@@ -0,0 +1,378 @@
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+use server_fn::codec::{GetUrl, PostUrl};
+
+use crate::components::poasts::{BlogPoast, Poast, PostPage};
+
+/// A page of the "for you" feed. `personalized` is false when the user hasn't bookmarked, read
+/// or followed anything yet and the page is the plain chronological listing.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ForYouPage {
+    pub page: PostPage,
+    pub personalized: bool,
+}
+
+/// Where the next personalized page starts: an offset into the ranking the previous page came
+/// from. The ranking is named by the engagement fingerprint it was built for, so reading a post
+/// mid-scroll doesn't re-rank the pages still to come.
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ForYouCursor {
+    ranking: u64,
+    offset: usize,
+}
+
+#[cfg(feature = "ssr")]
+impl ForYouCursor {
+    fn encode(&self) -> String {
+        format!("f:{:x}|{}", self.ranking, self.offset)
+    }
+
+    fn decode(cursor: &str) -> Option<Self> {
+        let (ranking, offset) = cursor.strip_prefix("f:")?.split_once('|')?;
+        Some(ForYouCursor {
+            ranking: u64::from_str_radix(ranking, 16).ok()?,
+            offset: offset.parse().ok()?,
+        })
+    }
+}
+
+/// The signed-in user's id and a pooled connection.
+#[cfg(feature = "ssr")]
+async fn follow_user_connection() -> Result<
+    (i32, diesel_async::pooled_connection::deadpool::Object<diesel_async::AsyncPgConnection>),
+    ServerFnError,
+> {
+    use crate::components::drawing_rooms::get_authenticated_user_id;
+    use crate::state::AppState;
+
+    let user_id = get_authenticated_user_id()
+        .await
+        .map_err(|_| ServerFnError::new("Not authenticated"))?;
+
+    let app_state = use_context::<AppState>()
+        .expect("Failed to get AppState from context");
+    let conn = app_state.pool
+        .get()
+        .await
+        .map_err(|e| ServerFnError::new(format!("Pool error: {}", e)))?;
+
+    Ok((user_id, conn))
+}
+
+/// Posts ranked for the signed-in user. The whole ranking is cached per user and engagement and
+/// paged with a `ForYouCursor`. A first page ranks for the current engagement; later pages keep
+/// paging the ranking their cursor names while it's cached, dropping posts read since.
+#[server(
+    prefix = "/api",
+    endpoint = "get_for_you_posts",
+    input = GetUrl
+)]
+pub async fn get_for_you_posts(cursor: Option<String>) -> Result<ForYouPage, ServerFnError> {
+    use crate::components::poasts::{search_poasts, PostFilter, DEFAULT_PAGE_SIZE};
+    use crate::components::search::SearchType;
+    use crate::models::{FollowedCompany, PostBookmark, PostRead};
+    use crate::recommendation_service::recommendations::{rank_for_user, Engagement};
+    use crate::server_fn::cache::{post_list_tags, FOR_YOU_CACHE};
+
+    let (user_id, mut conn) = follow_user_connection().await?;
+    let engagement = Engagement {
+        bookmarked: PostBookmark::post_ids_for_user(user_id, &mut conn)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?,
+        read: PostRead::post_ids_for_user(user_id, &mut conn)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?,
+        followed_companies: FollowedCompany::companies_for_user(user_id, &mut conn)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?,
+    };
+    drop(conn);
+
+    if engagement.is_empty() {
+        let filter = PostFilter { cursor, ..Default::default() };
+        let page = search_poasts(filter, SearchType::Basic).await?;
+        return Ok(ForYouPage { page, personalized: false });
+    }
+
+    let cursor = match cursor.as_deref() {
+        Some(cursor) => Some(ForYouCursor::decode(cursor).ok_or_else(|| ServerFnError::new("Invalid cursor"))?),
+        None => None,
+    };
+    let key = |ranking: u64| format!("{}:{:x}", user_id, ranking);
+    let read: std::collections::HashSet<i32> = engagement.read.iter().copied().collect();
+
+    let frozen = cursor.and_then(|cursor| FOR_YOU_CACHE.get(&key(cursor.ranking)).map(|ranked| (cursor, ranked)));
+    let (ranking, offset, ranked) = match frozen {
+        Some((cursor, ranked)) => (cursor.ranking, cursor.offset, ranked),
+        // evicted or invalidated by new posts: carry on at the same offset in a fresh ranking
+        None => {
+            let ranking = engagement.fingerprint();
+            let ranked = FOR_YOU_CACHE
+                .get_or_fetch(key(ranking), post_list_tags(&[]), move || rank_for_user(engagement))
+                .await
+                .map_err(|e| ServerFnError::new(format!("Ranking failed: {}", e)))?;
+            (ranking, cursor.map_or(0, |cursor| cursor.offset), ranked)
+        }
+    };
+
+    let end = (offset + DEFAULT_PAGE_SIZE).min(ranked.len());
+    let posts = ranked
+        .get(offset..end)
+        .map(<[Poast]>::to_vec)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|post| !read.contains(&post.id))
+        .collect();
+    let next_cursor = (end < ranked.len()).then(|| ForYouCursor { ranking, offset: end }.encode());
+
+    Ok(ForYouPage {
+        page: PostPage { posts, next_cursor },
+        personalized: true,
+    })
+}
+
+#[server(
+    prefix = "/api",
+    endpoint = "list_followed_companies",
+    input = GetUrl
+)]
+pub async fn list_followed_companies() -> Result<Vec<String>, ServerFnError> {
+    use crate::models::FollowedCompany;
+
+    let (user_id, mut conn) = follow_user_connection().await?;
+    FollowedCompany::companies_for_user(user_id, &mut conn)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))
+}
+
+#[server(
+    prefix = "/api",
+    endpoint = "set_company_followed",
+    input = PostUrl
+)]
+pub async fn set_company_followed(company: String, followed: bool) -> Result<(), ServerFnError> {
+    use crate::models::{FollowedCompany, MAX_FOLLOWED_COMPANIES};
+
+    let company: String = company.trim().chars().take(255).collect();
+    if company.is_empty() {
+        return Err(ServerFnError::new("No company given"));
+    }
+
+    let (user_id, mut conn) = follow_user_connection().await?;
+    if followed {
+        let following = FollowedCompany::count_for_user(user_id, &mut conn)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+        if following >= MAX_FOLLOWED_COMPANIES {
+            return Err(ServerFnError::new(format!("You can follow at most {} companies", MAX_FOLLOWED_COMPANIES)));
+        }
+    }
+
+    FollowedCompany::set(user_id, &company, followed, &mut conn)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+    Ok(())
+}
+
+/// Follow/unfollow toggle for one company. Bumps `version` after a change so the caller can
+/// refetch whatever depends on follows.
+#[component]
+pub fn FollowButton(
+    #[prop(into)] company: Signal<String>,
+    followed: Resource<Result<Vec<String>, ServerFnError>>,
+    set_version: WriteSignal<u32>,
+) -> impl IntoView {
+    let (error, set_error) = signal(Option::<String>::None);
+    let is_followed = move || {
+        followed
+            .get()
+            .and_then(|result| result.ok())
+            .is_some_and(|companies| companies.contains(&company.get()))
+    };
+
+    let toggle = move |_| {
+        let name = company.get_untracked();
+        let follow = !is_followed();
+        set_error(None);
+        wasm_bindgen_futures::spawn_local(async move {
+            match set_company_followed(name, follow).await {
+                Ok(()) => set_version.update(|v| *v += 1),
+                Err(e) => set_error(Some(e.to_string())),
+            }
+        });
+    };
+
+    view! {
+        <button
+            class="text-sm px-3 py-1 rounded-full border border-seafoam-500 text-seafoam-600 dark:text-aqua-400 hover:bg-seafoam-100 dark:hover:bg-teal-700"
+            on:click=toggle
+        >
+            {move || if is_followed() { "Unfollow" } else { "Follow" }}
+        </button>
+        {move || error.get().map(|e| view! { <span class="text-xs text-salmon-500">{e}</span> })}
+    }
+}
+
+/// The signed-in user's ranked feed, with the companies they follow above it.
+#[component]
+pub fn ForYou(
+    followed: Resource<Result<Vec<String>, ServerFnError>>,
+    set_version: WriteSignal<u32>,
+    #[prop(into)] version: Signal<u32>,
+) -> impl IntoView {
+    let feed = Resource::new(move || version.get(), |_| get_for_you_posts(None));
+
+    let (more_posts, set_more_posts) = signal(Vec::<Poast>::new());
+    let (next_cursor, set_next_cursor) = signal(None::<String>);
+    let (loading_more, set_loading_more) = signal(false);
+
+    Effect::new(move |_| {
+        if let Some(Ok(feed)) = feed.get() {
+            set_more_posts.set(Vec::new());
+            set_next_cursor.set(feed.page.next_cursor);
+        }
+    });
+
+    let load_more = move |_| {
+        let Some(cursor) = next_cursor.get_untracked() else {
+            return;
+        };
+        set_loading_more.set(true);
+        leptos::task::spawn_local(async move {
+            match get_for_you_posts(Some(cursor)).await {
+                Ok(feed) => {
+                    set_more_posts.update(|posts| posts.extend(feed.page.posts));
+                    set_next_cursor.set(feed.page.next_cursor);
+                }
+                Err(e) => log::error!("Error loading more posts: {:?}", e),
+            }
+            set_loading_more.set(false);
+        });
+    };
+
+    let unfollow = move |company: String| {
+        wasm_bindgen_futures::spawn_local(async move {
+            match set_company_followed(company, false).await {
+                Ok(()) => set_version.update(|v| *v += 1),
+                Err(e) => log::error!("Error unfollowing company: {:?}", e),
+            }
+        });
+    };
+
+    view! {
+        <div class="space-y-4">
+            <Suspense fallback=|| ()>
+                {move || {
+                    followed
+                        .get()
+                        .and_then(|result| result.ok())
+                        .filter(|companies| !companies.is_empty())
+                        .map(|companies| {
+                            view! {
+                                <div class="flex flex-wrap items-center gap-2 pl-4 text-sm">
+                                    <span class="text-gray-600 dark:text-gray-300">"Following:"</span>
+                                    {companies
+                                        .into_iter()
+                                        .map(|company| {
+                                            let name = company.clone();
+                                            view! {
+                                                <span class="px-2 py-0.5 rounded-full bg-seafoam-100 dark:bg-teal-700 text-seafoam-700 dark:text-mint-400">
+                                                    {company}
+                                                    <button
+                                                        class="ml-1 text-gray-500 hover:text-salmon-500"
+                                                        title="Unfollow"
+                                                        on:click=move |_| unfollow(name.clone())
+                                                    >
+                                                        "×"
+                                                    </button>
+                                                </span>
+                                            }
+                                        })
+                                        .collect_view()}
+                                </div>
+                            }
+                        })
+                }}
+            </Suspense>
+
+            <Suspense fallback=|| {
+                view! { <p class="text-center text-teal-600 dark:text-aqua-400">"Loading..."</p> }
+            }>
+                {move || {
+                    match feed.get() {
+                        Some(Ok(feed)) => {
+                            let hint = (!feed.personalized)
+                                .then(|| {
+                                    view! {
+                                        <p class="text-center text-sm text-gray-600 dark:text-gray-300">
+                                            "Bookmark, read or follow a few companies' posts and this feed will be ranked for you. Until then it shows the latest posts."
+                                        </p>
+                                    }
+                                });
+                            let mut posts = feed.page.posts;
+                            posts.extend(more_posts.get());
+                            if posts.is_empty() {
+                                view! {
+                                    <div class="text-center text-gray-500 dark:text-gray-400">
+                                        "You're all caught up"
+                                    </div>
+                                }
+                                    .into_any()
+                            } else {
+                                view! {
+                                    {hint}
+                                    <div class="grid grid-cols-1 sm:grid-cols-2 lg:grid-cols-3 xl:grid-cols-4 gap-4">
+                                        <For
+                                            each=move || posts.clone()
+                                            key=|poast| poast.id
+                                            children=move |poast| view! { <BlogPoast poast=poast /> }
+                                        />
+                                    </div>
+                                    {move || {
+                                        next_cursor
+                                            .get()
+                                            .map(|_| {
+                                                view! {
+                                                    <div class="flex justify-center pb-6">
+                                                        <button
+                                                            on:click=load_more
+                                                            disabled=loading_more
+                                                            class="px-4 py-2 rounded-md bg-seafoam-600 dark:bg-aqua-600 text-white hover:bg-seafoam-700 dark:hover:bg-aqua-700 disabled:opacity-50 transition-colors"
+                                                        >
+                                                            {move || if loading_more.get() { "Loading..." } else { "Load more" }}
+                                                        </button>
+                                                    </div>
+                                                }
+                                            })
+                                    }}
+                                }
+                                    .into_any()
+                            }
+                        }
+                        Some(Err(e)) => {
+                            log::error!("Error loading feed: {:?}", e);
+                            view! { <div class="text-center text-salmon-500">"Error loading posts"</div> }.into_any()
+                        }
+                        None => view! { <div class="text-center text-gray-500 dark:text-gray-400">"Loading..."</div> }.into_any(),
+                    }
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_you_cursor_roundtrip() {
+        let cursor = ForYouCursor { ranking: 0xdead_beef_0123, offset: 60 };
+        assert_eq!(ForYouCursor::decode(&cursor.encode()), Some(cursor));
+
+        assert_eq!(ForYouCursor::decode("o:60"), None);
+        assert_eq!(ForYouCursor::decode("f:zz|60"), None);
+        assert_eq!(ForYouCursor::decode("f:12|-1"), None);
+    }
+}
@@ -1,20 +1,29 @@
+pub mod api_keys;
+pub mod ask;
 pub mod auth_nav;
 pub mod batch_processor;
 pub mod canvas;
 pub mod canvas_sync;
 pub mod dark_mode_toggle;
+pub mod digest;
 pub mod drawing;
 pub mod drawing_rooms;
 pub mod embeddings;
+pub mod feed_sources;
 pub mod footer;
+pub mod for_you;
 pub mod local_embeddings;
 pub mod markdown;
 pub mod poasts;
+pub mod post_detail;
 pub mod rag_chat;
+pub mod reading_list;
 pub mod room_browser;
 pub mod room_page;
 pub mod rss_test;
+pub mod saved_searches;
 pub mod search;
 pub mod summary_refresh_processor;
 pub mod theme_selector;
+pub mod trends;
 pub mod user_avatar;
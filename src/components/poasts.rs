@@ -3,6 +3,9 @@ use serde::{Serialize, Deserialize};
 use server_fn::codec::GetUrl;
 use std::borrow::Cow;
 
+use crate::auth::context::AuthContext;
+use crate::components::for_you::{list_followed_companies, FollowButton, ForYou};
+use crate::components::reading_list::ReadingControls;
 use crate::components::search::{BlogSearch, SearchParams, SearchType};
 
 #[cfg(feature = "hydrate")]
@@ -38,12 +41,29 @@ pub struct Links {
     pub logo_url: Option<String>,
 }
 
+/// A post with everything its page shows.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PostDetail {
+    #[serde(flatten)]
+    pub post: Poast,
+    pub buzzwords: Option<Vec<String>>,
+}
+
 #[server(
     prefix = "/api",
     endpoint = "get_companies",
     input = GetUrl
 )]
 pub async fn get_companies() -> Result<Vec<String>, ServerFnError> {
+    use crate::server_fn::cache::COMPANIES_CACHE;
+
+    COMPANIES_CACHE
+        .get_or_fetch("companies".to_string(), vec!["companies".to_string()], fetch_companies)
+        .await
+}
+
+#[cfg(feature = "ssr")]
+async fn fetch_companies() -> Result<Vec<String>, ServerFnError> {
     use crate::supabase::get_client;
     use log::{debug, error};
     use std::fmt;
@@ -103,142 +123,497 @@ pub async fn get_companies() -> Result<Vec<String>, ServerFnError> {
     Ok(company_names)
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PostFilter {
     pub search_term: Option<String>,
     pub company: Option<String>,
+    /// Matches any of these companies, in addition to `company`.
+    pub companies: Vec<String>,
+    /// Inclusive lower bound on `published_at` (YYYY-MM-DD or a full timestamp).
+    pub published_after: Option<String>,
+    /// Exclusive upper bound on `published_at`.
+    pub published_before: Option<String>,
+    /// Only posts whose buzzwords contain this tag.
+    pub tag: Option<String>,
+    /// Semantic searches only: drop results below this similarity percentage.
+    pub min_similarity: Option<i32>,
+    /// Only posts the signed-in user hasn't marked as read.
+    pub unread_only: bool,
+    /// Only posts the signed-in user has bookmarked.
+    pub bookmarked_only: bool,
+    /// Opaque cursor from a previous `PostPage::next_cursor`.
+    pub cursor: Option<String>,
+    pub limit: Option<usize>,
 }
 
-#[server(
-    prefix = "/api",
-    endpoint = "get_poasts",
-    input = GetUrl
-)]
-pub async fn get_poasts(filter: Option<PostFilter>) -> Result<Vec<Poast>, ServerFnError> {
-    use crate::supabase::get_client;
-    use serde_json::from_str;
-    use std::fmt;
-    use log::{debug, info, error};
-    use std::time::Instant;
-    use crate::server_fn::cache::{POASTS_CACHE, CACHE_DURATION};
+pub const DEFAULT_PAGE_SIZE: usize = 30;
+pub const MAX_PAGE_SIZE: usize = 100;
 
-    #[derive(Debug)]
-    enum PoastError {
-        RequestError(String),
-        JsonParseError(String),
+impl PostFilter {
+    fn non_blank(value: &Option<String>) -> Option<&str> {
+        value.as_deref().map(str::trim).filter(|v| !v.is_empty())
     }
-    
-    impl fmt::Display for PoastError {
-        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-            match self {
-                PoastError::RequestError(e) => write!(f, "reqwest error: {}", e),
-                PoastError::JsonParseError(e) => write!(f, "JSON parse error: {}", e),
-            }
-        }
+
+    pub fn search_term(&self) -> Option<&str> {
+        Self::non_blank(&self.search_term)
     }
-    
-    fn to_server_error(e: PoastError) -> ServerFnError {
-        ServerFnError::ServerError(e.to_string())
+
+    pub fn company_set(&self) -> Vec<String> {
+        let mut companies: Vec<String> = self
+            .companies
+            .iter()
+            .map(|c| c.trim().to_string())
+            .chain(Self::non_blank(&self.company).map(String::from))
+            .filter(|c| !c.is_empty())
+            .collect();
+        companies.sort();
+        companies.dedup();
+        companies
     }
 
-    // check cache only if no search term is provided nor company filter is active
-    if let Some(filter) = &filter {
-        if filter.search_term.is_none() && filter.company.is_none() {
-            let cache_duration = CACHE_DURATION;
-            let cached_data = POASTS_CACHE.lock().unwrap().clone();
+    pub fn page_size(&self) -> usize {
+        self.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE)
+    }
 
-            if let (Some(cached_poasts), last_fetch) = cached_data {
-                if last_fetch.elapsed() < cache_duration {
-                    info!("Returning cached poasts");
-                    info!("Cache debug: {:?}", (cached_poasts.len(), last_fetch));
-                    return Ok(cached_poasts);
-                }
-            }
+    /// True when this is the default front-page listing.
+    pub fn is_unfiltered(&self) -> bool {
+        self.search_term().is_none()
+            && self.company_set().is_empty()
+            && Self::non_blank(&self.published_after).is_none()
+            && Self::non_blank(&self.published_before).is_none()
+            && Self::non_blank(&self.tag).is_none()
+            && !self.unread_only
+            && !self.bookmarked_only
+            && self.cursor.is_none()
+            && self.page_size() == DEFAULT_PAGE_SIZE
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostPage {
+    pub posts: Vec<Poast>,
+    pub next_cursor: Option<String>,
+}
+
+/// Where the next page starts. Date listings resume after `(published_at, id)`, ranked
+/// searches at an offset into the ranking. The encoded form is tagged so a cursor from one
+/// kind of listing is rejected by the other instead of being misread.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PostCursor {
+    Keyset { published_at: chrono::DateTime<chrono::Utc>, id: i32 },
+    Offset(usize),
+}
+
+impl PostCursor {
+    pub fn encode(&self) -> String {
+        match self {
+            PostCursor::Keyset { published_at, id } => format!(
+                "k:{}|{}",
+                published_at.to_rfc3339_opts(chrono::SecondsFormat::Micros, true),
+                id
+            ),
+            PostCursor::Offset(offset) => format!("o:{}", offset),
         }
-    } else {
-        // No filter at all, definitely use cache
-        let cache_duration = CACHE_DURATION;
-        let cached_data = POASTS_CACHE.lock().unwrap().clone();
-
-        if let (Some(cached_poasts), last_fetch) = cached_data {
-            if last_fetch.elapsed() < cache_duration {
-                info!("Returning cached poasts");
-                info!("Cache debug: {:?}", (cached_poasts.len(), last_fetch));
-                return Ok(cached_poasts);
-            }
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        if let Some(offset) = cursor.strip_prefix("o:") {
+            return offset.parse().ok().map(PostCursor::Offset);
         }
+        let (published_at, id) = cursor.strip_prefix("k:")?.rsplit_once('|')?;
+        Some(PostCursor::Keyset {
+            published_at: chrono::DateTime::parse_from_rfc3339(published_at).ok()?.with_timezone(&chrono::Utc),
+            id: id.parse().ok()?,
+        })
     }
 
-    info!("fetching blog poasts from supabase...");
-    let client = get_client();
+    /// The cursor for the page after `post`, or None if its date can't be read.
+    #[cfg(feature = "ssr")]
+    pub(crate) fn after(post: &Poast) -> Option<Self> {
+        parse_published(&post.published_at)
+            .map(|published_at| PostCursor::Keyset { published_at, id: post.id })
+    }
+}
 
-    let mut request = client
-        .from("poasts")
-        .select("id, published_at, company, title, link, summary, links!posts_company_fkey(logo_url)")
-        .order("published_at.desc")
-        .limit(30);
-
-    if let Some(ref filter) = filter {
-        if let Some(ref term) = filter.search_term {
-            if !term.trim().is_empty() {
-                debug!("Searching for term: {}", term);
-                request = request.or(format!(
-                        "title.ilike.%{}%,summary.ilike.%{}%",
-                        term, term
-                ));
-            }
+/// A post's `published_at`, which is RFC 3339, a naive timestamp or a bare date depending on
+/// how the row was written, as a UTC instant.
+#[cfg(feature = "ssr")]
+pub(crate) fn parse_published(published_at: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+
+    DateTime::parse_from_rfc3339(published_at)
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            NaiveDateTime::parse_from_str(published_at, "%Y-%m-%dT%H:%M:%S%.f")
+                .ok()
+                .map(|dt| dt.and_utc())
+        })
+        .or_else(|| {
+            NaiveDate::parse_from_str(published_at, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+                .map(|dt| dt.and_utc())
+        })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidCursor;
+
+impl std::fmt::Display for InvalidCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Invalid cursor")
+    }
+}
+
+impl PostFilter {
+    /// Where a date listing resumes; an offset cursor is as invalid here as a malformed one.
+    pub fn keyset_cursor(&self) -> Result<Option<(chrono::DateTime<chrono::Utc>, i32)>, InvalidCursor> {
+        match self.cursor.as_deref().map(PostCursor::decode) {
+            None => Ok(None),
+            Some(Some(PostCursor::Keyset { published_at, id })) => Ok(Some((published_at, id))),
+            Some(_) => Err(InvalidCursor),
         }
+    }
 
-        if let Some(ref company) = filter.company.clone().filter(|c| !c.trim().is_empty()) {
-            debug!("Filtering by company: {}", company);
-            request = request.eq("company", company);
-        } else {
-            debug!("No company filter applied - showing all companies");
+    /// Where a ranked listing resumes; a keyset cursor is as invalid here as a malformed one.
+    pub fn offset_cursor(&self) -> Result<usize, InvalidCursor> {
+        match self.cursor.as_deref().map(PostCursor::decode) {
+            None => Ok(0),
+            Some(Some(PostCursor::Offset(offset))) => Ok(offset),
+            Some(_) => Err(InvalidCursor),
         }
     }
+}
 
-    let response = request
-        .execute()
+/// Id and company restrictions the server adds to a `PostFilter`. Kept out of `PostFilter` so
+/// clients of the public endpoints can't send them.
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub(crate) struct PostScope {
+    /// Only these post ids.
+    pub post_ids: Option<Vec<i32>>,
+    /// None of these post ids.
+    pub exclude_post_ids: Vec<i32>,
+    /// None of these companies.
+    pub exclude_companies: Vec<String>,
+}
+
+#[cfg(feature = "ssr")]
+impl PostScope {
+    pub fn is_empty(&self) -> bool {
+        self.post_ids.is_none() && self.exclude_post_ids.is_empty() && self.exclude_companies.is_empty()
+    }
+
+    /// Stands in for the scope in cache keys, which would otherwise grow with a user's bookmarks.
+    pub fn fingerprint(&self) -> String {
+        use sha2::{Digest, Sha256};
+
+        let mut hasher = Sha256::new();
+        hasher.update(serde_json::to_vec(self).unwrap_or_default());
+        format!("{:x}", hasher.finalize())
+    }
+}
+
+/// Most post ids sent in one `id=in.(...)` filter. Longer lists, like a heavy bookmarker's,
+/// are queried a chunk at a time.
+#[cfg(feature = "ssr")]
+const SCOPE_ID_CHUNK: usize = 200;
+
+#[cfg(feature = "ssr")]
+const POAST_COLUMNS: &str = "id, published_at, company, title, link, summary, links!posts_company_fkey(logo_url)";
+
+/// Company, date range, tag and scope filters shared by every search path.
+#[cfg(feature = "ssr")]
+fn apply_post_filters(mut request: postgrest::Builder, filter: &PostFilter, scope: &PostScope) -> postgrest::Builder {
+    use log::debug;
+
+    let companies = filter.company_set();
+    match companies.len() {
+        0 => debug!("No company filter applied - showing all companies"),
+        1 => {
+            debug!("Filtering by company: {}", companies[0]);
+            request = request.eq("company", &companies[0]);
+        }
+        _ => {
+            debug!("Filtering by companies: {:?}", companies);
+            request = request.in_("company", &companies);
+        }
+    }
+
+    if let Some(after) = PostFilter::non_blank(&filter.published_after) {
+        request = request.gte("published_at", after);
+    }
+    if let Some(before) = PostFilter::non_blank(&filter.published_before) {
+        request = request.lt("published_at", before);
+    }
+    if let Some(tag) = PostFilter::non_blank(&filter.tag) {
+        // array literal elements are quoted and escaped the same way as filter values
+        let element = crate::supabase::quoted_filter_value(tag);
+        request = request.cs("buzzwords", format!("{{{}}}", element));
+    }
+    if let Some(ids) = &scope.post_ids {
+        request = request.in_("id", ids.iter().map(i32::to_string));
+    }
+    if !scope.exclude_post_ids.is_empty() {
+        let ids: Vec<String> = scope.exclude_post_ids.iter().map(i32::to_string).collect();
+        request = request.not("in", "id", format!("({})", ids.join(",")));
+    }
+    for company in &scope.exclude_companies {
+        request = request.neq("company", company);
+    }
+
+    request
+}
+
+/// Read posts excluded in the posts query itself. The rest of a heavy reader's history is
+/// dropped from each page after fetching, so the request URL and cache key stay bounded.
+#[cfg(feature = "ssr")]
+const READ_EXCLUDE_WINDOW: usize = 100;
+
+/// Turn `unread_only` and `bookmarked_only` into a scope for the signed-in user, so the rest
+/// of the search (and its cache key) only sees plain filters. Also returns the read posts
+/// beyond `READ_EXCLUDE_WINDOW`, which the caller drops from the fetched page.
+#[cfg(feature = "ssr")]
+async fn resolve_reading_filters(
+    filter: &mut PostFilter,
+) -> Result<(PostScope, std::collections::HashSet<i32>), ServerFnError> {
+    use crate::components::drawing_rooms::get_authenticated_user_id;
+    use crate::models::{PostBookmark, PostRead};
+    use crate::state::AppState;
+
+    let mut scope = PostScope::default();
+    let mut hidden = std::collections::HashSet::new();
+    if !filter.unread_only && !filter.bookmarked_only {
+        return Ok((scope, hidden));
+    }
+
+    let user_id = get_authenticated_user_id()
         .await
-        .map_err(|e| {
-            error!("supabase request error: {}", e);
-            PoastError::RequestError(e.to_string())
-        }).map_err(to_server_error)?;
+        .map_err(|_| ServerFnError::new("Log in to filter by bookmarks or read posts"))?;
+
+    let app_state = use_context::<AppState>()
+        .expect("Failed to get AppState from context");
+    let mut conn = app_state.pool
+        .get()
+        .await
+        .map_err(|e| ServerFnError::new(format!("Pool error: {}", e)))?;
+
+    if std::mem::take(&mut filter.bookmarked_only) {
+        let mut bookmarked = PostBookmark::post_ids_for_user(user_id, &mut conn)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+        bookmarked.sort_unstable();
+        scope.post_ids = Some(bookmarked);
+    }
+    if std::mem::take(&mut filter.unread_only) {
+        let mut read = PostRead::post_ids_for_user(user_id, &mut conn)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+        // ids grow with ingestion, so the newest read posts, the ones most likely to be on
+        // the page, are excluded by the query
+        read.sort_unstable_by(|a, b| b.cmp(a));
+        let older = read.split_off(read.len().min(READ_EXCLUDE_WINDOW));
+        read.sort_unstable();
+        read.dedup();
+        scope.exclude_post_ids = read;
+        hidden.extend(older);
+    }
+
+    Ok((scope, hidden))
+}
+
+/// Newest-first listing with keyset pagination on `(published_at, id)`.
+#[cfg(feature = "ssr")]
+pub(crate) async fn fetch_post_page(filter: &PostFilter, scope: &PostScope) -> Result<PostPage, ServerFnError> {
+    use log::info;
+
+    let limit = filter.page_size();
+    let mut posts = match scope.post_ids.as_deref() {
+        // the newest `limit + 1` of each chunk together hold the newest `limit + 1` overall
+        Some(ids) if ids.len() > SCOPE_ID_CHUNK => {
+            let mut merged = Vec::new();
+            for chunk in ids.chunks(SCOPE_ID_CHUNK) {
+                let chunk_scope = PostScope { post_ids: Some(chunk.to_vec()), ..scope.clone() };
+                merged.extend(fetch_post_rows(filter, &chunk_scope, limit + 1).await?);
+            }
+            merged.sort_by(|a, b| {
+                (parse_published(&b.published_at), b.id).cmp(&(parse_published(&a.published_at), a.id))
+            });
+            merged
+        }
+        _ => fetch_post_rows(filter, scope, limit + 1).await?,
+    };
+
+    let next_cursor = if posts.len() > limit {
+        posts.truncate(limit);
+        posts.last().and_then(PostCursor::after).map(|cursor| cursor.encode())
+    } else {
+        None
+    };
+
+    info!("successfully parsed {} poasts", posts.len());
+    Ok(PostPage { posts, next_cursor })
+}
+
+/// Up to `limit` posts matching the filters, scope and cursor, newest first.
+#[cfg(feature = "ssr")]
+async fn fetch_post_rows(filter: &PostFilter, scope: &PostScope, limit: usize) -> Result<Vec<Poast>, ServerFnError> {
+    use crate::supabase::get_client;
+    use log::{debug, error};
+
+    let mut request = apply_post_filters(
+        get_client()
+            .from("poasts")
+            .select(POAST_COLUMNS)
+            .order("published_at.desc,id.desc")
+            .limit(limit),
+        filter,
+        scope,
+    );
+
+    let mut or_groups = Vec::new();
+    if let Some(term) = filter.search_term() {
+        debug!("Searching for term: {}", term);
+        let pattern = crate::supabase::quoted_filter_value(&format!("%{}%", term));
+        or_groups.push(format!("title.ilike.{0},summary.ilike.{0}", pattern));
+    }
+    if let Some((published_at, id)) = filter.keyset_cursor().map_err(|e| ServerFnError::new(e.to_string()))? {
+        or_groups.push(format!(
+            "published_at.lt.\"{0}\",and(published_at.eq.\"{0}\",id.lt.{1})",
+            published_at.to_rfc3339_opts(chrono::SecondsFormat::Micros, true),
+            id
+        ));
+    }
+    request = match or_groups.len() {
+        0 => request,
+        1 => request.or(or_groups.remove(0)),
+        _ => request.and(or_groups.iter().map(|g| format!("or({})", g)).collect::<Vec<_>>().join(",")),
+    };
+
+    let response = request.execute().await.map_err(|e| {
+        error!("supabase request error: {}", e);
+        ServerFnError::new(format!("reqwest error: {}", e))
+    })?;
 
-    debug!("received response from Supabase");
     debug!("response status: {:?}", response.status());
-    
     let body = response.text().await.map_err(|e| {
         error!("error reading response body: {}", e);
-        PoastError::RequestError(e.to_string())
-    }).map_err(to_server_error)?;
-
-    debug!("response body length: {}", body.len());
+        ServerFnError::new(format!("reqwest error: {}", e))
+    })?;
 
     if body.trim().is_empty() {
         error!("empty response from Supabase");
         return Err(ServerFnError::ServerError("empty response from Supabase".to_string()));
     }
 
-    let poasts: Vec<Poast> = from_str(&body).map_err(|e| {
+    serde_json::from_str(&body).map_err(|e| {
         error!("JSON parse error: {}. Body: {}", e, body);
-        PoastError::JsonParseError(format!("failed to parse JSON: {}", e))
-    }).map_err(to_server_error)?;
+        ServerFnError::new(format!("JSON parse error: failed to parse JSON: {}", e))
+    })
+}
 
-    info!("successfully parsed {} poasts", poasts.len());
+/// One post by id, with its description, full text and buzzwords.
+#[cfg(feature = "ssr")]
+pub(crate) async fn fetch_post(id: i32) -> Result<Option<PostDetail>, ServerFnError> {
+    use crate::supabase::get_client;
+    use log::error;
 
-    // update cache
-    if let Some(filter) = &filter {
-        if filter.search_term.is_none() && filter.company.is_none(){ 
-            let mut cache = POASTS_CACHE.lock().unwrap();
-            *cache = (Some(poasts.clone()), Instant::now());
+    let body = get_client()
+        .from("poasts")
+        .select("id, published_at, company, title, link, description, summary, full_text, buzzwords, links!posts_company_fkey(logo_url)")
+        .eq("id", id.to_string())
+        .execute()
+        .await?
+        .text()
+        .await?;
+
+    let posts: Vec<PostDetail> = serde_json::from_str(&body).map_err(|e| {
+        error!("JSON parse error for post {}: {}", id, e);
+        ServerFnError::new(format!("JSON parse error: failed to parse JSON: {}", e))
+    })?;
+    Ok(posts.into_iter().next())
+}
+
+#[server(
+    prefix = "/api",
+    endpoint = "get_post",
+    input = GetUrl
+)]
+pub async fn get_post(id: i32) -> Result<Option<PostDetail>, ServerFnError> {
+    fetch_post(id).await
+}
+
+#[server(
+    prefix = "/api",
+    endpoint = "get_poasts",
+    input = GetUrl
+)]
+pub async fn get_poasts(filter: Option<PostFilter>) -> Result<Vec<Poast>, ServerFnError> {
+    use crate::server_fn::cache::{post_list_tags, POASTS_CACHE};
+    use log::info;
+
+    let mut filter = filter.unwrap_or_default();
+    let (scope, hidden) = resolve_reading_filters(&mut filter).await?;
+    let key = serde_json::to_string(&(&filter, scope.fingerprint()))?;
+    let tags = post_list_tags(&filter.company_set());
+
+    let mut posts = POASTS_CACHE
+        .get_or_fetch(key, tags, move || async move {
+            info!("fetching blog poasts from supabase...");
+            fetch_post_page(&filter, &scope).await.map(|page| page.posts)
+        })
+        .await?;
+    posts.retain(|post| !hidden.contains(&post.id));
+    Ok(posts)
+}
+
+/// One page of results for any search type. Semantic and hybrid searches rank a candidate pool,
+/// apply the filters to it and page through the ranked list; basic search pages by date.
+#[server(
+    prefix = "/api",
+    endpoint = "search_poasts",
+    input = GetUrl
+)]
+pub async fn search_poasts(filter: PostFilter, search_type: SearchType) -> Result<PostPage, ServerFnError> {
+    use crate::server_fn::cache::{post_list_tags, SEARCH_RESULTS_CACHE};
+    use log::info;
+
+    let mut filter = filter;
+    let (scope, hidden) = resolve_reading_filters(&mut filter).await?;
+    let query = filter.search_term().map(String::from);
+
+    if let (SearchType::Basic, _) | (_, None) = (search_type, &query) {
+        if filter.is_unfiltered() && scope.is_empty() {
+            let posts = get_poasts(None).await?;
+            let next_cursor = (posts.len() == DEFAULT_PAGE_SIZE)
+                .then(|| posts.last().and_then(PostCursor::after).map(|cursor| cursor.encode()))
+                .flatten();
+            return Ok(PostPage { posts, next_cursor });
         }
-    } else {
-        let mut cache = POASTS_CACHE.lock().unwrap();
-        *cache = (Some(poasts.clone()), Instant::now());
     }
 
-    Ok(poasts)
+    let cache_key = serde_json::to_string(&(search_type, &filter, scope.fingerprint()))?;
+    let tags = post_list_tags(&filter.company_set());
+
+    let mut page = SEARCH_RESULTS_CACHE
+        .get_or_fetch(cache_key, tags, move || async move {
+            match (search_type, query) {
+                // a vector search with no query is just the filtered listing
+                (SearchType::Basic, _) | (_, None) => fetch_post_page(&filter, &scope).await,
+                (search_type, Some(query)) => {
+                    let offset = filter.offset_cursor().map_err(|e| ServerFnError::new(e.to_string()))?;
+                    info!("Paged {:?} search for '{}' at offset {}", search_type, query, offset);
+                    let results = scored_links(&query, search_type, SEMANTIC_CANDIDATE_POOL).await?;
+                    ranked_posts(results, &filter, &scope, offset, filter.page_size()).await
+                }
+            }
+        })
+        .await?;
+    // the cursor still points past the whole page, so pages may come back short but none are skipped
+    page.posts.retain(|post| !hidden.contains(&post.id));
+    Ok(page)
 }
 
 #[component]
@@ -248,160 +623,337 @@ pub fn Poasts() -> impl IntoView {
         search_type: SearchType::Basic
     });
     let (selected_company, set_selected_company) = signal(String::new());
+    let (published_after, set_published_after) = signal(String::new());
+    let (published_before, set_published_before) = signal(String::new());
+    let (tag, set_tag) = signal(String::new());
+    let (min_similarity, set_min_similarity) = signal(0);
+    let (unread_only, set_unread_only) = signal(false);
+    let (bookmarked_only, set_bookmarked_only) = signal(false);
+
+    let auth = use_context::<AuthContext>();
+    let is_authenticated = move || auth.as_ref().is_some_and(|a| a.is_authenticated.get());
+
+    let (for_you, set_for_you) = signal(false);
+    let (follow_version, set_follow_version) = signal(0u32);
+    let followed = Resource::new(
+        move || (is_authenticated(), follow_version.get()),
+        |(signed_in, _)| async move {
+            if signed_in {
+                list_followed_companies().await
+            } else {
+                Ok(Vec::new())
+            }
+        },
+    );
+
+    let (more_posts, set_more_posts) = signal(Vec::<Poast>::new());
+    let (next_cursor, set_next_cursor) = signal(None::<String>);
+    let (loading_more, set_loading_more) = signal(false);
 
     let companies = Resource::new(|| (), |_| get_companies());
 
+    let non_empty = |value: String| (!value.trim().is_empty()).then_some(value);
+
+    let current_filter = move || {
+        let params = search_params.get();
+        PostFilter {
+            search_term: non_empty(params.query),
+            company: non_empty(selected_company.get()),
+            published_after: non_empty(published_after.get()),
+            published_before: non_empty(published_before.get()),
+            tag: non_empty(tag.get()),
+            min_similarity: (min_similarity.get() > 0).then(|| min_similarity.get()),
+            unread_only: unread_only.get(),
+            bookmarked_only: bookmarked_only.get(),
+            ..Default::default()
+        }
+    };
+
     let poasts = Resource::new(
         move || {
-            let params = search_params.get();
-            let company = selected_company.get();
-
-            console_log!(
-                "Filter changed - search: '{}', type: {:?}, company: '{}'", 
-                params.query, 
-                params.search_type,
-                company
-            );
-            
-            (params, company)
+            let filter = current_filter();
+            let search_type = search_params.get().search_type;
+
+            console_log!("Filter changed - type: {:?}, filter: {:?}", search_type, filter);
+
+            (filter, search_type)
         },
-        move |(params, company)| async move {
-            match params.search_type {
-                SearchType::OpenAISemantic | SearchType::LocalSemantic => {
-                    if params.query.trim().is_empty() {
-                        get_poasts(None).await
-                    } else {
-                        let semantic_results = semantic_search(params.query, params.search_type).await?;
-                        if company.trim().is_empty() {
-                            Ok(semantic_results)
-                        } else {
-                            Ok(semantic_results
-                                .into_iter()
-                                .filter(|post| post.company == company)
-                                .collect())
-                        }
-                    }
-                }
-                SearchType::Basic => {
-                    let filter = PostFilter {
-                        search_term: if params.query.trim().is_empty() { None } else { Some(params.query) },
-                        company: if company.trim().is_empty() { None } else { Some(company) },
-                    };
-                    get_poasts(Some(filter)).await
-                }
-            }
+        move |(filter, search_type)| async move {
+            search_poasts(filter, search_type).await
         }
     );
 
+    // a fresh first page replaces whatever "load more" appended
+    Effect::new(move |_| {
+        if let Some(Ok(page)) = poasts.get() {
+            set_more_posts.set(Vec::new());
+            set_next_cursor.set(page.next_cursor);
+        }
+    });
+
+    let load_more = move |_| {
+        let Some(cursor) = next_cursor.get_untracked() else {
+            return;
+        };
+        let filter = PostFilter { cursor: Some(cursor), ..current_filter() };
+        let search_type = search_params.get_untracked().search_type;
+
+        set_loading_more.set(true);
+        leptos::task::spawn_local(async move {
+            match search_poasts(filter, search_type).await {
+                Ok(page) => {
+                    set_more_posts.update(|posts| posts.extend(page.posts));
+                    set_next_cursor.set(page.next_cursor);
+                }
+                Err(e) => console_log!("Error loading more posts: {:?}", e),
+            }
+            set_loading_more.set(false);
+        });
+    };
+
     let on_search = Callback::new(move |new_params: SearchParams| {
         set_search_params.set(new_params);
     });
 
+    let filter_input_class = "p-2 rounded-md bg-gray-100 dark:bg-teal-800 text-gray-800 dark:text-gray-200 
+        border border-teal-500 dark:border-seafoam-500 
+        focus:border-seafoam-600 dark:focus:border-aqua-400 
+        focus:outline-none focus:ring-2 focus:ring-seafoam-500 dark:focus:ring-aqua-400";
+
+    let tab_class = move |active: bool| {
+        if active {
+            "px-4 py-1 rounded-full text-sm bg-seafoam-600 dark:bg-aqua-600 text-white"
+        } else {
+            "px-4 py-1 rounded-full text-sm text-seafoam-600 dark:text-aqua-400 hover:bg-seafoam-100 dark:hover:bg-teal-700"
+        }
+    };
+
     view! {
         <div class="pt-4 space-y-4">
-            <BlogSearch on_search=on_search />
-
-            <Suspense fallback=|| view! { <div class="pl-4 h-10"></div> }>
-                <div class="flex justify-start mb-2 pl-4">
-                    {move || {
-                        companies
-                            .get()
-                            .map(|companies_result| {
-                                let selected = selected_company.get();
-                                match companies_result {
-                                    Ok(companies) => {
-                                        view! {
-                                            <>
-                                                <select
-                                                    on:change=move |ev| set_selected_company(
-                                                        event_target_value(&ev),
-                                                    )
-                                                    class="w-52 p-2 rounded-md bg-gray-100 dark:bg-teal-800 text-gray-800 dark:text-gray-200 
-                                                    border border-teal-500 dark:border-seafoam-500 
-                                                    focus:border-seafoam-600 dark:focus:border-aqua-400 
-                                                    focus:outline-none focus:ring-2 focus:ring-seafoam-500 dark:focus:ring-aqua-400"
-                                                >
-                                                    <option value="">"All Companies"</option>
-                                                    {companies
-                                                        .into_iter()
-                                                        .map(|company| {
-                                                            view! {
-                                                                <option value=company.clone() selected=selected == company>
-                                                                    {company.clone()}
-                                                                </option>
-                                                            }
-                                                        })
-                                                        .collect_view()}
-                                                </select>
-                                            </>
+            {move || {
+                is_authenticated()
+                    .then(|| {
+                        view! {
+                            <div class="flex justify-center gap-2">
+                                <button class=move || tab_class(!for_you.get()) on:click=move |_| set_for_you(false)>
+                                    "Latest"
+                                </button>
+                                <button class=move || tab_class(for_you.get()) on:click=move |_| set_for_you(true)>
+                                    "For you"
+                                </button>
+                            </div>
+                        }
+                    })
+            }}
+            {move || {
+                (for_you.get() && is_authenticated())
+                    .then(|| {
+                        view! { <ForYou followed=followed set_version=set_follow_version version=follow_version /> }
+                    })
+            }}
+            <div class="space-y-4" class:hidden=move || for_you.get() && is_authenticated()>
+                <BlogSearch on_search=on_search />
+
+                <div class="flex flex-wrap items-center justify-start gap-2 mb-2 pl-4">
+                    <Suspense fallback=|| view! { <div class="h-10"></div> }>
+                        {move || {
+                            companies
+                                .get()
+                                .map(|companies_result| {
+                                    let selected = selected_company.get();
+                                    match companies_result {
+                                        Ok(companies) => {
+                                            view! {
+                                                <>
+                                                    <select
+                                                        on:change=move |ev| set_selected_company(
+                                                            event_target_value(&ev),
+                                                        )
+                                                        class="w-52 p-2 rounded-md bg-gray-100 dark:bg-teal-800 text-gray-800 dark:text-gray-200 
+                                                        border border-teal-500 dark:border-seafoam-500 
+                                                        focus:border-seafoam-600 dark:focus:border-aqua-400 
+                                                        focus:outline-none focus:ring-2 focus:ring-seafoam-500 dark:focus:ring-aqua-400"
+                                                    >
+                                                        <option value="">"All Companies"</option>
+                                                        {companies
+                                                            .into_iter()
+                                                            .map(|company| {
+                                                                view! {
+                                                                    <option value=company.clone() selected=selected == company>
+                                                                        {company.clone()}
+                                                                    </option>
+                                                                }
+                                                            })
+                                                            .collect_view()}
+                                                    </select>
+                                                </>
+                                            }
+                                                .into_any()
                                         }
-                                            .into_any()
-                                    }
-                                    Err(_) => {
-                                        view! {
-                                            <>
-                                                <div></div>
-                                            </>
+                                        Err(_) => {
+                                            view! {
+                                                <>
+                                                    <div></div>
+                                                </>
+                                            }
+                                                .into_any()
                                         }
-                                            .into_any()
                                     }
+                                })
+                        }}
+                    </Suspense>
+                    {move || {
+                        (is_authenticated() && !selected_company.get().is_empty())
+                            .then(|| {
+                                view! {
+                                    <FollowButton
+                                        company=Signal::derive(move || selected_company.get())
+                                        followed=followed
+                                        set_version=set_follow_version
+                                    />
+                                }
+                            })
+                    }}
+                    <label class="flex items-center gap-1 text-sm text-gray-600 dark:text-gray-300">
+                        "From"
+                        <input
+                            type="date"
+                            class=filter_input_class
+                            prop:value=published_after
+                            on:change=move |ev| set_published_after(event_target_value(&ev))
+                        />
+                    </label>
+                    <label class="flex items-center gap-1 text-sm text-gray-600 dark:text-gray-300">
+                        "To"
+                        <input
+                            type="date"
+                            class=filter_input_class
+                            prop:value=published_before
+                            on:change=move |ev| set_published_before(event_target_value(&ev))
+                        />
+                    </label>
+                    <input
+                        type="text"
+                        placeholder="Tag"
+                        class=format!("w-36 {}", filter_input_class)
+                        prop:value=tag
+                        on:change=move |ev| set_tag(event_target_value(&ev))
+                    />
+                    {move || {
+                        (search_params.get().search_type != SearchType::Basic)
+                            .then(|| {
+                                view! {
+                                    <select
+                                        class=filter_input_class
+                                        on:change=move |ev| {
+                                            set_min_similarity(event_target_value(&ev).parse().unwrap_or(0))
+                                        }
+                                        prop:value=move || min_similarity.get().to_string()
+                                    >
+                                        <option value="0">"Any similarity"</option>
+                                        <option value="50">"50%+"</option>
+                                        <option value="75">"75%+"</option>
+                                        <option value="90">"90%+"</option>
+                                    </select>
+                                }
+                            })
+                    }}
+                    {move || {
+                        is_authenticated()
+                            .then(|| {
+                                view! {
+                                    <label class="flex items-center gap-1 text-sm text-gray-600 dark:text-gray-300">
+                                        <input
+                                            type="checkbox"
+                                            prop:checked=unread_only
+                                            on:change=move |ev| set_unread_only(event_target_checked(&ev))
+                                        />
+                                        "Unread only"
+                                    </label>
+                                    <label class="flex items-center gap-1 text-sm text-gray-600 dark:text-gray-300">
+                                        <input
+                                            type="checkbox"
+                                            prop:checked=bookmarked_only
+                                            on:change=move |ev| set_bookmarked_only(event_target_checked(&ev))
+                                        />
+                                        "Bookmarked"
+                                    </label>
                                 }
                             })
                     }}
                 </div>
-            </Suspense>
 
-            <Suspense fallback=|| {
-                view! { <p class="text-center text-teal-600 dark:text-aqua-400">"Loading..."</p> }
-            }>
-                {move || {
-                    match poasts.get() {
-                        Some(Ok(posts)) => {
-                            if posts.is_empty() {
+                <Suspense fallback=|| {
+                    view! { <p class="text-center text-teal-600 dark:text-aqua-400">"Loading..."</p> }
+                }>
+                    {move || {
+                        match poasts.get() {
+                            Some(Ok(page)) => {
+                                let mut posts = page.posts;
+                                posts.extend(more_posts.get());
+                                if posts.is_empty() {
+                                    view! {
+                                        <div class="text-center text-gray-500 dark:text-gray-400">
+                                            "No posts found"
+                                        </div>
+                                    }
+                                        .into_any()
+                                } else {
+                                    view! {
+                                        <div class="grid grid-cols-1 sm:grid-cols-2 lg:grid-cols-3 xl:grid-cols-4 gap-4">
+                                            <For
+                                                each=move || posts.clone()
+                                                key=|poast| poast.id
+                                                children=move |poast| {
+                                                    view! {
+                                                        <BlogPoast
+                                                            poast=poast
+                                                            search_term=search_params.get().query
+                                                        />
+                                                    }
+                                                }
+                                            />
+                                        </div>
+                                        {move || {
+                                            next_cursor
+                                                .get()
+                                                .map(|_| {
+                                                    view! {
+                                                        <div class="flex justify-center pb-6">
+                                                            <button
+                                                                on:click=load_more
+                                                                disabled=loading_more
+                                                                class="px-4 py-2 rounded-md bg-seafoam-600 dark:bg-aqua-600 text-white hover:bg-seafoam-700 dark:hover:bg-aqua-700 disabled:opacity-50 transition-colors"
+                                                            >
+                                                                {move || if loading_more.get() { "Loading..." } else { "Load more" }}
+                                                            </button>
+                                                        </div>
+                                                    }
+                                                })
+                                        }}
+                                    }
+                                        .into_any()
+                                }
+                            }
+                            Some(Err(e)) => {
+                                console_log!("Error loading posts: {:?}", e);
                                 view! {
-                                    <div class="text-center text-gray-500 dark:text-gray-400">
-                                        "No posts found"
-                                    </div>
+                                    <div class="text-center text-salmon-500">"Error loading posts"</div>
                                 }
                                     .into_any()
-                            } else {
+                            }
+                            None => {
                                 view! {
-                                    <div class="grid grid-cols-1 sm:grid-cols-2 lg:grid-cols-3 xl:grid-cols-4 gap-4">
-                                        <For
-                                            each=move || posts.clone()
-                                            key=|poast| poast.id
-                                            children=move |poast| {
-                                                view! {
-                                                    <BlogPoast
-                                                        poast=poast
-                                                        search_term=search_params.get().query
-                                                    />
-                                                }
-                                            }
-                                        />
+                                    <div class="text-center text-gray-500 dark:text-gray-400">
+                                        "Loading..."
                                     </div>
                                 }
                                     .into_any()
                             }
                         }
-                        Some(Err(e)) => {
-                            console_log!("Error loading posts: {:?}", e);
-                            view! {
-                                <div class="text-center text-salmon-500">"Error loading posts"</div>
-                            }
-                                .into_any()
-                        }
-                        None => {
-                            view! {
-                                <div class="text-center text-gray-500 dark:text-gray-400">
-                                    "Loading..."
-                                </div>
-                            }
-                                .into_any()
-                        }
-                    }
-                }}
-            </Suspense>
+                    }}
+                </Suspense>
+            </div>
         </div>
     }
 }
@@ -413,6 +965,8 @@ pub fn BlogPoast(
 ) -> impl IntoView {
     let company = Memo::new(move |_| poast.company.clone());
     let (is_expanded, set_is_expanded) = signal(false);
+    let (show_related, set_show_related) = signal(false);
+    let poast_id = poast.id;
     
     let handle_show_more = move |ev: web_sys::MouseEvent| {
         ev.stop_propagation();
@@ -554,6 +1108,22 @@ pub fn BlogPoast(
                             })
                     }}
                 </div>
+                <div class="mt-2 flex gap-4">
+                    <a
+                        href=format!("/post/{}", poast_id)
+                        class="text-xs md:text-sm text-seafoam-600 dark:text-aqua-400 hover:text-seafoam-700 dark:hover:text-aqua-300 transition-colors"
+                    >
+                        "Read Post"
+                    </a>
+                    <button
+                        on:click=move |_| set_show_related.update(|shown| *shown = !*shown)
+                        class="text-xs md:text-sm text-seafoam-600 dark:text-aqua-400 hover:text-seafoam-700 dark:hover:text-aqua-300 transition-colors"
+                    >
+                        {move || if show_related() { "Hide Related" } else { "More Like This" }}
+                    </button>
+                    <ReadingControls post_id=poast_id />
+                </div>
+                {move || show_related().then(|| view! { <RelatedPosts poast_id=poast_id /> })}
             </article>
         </div>
     }
@@ -734,17 +1304,24 @@ async fn get_openai_embedding(query: &str) -> Result<Vec<f32>, ServerFnError> {
         types::{CreateEmbeddingRequestArgs, EmbeddingInput},
         Client,
     };
+    use crate::embedding_service::embeddings::OPENAI_EMBEDDING_MODEL;
+
     let openai = Client::new();
-    openai
+    let response = openai
         .embeddings()
         .create(CreateEmbeddingRequestArgs::default()
-            .model("text-embedding-3-small")
+            .model(OPENAI_EMBEDDING_MODEL)
             .input(EmbeddingInput::String(query.to_string()))
             .build()
             .map_err(|e| ServerFnError::new(format!("Failed to build embedding request: {}", e)))?)
         .await
-        .map_err(|e| ServerFnError::new(format!("OpenAI API error: {}", e)))
-        .map(|response| response.data[0].embedding.clone())
+        .map_err(|e| ServerFnError::new(format!("OpenAI API error: {}", e)))?;
+
+    response
+        .data
+        .first()
+        .map(|data| data.embedding.clone())
+        .ok_or_else(|| ServerFnError::new("OpenAI returned no embedding"))
 }
 
 #[server(
@@ -753,21 +1330,41 @@ async fn get_openai_embedding(query: &str) -> Result<Vec<f32>, ServerFnError> {
     input = GetUrl
 )]
 pub async fn semantic_search(query: String, search_type: SearchType) -> Result<Vec<Poast>, ServerFnError> {
-    use crate::embeddings_service::embeddings_local::LocalEmbeddingService;
-    use log::{info, warn, error, debug};
+    let filter = PostFilter {
+        search_term: Some(query),
+        ..Default::default()
+    };
+    Ok(search_poasts(filter, search_type).await?.posts)
+}
 
-    info!("Starting semantic search with query: {} using {:?}", query, search_type);
+/// Embed a search query in the given space, reusing recent embeddings of the same query.
+#[cfg(feature = "ssr")]
+async fn query_embedding(
+    query: &str,
+    column: crate::embedding_service::embeddings::EmbeddingColumn,
+) -> Result<Vec<f32>, ServerFnError> {
+    use crate::embeddings_service::embeddings_local::LocalEmbeddingService;
+    use crate::embedding_service::embeddings::EmbeddingColumn;
+    use crate::server_fn::cache::QUERY_EMBEDDING_CACHE;
+    use log::{debug, error, info};
+
+    // keyed by model so a model change never serves vectors from the old space
+    let key = format!("{}\n{}", column.current_model(), query.trim().to_lowercase());
+    if let Some(embedding) = QUERY_EMBEDDING_CACHE.get(&key) {
+        debug!("Query embedding cache hit for '{}'", query);
+        return Ok(embedding);
+    }
 
-    let query_embedding = match search_type {
-        SearchType::OpenAISemantic => {
+    let embedding = match column {
+        EmbeddingColumn::OpenAI => {
             info!("Using OpenAi embeddings");
-            get_openai_embedding(&query).await?
+            get_openai_embedding(query).await?
         }
-        SearchType::LocalSemantic => {
+        EmbeddingColumn::MiniLm => {
             info!("Using local embeddings");
             LocalEmbeddingService::init()?;
             match LocalEmbeddingService::get_instance() {
-                Ok(service) => service.generate_embedding(&query)
+                Ok(service) => service.generate_embedding(query)
                     .map_err(|e| ServerFnError::new(format!("Local embedding error: {}", e)))?,
                 Err(e) => {
                     error!("Failed to get local embedding service: {}", e);
@@ -775,159 +1372,317 @@ pub async fn semantic_search(query: String, search_type: SearchType) -> Result<V
                 }
             }
         }
-        SearchType::Basic => return Err(ServerFnError::new("Invalid search type"))
     };
 
-    info!("Query embedding generated, length: {}", query_embedding.len());
+    QUERY_EMBEDDING_CACHE.insert(key, embedding.clone(), Vec::new());
+    Ok(embedding)
+}
 
-    // Fetch all embeddings using pagination
-    let supabase = crate::supabase::get_client();
-    info!("Fetching embeddings from Supabase with pagination");
-    
-    let mut all_embeddings: Vec<PostEmbedding> = Vec::new();
-    let page_size = 1000;
-    let mut current_page = 0;
-    
-    loop {
-        let start = current_page * page_size;
-        let end = start + page_size - 1;
-        
-        info!("Fetching embeddings page {}: range {}-{}", current_page + 1, start, end);
-        
-        let response = supabase
-            .from("post_embeddings")
-            .select("*")
-            .range(start, end)
-            .execute()
+/// How many ranked candidates filtered and paged semantic searches draw from.
+#[cfg(feature = "ssr")]
+const SEMANTIC_CANDIDATE_POOL: usize = 300;
+
+/// Links nearest to `query` in the space picked by `search_type`, best first.
+#[cfg(feature = "ssr")]
+async fn scored_links(
+    query: &str,
+    search_type: SearchType,
+    limit: usize,
+) -> Result<Vec<crate::vector_search_service::vector_search::ScoredLink>, ServerFnError> {
+    use crate::embedding_service::embeddings::EmbeddingColumn;
+    use crate::vector_search_service::vector_search::{hybrid_links, nearest_links};
+    use log::info;
+
+    info!("Starting semantic search with query: {} using {:?}", query, search_type);
+
+    if search_type == SearchType::Hybrid {
+        info!("Using hybrid full-text + OpenAI ranking");
+        let query_embedding = query_embedding(query, EmbeddingColumn::OpenAI).await?;
+        return hybrid_links(query, &query_embedding, limit)
             .await
-            .map_err(|e| ServerFnError::new(format!("Failed to fetch embeddings: {}", e)))?;
-
-        let response_text = response.text().await
-            .map_err(|e| ServerFnError::new(format!("Failed to get response text: {}", e)))?;
-
-        let embeddings_value: serde_json::Value = serde_json::from_str(&response_text)
-            .map_err(|e| {
-                error!("Failed to parse embeddings page {}: {}", current_page + 1, e);
-                error!("First 500 chars of response: {}", &response_text[..response_text.len().min(500)]);
-                ServerFnError::new(format!("Failed to parse embeddings: {}", e))
-            })?;
-            
-        if let serde_json::Value::Array(arr) = embeddings_value {
-            if arr.is_empty() {
-                // No more records, exit the loop
-                break;
-            }
-            
-            let page_embeddings: Vec<PostEmbedding> = arr.iter()
-                .filter_map(|v| {
-                    let result = serde_json::from_value(v.clone());
-                    if let Err(ref e) = result {
-                        error!("Failed to parse embedding: {}", e);
-                    }
-                    result.ok()
-                })
-                .collect();
-                
-            info!("Successfully parsed {} embeddings from page {}", page_embeddings.len(), current_page + 1);
-            all_embeddings.extend(page_embeddings);
-            current_page += 1;
-        } else {
-            error!("Expected array response from Supabase, got: {}", &response_text[..response_text.len().min(500)]);
-            break;
-        }
+            .map_err(|e| ServerFnError::new(format!("Hybrid search failed: {}", e)));
     }
-    
-    info!("Successfully fetched and parsed {} embeddings across {} pages", all_embeddings.len(), current_page);
 
-    let mut results: Vec<(String, f32)> = all_embeddings
+    let column = match search_type {
+        SearchType::OpenAISemantic => EmbeddingColumn::OpenAI,
+        SearchType::LocalSemantic => EmbeddingColumn::MiniLm,
+        SearchType::Basic | SearchType::Hybrid => return Err(ServerFnError::new("Invalid search type"))
+    };
+    let query_embedding = query_embedding(query, column).await?;
+
+    info!("Query embedding generated, length: {}", query_embedding.len());
+
+    nearest_links(column, &query_embedding, limit)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Vector search failed: {}", e)))
+}
+
+/// Turn ranked links into a page of posts. Candidates below `min_similarity` are dropped, the
+/// rest are checked against the filters 50 links at a time in rank order, and the cursor is the
+/// offset into the filtered ranking.
+#[cfg(feature = "ssr")]
+pub(crate) async fn ranked_posts(
+    results: Vec<crate::vector_search_service::vector_search::ScoredLink>,
+    filter: &PostFilter,
+    scope: &PostScope,
+    offset: usize,
+    limit: usize,
+) -> Result<PostPage, ServerFnError> {
+    use log::{info, error, debug};
+    use std::collections::HashMap;
+
+    let min_similarity = filter.min_similarity.unwrap_or(0);
+    let candidates: Vec<(String, i32)> = results
         .into_iter()
-        .filter_map(|post| {
-            let embedding_to_compare = match search_type {
-                SearchType::OpenAISemantic => {
-                    if post.embedding.is_empty() {
-                        warn!("Skipping post {} - no OpenAI embedding", post.link);
-                        return None;
-                    }
-                    &post.embedding
-                },
-                SearchType::LocalSemantic => {
-                    if post.minilm.is_empty() {
-                        warn!("Skipping post {} - no local embedding", post.link);
-                        return None;
-                    }
-                    &post.minilm
-                },
-                SearchType::Basic => return None,
-            };
-    
-            let similarity = cosine_similarity(&query_embedding, embedding_to_compare);
-            debug!("Similarity for {}: {}", post.link, similarity);
-            Some((post.link, similarity))
+        .map(|result| {
+            // Convert similarity score to percentage (0-100)
+            let percentage = (result.similarity * 100.0).round() as i32;
+            debug!("Selected result - link: {}, score: {}%", result.link, percentage);
+            (result.link, percentage)
         })
+        .filter(|(_, percentage)| *percentage >= min_similarity)
         .collect();
 
-    results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
-    info!("Sorted {} results by similarity", results.len());
+    // an id scope is checked here rather than sent with every chunk of links
+    let allowed: Option<std::collections::HashSet<i32>> =
+        scope.post_ids.as_ref().map(|ids| ids.iter().copied().collect());
+    let scope = PostScope { post_ids: None, ..scope.clone() };
 
-    let links: Vec<String> = results.iter()
-        .take(30)
-        .map(|(link, score)| {
-            debug!("Selected result - link: {}, score: {}", link, score);
-            link.clone()
-        })
-        .collect();
+    let wanted = offset + limit;
+    let mut matched: Vec<Poast> = Vec::new();
 
-    info!("Fetching full post data for top {} results", links.len());
-    let posts_response = supabase
-        .from("poasts")
-        .select("id, published_at, company, title, link, summary, links!posts_company_fkey(logo_url)")
-        .in_("link", &links)
-        .execute()
-        .await?;
+    for chunk in candidates.chunks(50) {
+        let links: Vec<&str> = chunk.iter().map(|(link, _)| link.as_str()).collect();
 
-    let posts_text = posts_response.text().await?;
-    debug!("Posts response: {}", posts_text);
+        info!("Fetching full post data for {} ranked results", links.len());
+        let request = crate::supabase::get_client()
+            .from("poasts")
+            .select(POAST_COLUMNS)
+            .in_("link", &links);
+        let posts_response = apply_post_filters(request, filter, &scope)
+            .execute()
+            .await?;
 
-    let parse_posts_result = serde_json::from_str::<Vec<Poast>>(&posts_text);
-    
-    match &parse_posts_result {
-        Ok(posts) => info!("Successfully parsed {} posts", posts.len()),
-        Err(e) => {
+        let posts_text = posts_response.text().await?;
+        debug!("Posts response: {}", posts_text);
+
+        let posts: Vec<Poast> = serde_json::from_str(&posts_text).map_err(|e| {
             error!("Failed to parse posts: {}", e);
             error!("First 500 chars of posts response: {}", &posts_text[..posts_text.len().min(500)]);
-            return Err(ServerFnError::ServerError(format!("Failed to parse posts: {}", e)));
+            ServerFnError::ServerError(format!("Failed to parse posts: {}", e))
+        })?;
+
+        let mut by_link: HashMap<String, Poast> = posts.into_iter().map(|p| (p.link.clone(), p)).collect();
+        for (link, percentage) in chunk {
+            if let Some(mut post) = by_link.remove(link) {
+                if allowed.as_ref().is_some_and(|allowed| !allowed.contains(&post.id)) {
+                    continue;
+                }
+                post.similarity = Some(*percentage);
+                matched.push(post);
+            }
+        }
+
+        if matched.len() > wanted {
+            break;
         }
     }
 
-    let mut posts = parse_posts_result?;
+    let next_cursor = (matched.len() > wanted).then(|| PostCursor::Offset(wanted).encode());
+    let posts: Vec<Poast> = matched.into_iter().skip(offset).take(limit).collect();
 
-    info!("Adding similarity scores to posts");
-    for post in &mut posts {
-        if let Some((_, score)) = results.iter().find(|(l, _)| l == &post.link) {
-            // Convert similarity score to percentage (0-100)
-            let percentage = (score * 100.0).round() as i32;
-            post.similarity = Some(percentage);
-            debug!("Post '{}' has similarity score: {}%", post.title, percentage);
+    info!("Returning {} ranked posts", posts.len());
+    Ok(PostPage { posts, next_cursor })
+}
+
+/// Nearest neighbors of one post (by `id` or `link`) from its stored embedding. Uses the OpenAI
+/// vector when the post has one, otherwise MiniLM.
+#[server(
+    prefix = "/api",
+    endpoint = "get_related_posts",
+    input = GetUrl
+)]
+pub async fn get_related_posts(
+    id: Option<i32>,
+    link: Option<String>,
+    exclude_same_company: bool,
+    limit: Option<usize>,
+) -> Result<Vec<Poast>, ServerFnError> {
+    use crate::embedding_service::embeddings::EmbeddingColumn;
+    use crate::vector_search_service::vector_search::{nearest_links, stored_vector};
+    use log::info;
+
+    #[derive(Deserialize)]
+    struct Source {
+        link: String,
+        company: String,
+    }
+
+    let limit = limit.unwrap_or(5).clamp(1, 20);
+
+    let request = crate::supabase::get_client().from("poasts").select("link, company");
+    let request = match (id, link) {
+        (Some(id), _) => request.eq("id", id.to_string()),
+        (None, Some(link)) => request.eq("link", link),
+        (None, None) => return Err(ServerFnError::new("Either a post id or link is required")),
+    };
+
+    let body = request.execute().await?.text().await?;
+    let source: Source = serde_json::from_str::<Vec<Source>>(&body)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| ServerFnError::new("Post not found"))?;
+
+    let mut found = None;
+    for column in [EmbeddingColumn::OpenAI, EmbeddingColumn::MiniLm] {
+        if let Some(vector) = stored_vector(column, &source.link)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Failed to load embedding: {}", e)))?
+        {
+            found = Some((column, vector));
+            break;
         }
     }
+    let Some((column, vector)) = found else {
+        info!("No embedding stored for {}, no related posts", source.link);
+        return Ok(Vec::new());
+    };
 
-    info!("Sorting posts by similarity scores");
-    posts.sort_by(|a, b| {
-        let a_score = results.iter().find(|(l, _)| l == &a.link).unwrap().1;
-        let b_score = results.iter().find(|(l, _)| l == &b.link).unwrap().1;
-        b_score.partial_cmp(&a_score).unwrap()
-    });
+    // the company is excluded in the posts query, so a prolific company can't crowd out the
+    // page; `ranked_posts` keeps walking the candidates until enough others match
+    let (pool, scope) = if exclude_same_company {
+        let scope = PostScope { exclude_companies: vec![source.company], ..Default::default() };
+        (SEMANTIC_CANDIDATE_POOL, scope)
+    } else {
+        (limit + 1, PostScope::default())
+    };
+    let results = nearest_links(column, &vector, pool)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Vector search failed: {}", e)))?
+        .into_iter()
+        .filter(|result| result.link != source.link)
+        .collect();
 
-    info!("Returning {} ranked posts", posts.len());
-    Ok(posts)
+    Ok(ranked_posts(results, &PostFilter::default(), &scope, 0, limit).await?.posts)
 }
 
-#[cfg(feature = "ssr")]
-fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
-    let dot_product: f32 = a.iter().zip(b.iter()).map(|(x, y)| x * y).sum();
-    let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
-    let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
-    dot_product / (norm_a * norm_b)
+#[component]
+pub fn RelatedPosts(poast_id: i32) -> impl IntoView {
+    let (other_companies_only, set_other_companies_only) = signal(true);
+
+    let related = Resource::new(
+        move || other_companies_only.get(),
+        move |exclude_same_company| get_related_posts(Some(poast_id), None, exclude_same_company, Some(5)),
+    );
+
+    view! {
+        <div class="mt-3 w-full border-t border-gray-200 dark:border-teal-700 pt-2">
+            <label class="flex items-center gap-2 text-xs text-gray-500 dark:text-gray-400 mb-1">
+                <input
+                    type="checkbox"
+                    prop:checked=other_companies_only
+                    on:change=move |ev| set_other_companies_only(event_target_checked(&ev))
+                />
+                "Other companies only"
+            </label>
+            <Suspense fallback=|| {
+                view! { <p class="text-xs text-teal-600 dark:text-aqua-400">"Finding related posts..."</p> }
+            }>
+                {move || {
+                    related
+                        .get()
+                        .map(|result| match result {
+                            Ok(posts) if posts.is_empty() => {
+                                view! {
+                                    <p class="text-xs text-gray-500 dark:text-gray-400">"No related posts"</p>
+                                }
+                                    .into_any()
+                            }
+                            Ok(posts) => {
+                                view! {
+                                    <ul class="space-y-1">
+                                        {posts
+                                            .into_iter()
+                                            .map(|post| {
+                                                view! {
+                                                    <li class="text-xs md:text-sm">
+                                                        <a
+                                                            href=format!("/post/{}", post.id)
+                                                            class="text-seafoam-600 dark:text-aqua-400 hover:underline"
+                                                        >
+                                                            {post.title.clone()}
+                                                        </a>
+                                                        <span class="text-gray-500 dark:text-gray-400">
+                                                            {format!(
+                                                                " - {}{}",
+                                                                post.company,
+                                                                post.similarity.map(|s| format!(" ({}%)", s)).unwrap_or_default(),
+                                                            )}
+                                                        </span>
+                                                    </li>
+                                                }
+                                            })
+                                            .collect_view()}
+                                    </ul>
+                                }
+                                    .into_any()
+                            }
+                            Err(e) => {
+                                console_log!("Error loading related posts: {:?}", e);
+                                view! {
+                                    <p class="text-xs text-salmon-500">"Error loading related posts"</p>
+                                }
+                                    .into_any()
+                            }
+                        })
+                }}
+            </Suspense>
+        </div>
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_post_cursor_round_trip() {
+        let published_at = chrono::DateTime::parse_from_rfc3339("2024-03-01T12:30:00.250Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let keyset = PostCursor::Keyset { published_at, id: 42 };
+        assert_eq!(keyset.encode(), "k:2024-03-01T12:30:00.250000Z|42");
+        assert_eq!(PostCursor::decode(&keyset.encode()), Some(keyset));
+        assert_eq!(PostCursor::decode("o:60"), Some(PostCursor::Offset(60)));
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn test_parse_published() {
+        let parsed = |s: &str| parse_published(s).map(|dt| dt.to_rfc3339());
+        assert_eq!(parsed("2024-03-01T12:30:00+02:00").as_deref(), Some("2024-03-01T10:30:00+00:00"));
+        assert_eq!(parsed("2024-03-01T12:30:00.5Z").as_deref(), Some("2024-03-01T12:30:00.500+00:00"));
+        assert_eq!(parsed("2024-03-01T12:30:00").as_deref(), Some("2024-03-01T12:30:00+00:00"));
+        assert_eq!(parsed("2024-03-01T12:30:00.123456").as_deref(), Some("2024-03-01T12:30:00.123456+00:00"));
+        assert_eq!(parsed("2024-03-01").as_deref(), Some("2024-03-01T00:00:00+00:00"));
+        for malformed in ["", "yesterday", "2024-13-01", "01/03/2024", "2024-03-01 12:30:00"] {
+            assert_eq!(parse_published(malformed), None, "{}", malformed);
+        }
+    }
 
+    #[test]
+    fn test_post_cursor_rejects_malformed_and_mismatched() {
+        for cursor in ["60", "k:2024-03-01|42", "k:\") or (id.gt.0|1", "k:2024-03-01T12:30:00Z|x", "o:-1", ""] {
+            assert_eq!(PostCursor::decode(cursor), None, "{}", cursor);
+        }
+
+        let ranked = PostFilter { cursor: Some("o:30".to_string()), ..Default::default() };
+        assert_eq!(ranked.offset_cursor(), Ok(30));
+        assert_eq!(ranked.keyset_cursor(), Err(InvalidCursor));
+
+        let listing = PostFilter { cursor: Some("k:2024-03-01T12:30:00Z|7".to_string()), ..Default::default() };
+        assert!(matches!(listing.keyset_cursor(), Ok(Some((_, 7)))));
+        assert_eq!(listing.offset_cursor(), Err(InvalidCursor));
+        assert_eq!(PostFilter::default().offset_cursor(), Ok(0));
+    }
+}
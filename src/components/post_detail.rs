@@ -0,0 +1,166 @@
+use leptos::prelude::*;
+
+use crate::auth::context::AuthContext;
+use crate::components::ask::Ask;
+use crate::components::markdown::MarkdownRenderer;
+use crate::components::poasts::{get_post, PostDetail, RelatedPosts};
+use crate::components::reading_list::{ReadingControls, ReadingListPicker, ReadingState};
+
+/// One post with its stored full text, summary, buzzwords, related posts and a question box
+/// scoped to it.
+#[component]
+pub fn PostArticle(post_id: Memo<Option<i32>>) -> impl IntoView {
+    let post = Resource::new(
+        move || post_id.get(),
+        |id| async move {
+            match id {
+                Some(id) => get_post(id).await,
+                None => Ok(None),
+            }
+        },
+    );
+
+    view! {
+        <div class="container mx-auto px-4 pb-8 max-w-4xl">
+            <Suspense fallback=|| {
+                view! { <div class="text-center text-gray-500 dark:text-gray-400">"Loading..."</div> }
+            }>
+                {move || {
+                    post.get()
+                        .map(|result| match result {
+                            Ok(Some(detail)) => view! { <PostBody detail=detail /> }.into_any(),
+                            Ok(None) => {
+                                view! {
+                                    <div class="text-center text-gray-600 dark:text-gray-300 p-6 bg-white dark:bg-teal-800 rounded-lg">
+                                        "Post not found. "
+                                        <a href="/" class="text-teal-600 dark:text-aqua-400 underline">"Back to all posts"</a>
+                                    </div>
+                                }
+                                    .into_any()
+                            }
+                            Err(e) => {
+                                log::error!("Error loading post: {:?}", e);
+                                view! { <div class="text-center text-salmon-500">"Error loading post"</div> }.into_any()
+                            }
+                        })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+fn PostBody(detail: PostDetail) -> impl IntoView {
+    let PostDetail { post, buzzwords } = detail;
+    let logo_url = post.links.clone().and_then(|links| links.logo_url);
+    let body = post
+        .full_text
+        .clone()
+        .filter(|text| !text.trim().is_empty())
+        .or_else(|| post.description.clone().filter(|text| !text.trim().is_empty()));
+
+    // opening a post's page counts as reading it
+    let post_id = post.id;
+    let auth = use_context::<AuthContext>();
+    let reading = use_context::<ReadingState>();
+    Effect::new(move |_| {
+        let signed_in = auth.as_ref().is_some_and(|a| a.is_authenticated.get());
+        if let Some(reading) = reading.filter(|_| signed_in) {
+            reading.set_read(post_id, true);
+        }
+    });
+
+    view! {
+        <article class="space-y-4">
+            <header class="bg-white dark:bg-teal-800 rounded-lg p-4 shadow-md">
+                <div class="flex items-center pb-2">
+                    {logo_url
+                        .map(|url| {
+                            view! {
+                                <img
+                                    src=url
+                                    alt=format!("{} logo", post.company)
+                                    class="w-8 h-8 mr-2 rounded-sm"
+                                />
+                            }
+                        })}
+                    <span class="text-base lg:text-lg text-teal-600 dark:text-mint-400 font-semibold">
+                        {post.company.clone()}
+                    </span>
+                </div>
+                <h1 class="text-xl lg:text-2xl text-seafoam-600 dark:text-aqua-400 font-medium">
+                    {post.title.clone()}
+                </h1>
+                <div class="flex flex-wrap items-center gap-3 mt-1 text-sm text-gray-500 dark:text-gray-400">
+                    <span>{post.published_at.clone()}</span>
+                    <a
+                        href=post.link.clone()
+                        target="_blank"
+                        rel="noopener noreferrer"
+                        class="text-seafoam-600 dark:text-aqua-400 hover:underline"
+                    >
+                        "Read on the original blog"
+                    </a>
+                    <ReadingControls post_id=post.id />
+                </div>
+                <div class="mt-2">
+                    <ReadingListPicker post_id=post.id />
+                </div>
+                {buzzwords
+                    .filter(|words| !words.is_empty())
+                    .map(|words| {
+                        view! {
+                            <div class="flex flex-wrap gap-2 mt-3">
+                                {words
+                                    .into_iter()
+                                    .map(|word| {
+                                        view! {
+                                            <span class="px-2 py-0.5 rounded-full text-xs bg-seafoam-100 dark:bg-teal-700 text-seafoam-700 dark:text-mint-400">
+                                                {word}
+                                            </span>
+                                        }
+                                    })
+                                    .collect_view()}
+                            </div>
+                        }
+                    })}
+            </header>
+
+            {post
+                .summary
+                .clone()
+                .map(|summary| {
+                    view! {
+                        <section class="bg-white dark:bg-teal-800 rounded-lg p-4">
+                            <h2 class="text-sm font-semibold text-teal-600 dark:text-mint-400 mb-1">"Summary"</h2>
+                            <p class="text-sm md:text-base text-gray-600 dark:text-gray-300">{summary}</p>
+                        </section>
+                    }
+                })}
+
+            <section class="bg-white dark:bg-teal-800 rounded-lg p-4 text-gray-800 dark:text-gray-200">
+                {match body {
+                    Some(text) => view! { <MarkdownRenderer content=text class="" /> }.into_any(),
+                    None => {
+                        view! {
+                            <p class="text-sm text-gray-500 dark:text-gray-400">
+                                "The full text of this post hasn't been stored yet."
+                            </p>
+                        }
+                            .into_any()
+                    }
+                }}
+            </section>
+
+            <section class="bg-white dark:bg-teal-800 rounded-lg p-4">
+                <h2 class="text-sm font-semibold text-teal-600 dark:text-mint-400">"Related posts"</h2>
+                <RelatedPosts poast_id=post.id />
+            </section>
+
+            <section>
+                <h2 class="text-sm font-semibold text-teal-600 dark:text-mint-400 px-4 mb-2">"Ask about this post"</h2>
+                <Ask post_id=post.id />
+            </section>
+        </article>
+    }
+}
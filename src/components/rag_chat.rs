@@ -1,5 +1,7 @@
 use leptos::prelude::*;
 use serde::{Deserialize, Serialize};
+use server_fn::codec::{GetUrl, PostUrl};
+use uuid::Uuid;
 use wasm_bindgen::closure::Closure;
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::JsFuture;
@@ -7,6 +9,7 @@ use web_sys::{ErrorEvent, EventSource, MessageEvent};
 
 use crate::components::search::SearchType;
 use crate::components::markdown::MarkdownRenderer;
+use crate::models::{ChatMessageView, ChatSessionView};
 use crate::types::StreamResponse;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -15,6 +18,10 @@ pub struct RagMessage {
     pub content: String,
     pub citations: Option<Vec<Citation>>,
     pub timestamp: String,
+    #[serde(default)]
+    pub verification: Option<AnswerVerification>,
+    #[serde(default)]
+    pub context_usage: Option<ContextUsage>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -26,11 +33,241 @@ pub struct Citation {
     pub relevance_score: f32,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UnsupportedClaim {
+    pub sentence: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AnswerVerification {
+    pub checked: usize,
+    pub unsupported: Vec<UnsupportedClaim>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ContextUsage {
+    pub tokens_used: usize,
+    pub token_budget: usize,
+    pub posts_used: usize,
+    pub posts_dropped: usize,
+    pub passages: usize,
+    pub duplicates_skipped: usize,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RagResponse {
     pub message_type: String,
     pub content: Option<String>,
     pub citations: Option<Vec<Citation>>,
+    #[serde(default)]
+    pub verification: Option<AnswerVerification>,
+    #[serde(default)]
+    pub context_usage: Option<ContextUsage>,
+}
+
+/// Turn `[n]` / `[n, m]` markers into markdown links to the n-th citation (1-based).
+/// Markers that don't match a citation are left as they are.
+pub fn link_citation_markers(content: &str, citations: &[Citation]) -> String {
+    let mut output = String::with_capacity(content.len());
+    let mut rest = content;
+
+    while let Some(open) = rest.find('[') {
+        output.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+
+        let marker = after.find(']').and_then(|close| {
+            let inner = &after[..close];
+            let is_marker = !inner.trim().is_empty()
+                && inner.chars().all(|c| c.is_ascii_digit() || c == ',' || c == ' ')
+                // already a link, e.g. [1](https://...)
+                && !after[close + 1..].starts_with('(');
+            let numbers: Option<Vec<usize>> = inner
+                .split(',')
+                .map(|n| n.trim().parse::<usize>().ok().filter(|n| (1..=citations.len()).contains(n)))
+                .collect();
+            is_marker.then_some(numbers).flatten().map(|numbers| (close, numbers))
+        });
+
+        match marker {
+            Some((close, numbers)) => {
+                for n in numbers {
+                    let citation = &citations[n - 1];
+                    output.push_str(&format!(
+                        "[\\[{}\\]]({} \"{} - {}\")",
+                        n,
+                        citation.link,
+                        citation.company.replace('"', "'"),
+                        citation.title.replace('"', "'")
+                    ));
+                }
+                rest = &after[close + 1..];
+            }
+            None => {
+                output.push('[');
+                rest = after;
+            }
+        }
+    }
+
+    output.push_str(rest);
+    output
+}
+
+impl From<ChatMessageView> for RagMessage {
+    fn from(message: ChatMessageView) -> Self {
+        RagMessage {
+            role: message.role,
+            content: message.content,
+            citations: message.citations.and_then(|c| serde_json::from_value(c).ok()),
+            timestamp: message
+                .created_at
+                .map(|t| t.and_utc().to_rfc3339())
+                .unwrap_or_default(),
+            verification: None,
+            context_usage: None,
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Debug)]
+enum ChatError {
+    Pool(String),
+    Database(diesel::result::Error),
+    NotFound,
+    Unauthorized,
+}
+
+#[cfg(feature = "ssr")]
+impl std::fmt::Display for ChatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChatError::Pool(e) => write!(f, "Pool error: {e}"),
+            ChatError::Database(e) => write!(f, "Database error: {e}"),
+            ChatError::NotFound => write!(f, "Chat session not found"),
+            ChatError::Unauthorized => write!(f, "Unauthorized"),
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl From<ChatError> for ServerFnError {
+    fn from(error: ChatError) -> Self {
+        ServerFnError::ServerError(error.to_string())
+    }
+}
+
+/// The signed-in user's id and a pooled connection.
+#[cfg(feature = "ssr")]
+async fn chat_user_connection() -> Result<
+    (i32, diesel_async::pooled_connection::deadpool::Object<diesel_async::AsyncPgConnection>),
+    ChatError,
+> {
+    use crate::components::drawing_rooms::get_authenticated_user_id;
+    use crate::state::AppState;
+
+    let user_id = get_authenticated_user_id().await
+        .map_err(|_| ChatError::Unauthorized)?;
+
+    let app_state = use_context::<AppState>()
+        .expect("Failed to get AppState from context");
+
+    let conn = app_state.pool
+        .get()
+        .await
+        .map_err(|e| ChatError::Pool(e.to_string()))?;
+
+    Ok((user_id, conn))
+}
+
+/// The signed-in user's chat sessions, most recently active first.
+#[server(
+    prefix = "/api",
+    endpoint = "list_chat_sessions",
+    input = GetUrl
+)]
+pub async fn list_chat_sessions() -> Result<Vec<ChatSessionView>, ServerFnError> {
+    use crate::models::ChatSession;
+
+    let (user_id, mut conn) = chat_user_connection().await?;
+
+    let sessions = ChatSession::list_for_user(user_id, &mut conn)
+        .await
+        .map_err(ChatError::Database)?;
+
+    Ok(sessions.into_iter().map(ChatSessionView::from).collect())
+}
+
+#[server(
+    prefix = "/api",
+    endpoint = "get_chat_messages",
+    input = GetUrl
+)]
+pub async fn get_chat_messages(session_id: Uuid) -> Result<Vec<ChatMessageView>, ServerFnError> {
+    use crate::models::ChatSession;
+
+    let (user_id, mut conn) = chat_user_connection().await?;
+
+    let session = ChatSession::find_owned(session_id, user_id, &mut conn)
+        .await
+        .map_err(ChatError::Database)?
+        .ok_or(ChatError::NotFound)?;
+
+    let messages = session.messages(&mut conn)
+        .await
+        .map_err(ChatError::Database)?;
+
+    Ok(messages.into_iter().map(ChatMessageView::from).collect())
+}
+
+#[server(
+    prefix = "/api",
+    endpoint = "rename_chat_session",
+    input = PostUrl
+)]
+pub async fn rename_chat_session(session_id: Uuid, title: String) -> Result<ChatSessionView, ServerFnError> {
+    use crate::models::ChatSession;
+
+    let title = title.trim();
+    if title.is_empty() {
+        return Err(ServerFnError::ServerError("Title cannot be empty".to_string()));
+    }
+    let title: String = title.chars().take(255).collect();
+
+    let (user_id, mut conn) = chat_user_connection().await?;
+
+    let session = ChatSession::find_owned(session_id, user_id, &mut conn)
+        .await
+        .map_err(ChatError::Database)?
+        .ok_or(ChatError::NotFound)?;
+
+    let renamed = session.rename(title, &mut conn)
+        .await
+        .map_err(ChatError::Database)?;
+
+    Ok(renamed.into())
+}
+
+#[server(
+    prefix = "/api",
+    endpoint = "delete_chat_session",
+    input = PostUrl
+)]
+pub async fn delete_chat_session(session_id: Uuid) -> Result<(), ServerFnError> {
+    use crate::models::ChatSession;
+
+    let (user_id, mut conn) = chat_user_connection().await?;
+
+    let deleted = ChatSession::delete_owned(session_id, user_id, &mut conn)
+        .await
+        .map_err(ChatError::Database)?;
+
+    if deleted == 0 {
+        return Err(ChatError::NotFound.into());
+    }
+
+    Ok(())
 }
 
 #[component]
@@ -40,9 +277,14 @@ pub fn RagChat() -> impl IntoView {
     let (is_loading, set_is_loading) = signal(false);
     let (current_response, set_current_response) = signal(String::new());
     let (current_citations, set_current_citations) = signal::<Vec<Citation>>(Vec::new());
+    let (current_verification, set_current_verification) = signal(Option::<AnswerVerification>::None);
+    let (current_context_usage, set_current_context_usage) = signal(Option::<ContextUsage>::None);
     let (search_type, set_search_type) = signal(SearchType::OpenAISemantic);
     let (current_stream_id, set_current_stream_id) = signal(Option::<String>::None);
     let (status_message, set_status_message) = signal(String::new());
+    let (active_session, set_active_session) = signal(Option::<Uuid>::None);
+    let (sessions_version, set_sessions_version) = signal(0u32);
+    let sessions = Resource::new(move || sessions_version.get(), |_| list_chat_sessions());
 
     let cancel_current_request = move || {
         if let Some(stream_id) = current_stream_id.get() {
@@ -69,6 +311,8 @@ pub fn RagChat() -> impl IntoView {
         set_is_loading(true);
         set_current_response(String::new());
         set_current_citations(Vec::new());
+        set_current_verification(None);
+        set_current_context_usage(None);
         set_status_message(String::new());
 
         // Add user message to chat
@@ -77,6 +321,8 @@ pub fn RagChat() -> impl IntoView {
             content: query.clone(),
             citations: None,
             timestamp: js_sys::Date::new_0().to_iso_string().as_string().unwrap(),
+            verification: None,
+            context_usage: None,
         };
 
         set_messages.update(|msgs| msgs.push(user_message));
@@ -110,16 +356,20 @@ pub fn RagChat() -> impl IntoView {
             set_current_stream_id(Some(stream_id.clone()));
 
             // Start SSE connection
-            let url = format!(
+            let mut url = format!(
                 "/api/rag-query?stream_id={}&query={}&search_type={}",
                 stream_id,
                 urlencoding::encode(&query),
                 match search_type.get() {
                     SearchType::OpenAISemantic => "openai",
                     SearchType::LocalSemantic => "local",
+                    SearchType::Hybrid => "hybrid",
                     SearchType::Basic => "basic",
                 }
             );
+            if let Some(session_id) = active_session.get_untracked() {
+                url.push_str(&format!("&session_id={}", session_id));
+            }
 
             let event_source = EventSource::new(&url).expect("Failed to connect to SSE endpoint");
             let event_source_clone = event_source.clone();
@@ -128,6 +378,12 @@ pub fn RagChat() -> impl IntoView {
                 if let Some(data) = event.data().as_string() {
                     match serde_json::from_str::<RagResponse>(&data) {
                         Ok(response) => match response.message_type.as_str() {
+                            "session" => {
+                                if let Some(id) = response.content.and_then(|c| Uuid::parse_str(&c).ok()) {
+                                    set_active_session(Some(id));
+                                    set_sessions_version.update(|v| *v += 1);
+                                }
+                            }
                             "status" => {
                                 if let Some(status) = response.content {
                                     set_status_message(status);
@@ -143,6 +399,12 @@ pub fn RagChat() -> impl IntoView {
                                     set_current_response.update(|resp| resp.push_str(&content));
                                 }
                             }
+                            "verification" => {
+                                set_current_verification(response.verification);
+                            }
+                            "context_usage" => {
+                                set_current_context_usage(response.context_usage);
+                            }
                             "error" => {
                                 if let Some(error) = response.content {
                                     log::error!("RAG error: {}", error);
@@ -153,6 +415,8 @@ pub fn RagChat() -> impl IntoView {
                                         content: format!("Sorry, I encountered an error: {}", error),
                                         citations: None,
                                         timestamp: js_sys::Date::new_0().to_iso_string().as_string().unwrap(),
+                                        verification: None,
+                                        context_usage: None,
                                     };
                                     set_messages.update(|msgs| msgs.push(error_message));
                                 }
@@ -172,11 +436,15 @@ pub fn RagChat() -> impl IntoView {
                                         Some(current_citations.get())
                                     },
                                     timestamp: js_sys::Date::new_0().to_iso_string().as_string().unwrap(),
+                                    verification: current_verification.get(),
+                                    context_usage: current_context_usage.get(),
                                 };
 
                                 set_messages.update(|msgs| msgs.push(assistant_message));
                                 set_current_response(String::new());
                                 set_current_citations(Vec::new());
+                                set_current_verification(None);
+                                set_current_context_usage(None);
                                 set_status_message(String::new());
                                 set_is_loading(false);
                                 set_current_stream_id(None);
@@ -204,6 +472,8 @@ pub fn RagChat() -> impl IntoView {
                     content: "Sorry, I encountered a connection error. Please try again.".to_string(),
                     citations: None,
                     timestamp: js_sys::Date::new_0().to_iso_string().as_string().unwrap(),
+                    verification: None,
+                    context_usage: None,
                 };
                 set_messages.update(|msgs| msgs.push(error_message));
                 
@@ -225,18 +495,59 @@ pub fn RagChat() -> impl IntoView {
         }
     };
 
-    let clear_chat = move |_| {
+    let clear_chat = move || {
         set_messages(Vec::new());
         set_current_response(String::new());
         set_current_citations(Vec::new());
+        set_current_verification(None);
+        set_current_context_usage(None);
         set_status_message(String::new());
         if is_loading.get() {
             cancel_current_request();
         }
     };
 
+    let new_chat = move |_| {
+        clear_chat();
+        set_active_session(None);
+    };
+
+    let open_session = Callback::new(move |session_id: Uuid| {
+        if is_loading.get_untracked() || active_session.get_untracked() == Some(session_id) {
+            return;
+        }
+        clear_chat();
+        set_active_session(Some(session_id));
+        wasm_bindgen_futures::spawn_local(async move {
+            match get_chat_messages(session_id).await {
+                Ok(history) => {
+                    // ignore the result if the user switched sessions meanwhile
+                    if active_session.get_untracked() == Some(session_id) {
+                        set_messages(history.into_iter().map(RagMessage::from).collect());
+                    }
+                }
+                Err(e) => log::error!("Failed to load chat session: {}", e),
+            }
+        });
+    });
+
+    let sessions_changed = Callback::new(move |deleted: Option<Uuid>| {
+        if deleted.is_some() && deleted == active_session.get_untracked() {
+            clear_chat();
+            set_active_session(None);
+        }
+        set_sessions_version.update(|v| *v += 1);
+    });
+
     view! {
-        <div class="flex flex-col h-[500px] sm:h-[700px] max-w-6xl mx-auto bg-gray-200 dark:bg-teal-900 rounded-lg shadow-lg dark:shadow-teal-highlight border-l-4 border-seafoam-500 dark:border-mint-800">
+        <div class="flex h-[500px] sm:h-[700px] max-w-6xl mx-auto bg-gray-200 dark:bg-teal-900 rounded-lg shadow-lg dark:shadow-teal-highlight border-l-4 border-seafoam-500 dark:border-mint-800">
+            <ChatSessionList
+                sessions=sessions
+                active_session=active_session
+                on_open=open_session
+                on_changed=sessions_changed
+            />
+        <div class="flex flex-col flex-1 min-w-0">
             // Header
             <div class="flex flex-col sm:flex-row sm:items-center justify-between p-3 sm:p-4 border-b border-seafoam-300 dark:border-mint-700 space-y-3 sm:space-y-0">
                 <h2 class="text-lg sm:text-xl font-semibold text-seafoam-800 dark:text-mint-600">
@@ -251,6 +562,7 @@ pub fn RagChat() -> impl IntoView {
                             let new_type = match value.as_str() {
                                 "openai" => SearchType::OpenAISemantic,
                                 "local" => SearchType::LocalSemantic,
+                                "hybrid" => SearchType::Hybrid,
                                 _ => SearchType::OpenAISemantic,
                             };
                             set_search_type(new_type);
@@ -258,12 +570,13 @@ pub fn RagChat() -> impl IntoView {
                     >
                         <option value="openai">"OpenAI Search"</option>
                         <option value="local">"Local Search"</option>
+                        <option value="hybrid">"Hybrid Search"</option>
                     </select>
                     <button
                         class="px-2 py-1 sm:px-3 text-xs sm:text-sm bg-gray-500 hover:bg-gray-600 active:bg-gray-700 text-white rounded-md transition-colors touch-manipulation"
-                        on:click=clear_chat
+                        on:click=new_chat
                     >
-                        "Clear Chat"
+                        "New Chat"
                     </button>
                     {move || {
                         is_loading
@@ -341,7 +654,10 @@ pub fn RagChat() -> impl IntoView {
                                         .then(|| {
                                             view! {
                                                 <div class="text-seafoam-800 dark:text-mint-600 w-full text-sm sm:text-base">
-                                                    <MarkdownRenderer content=current class="" />
+                                                    <MarkdownRenderer
+                                                        content=link_citation_markers(&current, &citations)
+                                                        class=""
+                                                    />
                                                     <span class="animate-pulse text-seafoam-600 dark:text-seafoam-400">
                                                         "|"
                                                     </span>
@@ -398,11 +714,149 @@ pub fn RagChat() -> impl IntoView {
                 </div>
             </div>
         </div>
+        </div>
     }.into_any()
 }
 
 #[component]
-fn MessageBubble(message: RagMessage) -> impl IntoView {
+fn ChatSessionList(
+    sessions: Resource<Result<Vec<ChatSessionView>, ServerFnError>>,
+    active_session: ReadSignal<Option<Uuid>>,
+    #[prop(into)] on_open: Callback<Uuid>,
+    /// Called after a rename (`None`) or a delete (`Some(id)`).
+    #[prop(into)] on_changed: Callback<Option<Uuid>>,
+) -> impl IntoView {
+    let (editing, set_editing) = signal(Option::<Uuid>::None);
+    let (edit_title, set_edit_title) = signal(String::new());
+
+    let save_title = move |session_id: Uuid| {
+        let title = edit_title.get_untracked();
+        set_editing(None);
+        wasm_bindgen_futures::spawn_local(async move {
+            match rename_chat_session(session_id, title).await {
+                Ok(_) => on_changed.run(None),
+                Err(e) => log::error!("Failed to rename chat session: {}", e),
+            }
+        });
+    };
+
+    let delete_session = move |session_id: Uuid| {
+        wasm_bindgen_futures::spawn_local(async move {
+            match delete_chat_session(session_id).await {
+                Ok(()) => on_changed.run(Some(session_id)),
+                Err(e) => log::error!("Failed to delete chat session: {}", e),
+            }
+        });
+    };
+
+    view! {
+        <aside class="hidden sm:flex flex-col w-56 shrink-0 border-r border-seafoam-300 dark:border-mint-700">
+            <div class="p-3 text-sm font-semibold text-seafoam-800 dark:text-mint-600 border-b border-seafoam-300 dark:border-mint-700">
+                "Conversations"
+            </div>
+            <div class="flex-1 overflow-y-auto">
+                <Suspense fallback=|| {
+                    view! { <p class="p-3 text-xs text-seafoam-600 dark:text-mint-400">"Loading..."</p> }
+                }>
+                    {move || {
+                        sessions
+                            .get()
+                            .map(|result| match result {
+                                Ok(list) if list.is_empty() => {
+                                    view! {
+                                        <p class="p-3 text-xs text-seafoam-600 dark:text-mint-400">
+                                            "No conversations yet."
+                                        </p>
+                                    }
+                                        .into_any()
+                                }
+                                Ok(list) => {
+                                    list.into_iter()
+                                        .map(|session| {
+                                            let id = session.id;
+                                            let title = session.title.clone();
+                                            view! {
+                                                <div class=move || {
+                                                    format!(
+                                                        "group flex items-center gap-1 px-3 py-2 text-xs cursor-pointer {}",
+                                                        if active_session.get() == Some(id) {
+                                                            "bg-seafoam-200 dark:bg-teal-700"
+                                                        } else {
+                                                            "hover:bg-seafoam-100 dark:hover:bg-teal-800"
+                                                        },
+                                                    )
+                                                }>
+                                                    {move || {
+                                                        if editing.get() == Some(id) {
+                                                            view! {
+                                                                <input
+                                                                    class="flex-1 min-w-0 px-1 py-0.5 rounded bg-seafoam-50 dark:bg-teal-700 text-seafoam-800 dark:text-mint-600 border border-seafoam-300 dark:border-mint-600"
+                                                                    prop:value=edit_title
+                                                                    on:input=move |ev| set_edit_title(event_target_value(&ev))
+                                                                    on:keydown=move |ev: web_sys::KeyboardEvent| {
+                                                                        match ev.key().as_str() {
+                                                                            "Enter" => save_title(id),
+                                                                            "Escape" => set_editing(None),
+                                                                            _ => {}
+                                                                        }
+                                                                    }
+                                                                />
+                                                            }
+                                                                .into_any()
+                                                        } else {
+                                                            let title = title.clone();
+                                                            let edit_value = title.clone();
+                                                            view! {
+                                                                <span
+                                                                    class="flex-1 min-w-0 truncate text-seafoam-800 dark:text-mint-600"
+                                                                    title=title.clone()
+                                                                    on:click=move |_| on_open.run(id)
+                                                                >
+                                                                    {title}
+                                                                </span>
+                                                                <button
+                                                                    class="hidden group-hover:inline text-seafoam-600 dark:text-mint-400 hover:underline"
+                                                                    on:click=move |_| {
+                                                                        set_edit_title(edit_value.clone());
+                                                                        set_editing(Some(id));
+                                                                    }
+                                                                >
+                                                                    "Rename"
+                                                                </button>
+                                                                <button
+                                                                    class="hidden group-hover:inline text-salmon-500 hover:underline"
+                                                                    on:click=move |_| delete_session(id)
+                                                                >
+                                                                    "Delete"
+                                                                </button>
+                                                            }
+                                                                .into_any()
+                                                        }
+                                                    }}
+                                                </div>
+                                            }
+                                        })
+                                        .collect_view()
+                                        .into_any()
+                                }
+                                Err(e) => {
+                                    view! {
+                                        <p class="p-3 text-xs text-salmon-500">
+                                            {format!("Error loading conversations: {}", e)}
+                                        </p>
+                                    }
+                                        .into_any()
+                                }
+                            })
+                    }}
+                </Suspense>
+            </div>
+        </aside>
+    }
+}
+
+#[component]
+pub(crate) fn MessageBubble(message: RagMessage) -> impl IntoView {
     let is_user = message.role == "user";
     
     view! {
@@ -415,13 +869,27 @@ fn MessageBubble(message: RagMessage) -> impl IntoView {
                     "bg-gray-200 dark:bg-teal-900 text-seafoam-800 dark:text-mint-600 border-aqua-500 dark:border-seafoam-400"
                 },
             )>
-                <div class=format!(
-                    "whitespace-pre-wrap {}",
-                    if is_user { "" } else { "prose prose-sm dark:prose-invert max-w-none" },
-                )>{message.content}</div>
+                {if is_user {
+                    view! { <div class="whitespace-pre-wrap">{message.content}</div> }.into_any()
+                } else {
+                    let content = link_citation_markers(
+                        &message.content,
+                        message.citations.as_deref().unwrap_or_default(),
+                    );
+                    view! {
+                        <div class="prose prose-sm dark:prose-invert max-w-none">
+                            <MarkdownRenderer content=content class="" />
+                        </div>
+                    }
+                        .into_any()
+                }}
+
+                {message.verification.map(|verification| view! { <VerificationNotice verification=verification /> })}
 
                 {message.citations.map(|citations| view! { <CitationsList citations=citations /> })}
 
+                {message.context_usage.map(|usage| view! { <ContextUsageNote usage=usage /> })}
+
                 <div class="text-xs opacity-70 mt-2 text-seafoam-600 dark:text-mint-400">
                     {format_timestamp(&message.timestamp)}
                 </div>
@@ -430,6 +898,62 @@ fn MessageBubble(message: RagMessage) -> impl IntoView {
     }
 }
 
+#[component]
+fn ContextUsageNote(usage: ContextUsage) -> impl IntoView {
+    let mut details = format!(
+        "Context: {} of {} tokens, {} passages from {} posts",
+        usage.tokens_used, usage.token_budget, usage.passages, usage.posts_used,
+    );
+    if usage.posts_dropped > 0 {
+        details.push_str(&format!(", {} posts left out", usage.posts_dropped));
+    }
+    if usage.duplicates_skipped > 0 {
+        details.push_str(&format!(", {} duplicate passages skipped", usage.duplicates_skipped));
+    }
+
+    view! { <div class="mt-2 text-xs opacity-70 text-seafoam-600 dark:text-mint-400">{details}</div> }
+}
+
+#[component]
+fn VerificationNotice(verification: AnswerVerification) -> impl IntoView {
+    if verification.unsupported.is_empty() {
+        return view! {
+            <div class="mt-3 text-xs text-aquamarine-dark dark:text-aquamarine">
+                {format!("✓ All {} checked statements are supported by the sources.", verification.checked)}
+            </div>
+        }
+            .into_any();
+    }
+
+    view! {
+        <div class="mt-3 p-3 text-xs rounded border-l-4 border-orange bg-orange/10 text-seafoam-800 dark:text-mint-600">
+            <div class="font-medium mb-1">
+                {format!(
+                    "⚠ {} of {} statements may not be supported by the sources:",
+                    verification.unsupported.len(),
+                    verification.checked,
+                )}
+            </div>
+            <ul class="list-disc pl-4 space-y-1">
+                {verification
+                    .unsupported
+                    .into_iter()
+                    .map(|claim| {
+                        view! {
+                            <li>
+                                <span class="italic">{claim.sentence}</span>
+                                " — "
+                                {claim.reason}
+                            </li>
+                        }
+                    })
+                    .collect_view()}
+            </ul>
+        </div>
+    }
+        .into_any()
+}
+
 #[component]
 fn CitationsList(citations: Vec<Citation>) -> impl IntoView {
     view! {
@@ -439,11 +963,14 @@ fn CitationsList(citations: Vec<Citation>) -> impl IntoView {
             </div>
             <div class="grid gap-2 max-h-48 overflow-y-auto">
                 <For
-                    each=move || citations.clone()
-                    key=|citation| citation.link.clone()
-                    children=move |citation| {
+                    each=move || citations.clone().into_iter().enumerate()
+                    key=|(_, citation)| citation.link.clone()
+                    children=move |(i, citation)| {
                         view! {
                             <div class="p-3 bg-seafoam-100 dark:bg-teal-700 rounded border-l-4 border-seafoam-400 dark:border-mint-500">
+                                <span class="text-xs font-semibold text-seafoam-600 dark:text-mint-500 mr-1">
+                                    {format!("[{}]", i + 1)}
+                                </span>
                                 <a
                                     href=citation.link.clone()
                                     target="_blank"
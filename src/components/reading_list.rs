@@ -0,0 +1,576 @@
+use leptos::prelude::*;
+use leptos_router::hooks::use_query_map;
+use server_fn::codec::{GetUrl, PostUrl};
+use std::collections::HashSet;
+
+use crate::auth::context::AuthContext;
+use crate::components::poasts::{BlogPoast, Poast};
+use crate::models::{ReadingListView, ReadingStateView};
+
+/// The signed-in user's id and a pooled connection.
+#[cfg(feature = "ssr")]
+async fn reading_user_connection() -> Result<
+    (i32, diesel_async::pooled_connection::deadpool::Object<diesel_async::AsyncPgConnection>),
+    ServerFnError,
+> {
+    use crate::components::drawing_rooms::get_authenticated_user_id;
+    use crate::state::AppState;
+
+    let user_id = get_authenticated_user_id()
+        .await
+        .map_err(|_| ServerFnError::new("Not authenticated"))?;
+
+    let app_state = use_context::<AppState>()
+        .expect("Failed to get AppState from context");
+    let conn = app_state.pool
+        .get()
+        .await
+        .map_err(|e| ServerFnError::new(format!("Pool error: {}", e)))?;
+
+    Ok((user_id, conn))
+}
+
+/// Bookmarked and read post ids; empty when nobody is signed in.
+#[server(
+    prefix = "/api",
+    endpoint = "get_reading_state",
+    input = GetUrl
+)]
+pub async fn get_reading_state() -> Result<ReadingStateView, ServerFnError> {
+    use crate::components::drawing_rooms::get_authenticated_user_id;
+    use crate::models::{PostBookmark, PostRead};
+
+    if get_authenticated_user_id().await.is_err() {
+        return Ok(ReadingStateView::default());
+    }
+
+    let (user_id, mut conn) = reading_user_connection().await?;
+    let bookmarked = PostBookmark::post_ids_for_user(user_id, &mut conn)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+    let read = PostRead::post_ids_for_user(user_id, &mut conn)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+
+    Ok(ReadingStateView { bookmarked, read })
+}
+
+#[server(
+    prefix = "/api",
+    endpoint = "set_post_bookmarked",
+    input = PostUrl
+)]
+pub async fn set_post_bookmarked(post_id: i32, bookmarked: bool) -> Result<(), ServerFnError> {
+    use crate::models::PostBookmark;
+
+    let (user_id, mut conn) = reading_user_connection().await?;
+    PostBookmark::set(user_id, post_id, bookmarked, &mut conn)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+    Ok(())
+}
+
+#[server(
+    prefix = "/api",
+    endpoint = "set_post_read",
+    input = PostUrl
+)]
+pub async fn set_post_read(post_id: i32, read: bool) -> Result<(), ServerFnError> {
+    use crate::models::PostRead;
+
+    let (user_id, mut conn) = reading_user_connection().await?;
+    PostRead::set(user_id, post_id, read, &mut conn)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+    Ok(())
+}
+
+#[server(
+    prefix = "/api",
+    endpoint = "list_reading_lists",
+    input = GetUrl
+)]
+pub async fn list_reading_lists() -> Result<Vec<ReadingListView>, ServerFnError> {
+    use crate::models::ReadingList;
+
+    let (user_id, mut conn) = reading_user_connection().await?;
+    let lists = ReadingList::list_for_user(user_id, &mut conn)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+
+    Ok(lists.into_iter().map(ReadingListView::from).collect())
+}
+
+#[server(
+    prefix = "/api",
+    endpoint = "create_reading_list",
+    input = PostUrl
+)]
+pub async fn create_reading_list(
+    name: String,
+    description: Option<String>,
+) -> Result<ReadingListView, ServerFnError> {
+    use crate::models::{NewReadingList, ReadingList, MAX_READING_LISTS};
+
+    let name: String = name.trim().chars().take(100).collect();
+    if name.is_empty() {
+        return Err(ServerFnError::new("Give the list a name"));
+    }
+    let description = description
+        .map(|d| d.trim().to_string())
+        .filter(|d| !d.is_empty());
+
+    let (user_id, mut conn) = reading_user_connection().await?;
+    let count = ReadingList::count_for_user(user_id, &mut conn)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+    if count >= MAX_READING_LISTS {
+        return Err(ServerFnError::new(format!("You can have at most {} reading lists", MAX_READING_LISTS)));
+    }
+
+    let new_list = NewReadingList { user_id, name, description };
+    let list = ReadingList::create(&new_list, &mut conn)
+        .await
+        .map_err(|e| match e {
+            diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::UniqueViolation, _) => {
+                ServerFnError::new("You already have a list with that name")
+            }
+            e => ServerFnError::new(format!("Database error: {}", e)),
+        })?;
+
+    Ok(ReadingListView::from((list, Vec::new())))
+}
+
+#[server(
+    prefix = "/api",
+    endpoint = "delete_reading_list",
+    input = PostUrl
+)]
+pub async fn delete_reading_list(id: i32) -> Result<(), ServerFnError> {
+    use crate::models::ReadingList;
+
+    let (user_id, mut conn) = reading_user_connection().await?;
+    let deleted = ReadingList::delete_owned(id, user_id, &mut conn)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+    if deleted == 0 {
+        return Err(ServerFnError::new("Reading list not found"));
+    }
+    Ok(())
+}
+
+/// Add a post to one of the user's lists, or take it off.
+#[server(
+    prefix = "/api",
+    endpoint = "set_in_reading_list",
+    input = PostUrl
+)]
+pub async fn set_in_reading_list(list_id: i32, post_id: i32, included: bool) -> Result<(), ServerFnError> {
+    use crate::models::ReadingList;
+
+    let (user_id, mut conn) = reading_user_connection().await?;
+    let list = ReadingList::find_owned(list_id, user_id, &mut conn)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?
+        .ok_or_else(|| ServerFnError::new("Reading list not found"))?;
+
+    let result = if included {
+        list.add_post(post_id, &mut conn).await
+    } else {
+        list.remove_post(post_id, &mut conn).await
+    };
+    result.map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+    Ok(())
+}
+
+/// Posts in a reading list, or the user's bookmarks when `list_id` is `None`. Newest posts
+/// first, at most one page.
+#[server(
+    prefix = "/api",
+    endpoint = "get_saved_posts",
+    input = GetUrl
+)]
+pub async fn get_saved_posts(list_id: Option<i32>) -> Result<Vec<Poast>, ServerFnError> {
+    use crate::components::poasts::{fetch_post_page, PostFilter, PostScope, MAX_PAGE_SIZE};
+    use crate::models::{PostBookmark, ReadingList};
+
+    let (user_id, mut conn) = reading_user_connection().await?;
+    let post_ids = match list_id {
+        Some(id) => {
+            let list = ReadingList::find_owned(id, user_id, &mut conn)
+                .await
+                .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?
+                .ok_or_else(|| ServerFnError::new("Reading list not found"))?;
+            list.post_ids(&mut conn).await
+        }
+        None => PostBookmark::post_ids_for_user(user_id, &mut conn).await,
+    }
+    .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+    drop(conn);
+
+    if post_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let filter = PostFilter { limit: Some(MAX_PAGE_SIZE), ..Default::default() };
+    let scope = PostScope { post_ids: Some(post_ids), ..Default::default() };
+    Ok(fetch_post_page(&filter, &scope).await?.posts)
+}
+
+/// The signed-in user's bookmarks and reads, shared by every post card on the page.
+#[derive(Clone, Copy)]
+pub struct ReadingState {
+    pub bookmarked: RwSignal<HashSet<i32>>,
+    pub read: RwSignal<HashSet<i32>>,
+}
+
+impl ReadingState {
+    /// Flip a post's bookmark, rolling back if the server refuses.
+    pub fn toggle_bookmark(&self, post_id: i32) {
+        let bookmarked = self.bookmarked;
+        let now_bookmarked = !bookmarked.get_untracked().contains(&post_id);
+        Self::set_local(bookmarked, post_id, now_bookmarked);
+        wasm_bindgen_futures::spawn_local(async move {
+            if let Err(e) = set_post_bookmarked(post_id, now_bookmarked).await {
+                log::error!("Failed to update bookmark: {:?}", e);
+                Self::set_local(bookmarked, post_id, !now_bookmarked);
+            }
+        });
+    }
+
+    pub fn set_read(&self, post_id: i32, now_read: bool) {
+        let read = self.read;
+        if read.get_untracked().contains(&post_id) == now_read {
+            return;
+        }
+        Self::set_local(read, post_id, now_read);
+        wasm_bindgen_futures::spawn_local(async move {
+            if let Err(e) = set_post_read(post_id, now_read).await {
+                log::error!("Failed to update read state: {:?}", e);
+                Self::set_local(read, post_id, !now_read);
+            }
+        });
+    }
+
+    fn set_local(set: RwSignal<HashSet<i32>>, post_id: i32, present: bool) {
+        set.update(|ids| {
+            if present {
+                ids.insert(post_id);
+            } else {
+                ids.remove(&post_id);
+            }
+        });
+    }
+}
+
+/// Loads the reading state whenever someone signs in and clears it when they sign out.
+#[component]
+pub fn ReadingStateProvider(children: Children) -> impl IntoView {
+    let auth = use_context::<AuthContext>();
+    let state = ReadingState {
+        bookmarked: RwSignal::new(HashSet::new()),
+        read: RwSignal::new(HashSet::new()),
+    };
+
+    Effect::new(move |_| {
+        let signed_in = auth.as_ref().is_some_and(|a| a.is_authenticated.get());
+        if !signed_in {
+            state.bookmarked.set(HashSet::new());
+            state.read.set(HashSet::new());
+            return;
+        }
+        wasm_bindgen_futures::spawn_local(async move {
+            match get_reading_state().await {
+                Ok(loaded) => {
+                    state.bookmarked.set(loaded.bookmarked.into_iter().collect());
+                    state.read.set(loaded.read.into_iter().collect());
+                }
+                Err(e) => log::error!("Failed to load reading state: {:?}", e),
+            }
+        });
+    });
+
+    provide_context(state);
+
+    view! { {children()} }
+}
+
+/// Bookmark and read toggles for one post, shown to signed-in users.
+#[component]
+pub fn ReadingControls(post_id: i32) -> impl IntoView {
+    let auth = use_context::<AuthContext>();
+    let is_authenticated = move || auth.as_ref().is_some_and(|a| a.is_authenticated.get());
+    let state = use_context::<ReadingState>();
+
+    let button_class = "text-xs md:text-sm text-seafoam-600 dark:text-aqua-400 hover:text-seafoam-700 dark:hover:text-aqua-300 transition-colors";
+
+    move || {
+        let state = state.filter(|_| is_authenticated())?;
+        let bookmarked = move || state.bookmarked.with(|ids| ids.contains(&post_id));
+        let read = move || state.read.with(|ids| ids.contains(&post_id));
+        Some(view! {
+            <button class=button_class on:click=move |_| state.toggle_bookmark(post_id)>
+                {move || if bookmarked() { "Bookmarked" } else { "Bookmark" }}
+            </button>
+            <button class=button_class on:click=move |_| state.set_read(post_id, !read())>
+                {move || if read() { "Mark Unread" } else { "Mark Read" }}
+            </button>
+        })
+    }
+}
+
+/// Checkboxes for each of the user's reading lists, to file a post under them.
+#[component]
+pub fn ReadingListPicker(post_id: i32) -> impl IntoView {
+    let auth = use_context::<AuthContext>();
+    let is_authenticated = move || auth.as_ref().is_some_and(|a| a.is_authenticated.get());
+    let lists = Resource::new(move || is_authenticated(), |signed_in| async move {
+        if signed_in { list_reading_lists().await } else { Ok(Vec::new()) }
+    });
+
+    let toggle = move |list_id: i32, included: bool| {
+        wasm_bindgen_futures::spawn_local(async move {
+            if let Err(e) = set_in_reading_list(list_id, post_id, included).await {
+                log::error!("Failed to update reading list: {:?}", e);
+            }
+        });
+    };
+
+    view! {
+        <Suspense fallback=|| ()>
+            {move || {
+                lists
+                    .get()
+                    .and_then(|result| result.ok())
+                    .filter(|lists| !lists.is_empty())
+                    .map(|lists| {
+                        view! {
+                            <div class="flex flex-wrap items-center gap-3 text-sm text-gray-600 dark:text-gray-300">
+                                <span>"Reading lists:"</span>
+                                {lists
+                                    .into_iter()
+                                    .map(|list| {
+                                        let list_id = list.id;
+                                        view! {
+                                            <label class="flex items-center gap-1">
+                                                <input
+                                                    type="checkbox"
+                                                    prop:checked=list.post_ids.contains(&post_id)
+                                                    on:change=move |ev| toggle(list_id, event_target_checked(&ev))
+                                                />
+                                                {list.name}
+                                            </label>
+                                        }
+                                    })
+                                    .collect_view()}
+                            </div>
+                        }
+                    })
+            }}
+        </Suspense>
+    }
+}
+
+/// Bookmarks and reading lists, with a form for new lists. `?list=<id>` picks the list shown.
+#[component]
+pub fn ReadingLists() -> impl IntoView {
+    let auth = use_context::<AuthContext>();
+    let is_authenticated = move || auth.as_ref().is_some_and(|a| a.is_authenticated.get());
+
+    let query = use_query_map();
+    let selected = Memo::new(move |_| query.get().get("list").and_then(|id| id.parse::<i32>().ok()));
+
+    let (version, set_version) = signal(0u32);
+    let lists = Resource::new(move || version.get(), |_| list_reading_lists());
+    let posts = Resource::new(move || (selected.get(), version.get()), |(list_id, _)| get_saved_posts(list_id));
+
+    let (name, set_name) = signal(String::new());
+    let (description, set_description) = signal(String::new());
+    let (error, set_error) = signal(Option::<String>::None);
+
+    let create = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        set_error(None);
+        let list_name = name.get_untracked();
+        let list_description = Some(description.get_untracked());
+        wasm_bindgen_futures::spawn_local(async move {
+            match create_reading_list(list_name, list_description).await {
+                Ok(_) => {
+                    set_name(String::new());
+                    set_description(String::new());
+                    set_version.update(|v| *v += 1);
+                }
+                Err(e) => set_error(Some(e.to_string())),
+            }
+        });
+    };
+
+    let delete = move |id: i32| {
+        wasm_bindgen_futures::spawn_local(async move {
+            match delete_reading_list(id).await {
+                Ok(()) => {
+                    if let Some(window) = web_sys::window() {
+                        let _ = window.location().set_href("/reading-list");
+                    }
+                }
+                Err(e) => set_error(Some(e.to_string())),
+            }
+        });
+    };
+
+    let link_class = move |active: bool| {
+        if active {
+            "block px-3 py-2 rounded-md text-sm bg-seafoam-600 dark:bg-seafoam-500 text-white"
+        } else {
+            "block px-3 py-2 rounded-md text-sm text-gray-700 dark:text-gray-200 hover:bg-gray-200 dark:hover:bg-teal-800"
+        }
+    };
+
+    view! {
+        <div class="container mx-auto px-4 pb-8">
+            {move || {
+                if !is_authenticated() {
+                    return view! {
+                        <div class="text-center text-gray-600 dark:text-gray-300 p-6 bg-white dark:bg-teal-800 rounded-lg">
+                            <a href="/admin" class="text-teal-600 dark:text-aqua-400 underline">"Log in"</a>
+                            " to keep bookmarks and reading lists."
+                        </div>
+                    }
+                        .into_any();
+                }
+
+                view! {
+                    <div class="flex flex-col lg:flex-row gap-4">
+                        <aside class="lg:w-64 shrink-0 space-y-3">
+                            <a href="/reading-list" class=move || link_class(selected.get().is_none())>
+                                "Bookmarks"
+                            </a>
+                            <Suspense fallback=|| ()>
+                                {move || {
+                                    lists
+                                        .get()
+                                        .and_then(|result| result.ok())
+                                        .map(|lists| {
+                                            view! {
+                                                <ul class="space-y-1">
+                                                    {lists
+                                                        .into_iter()
+                                                        .map(|list| {
+                                                            let id = list.id;
+                                                            view! {
+                                                                <li>
+                                                                    <a
+                                                                        href=format!("/reading-list?list={}", id)
+                                                                        class=move || link_class(selected.get() == Some(id))
+                                                                    >
+                                                                        {format!("{} ({})", list.name, list.post_ids.len())}
+                                                                    </a>
+                                                                </li>
+                                                            }
+                                                        })
+                                                        .collect_view()}
+                                                </ul>
+                                            }
+                                        })
+                                }}
+                            </Suspense>
+                            <form class="space-y-2" on:submit=create>
+                                <input
+                                    class="w-full p-2 rounded-md bg-gray-100 dark:bg-teal-700 text-gray-800 dark:text-gray-200 border border-teal-500 dark:border-seafoam-500"
+                                    placeholder="New list name"
+                                    prop:value=name
+                                    on:input=move |ev| set_name(event_target_value(&ev))
+                                />
+                                <input
+                                    class="w-full p-2 rounded-md bg-gray-100 dark:bg-teal-700 text-gray-800 dark:text-gray-200 border border-teal-500 dark:border-seafoam-500"
+                                    placeholder="Description (optional)"
+                                    prop:value=description
+                                    on:input=move |ev| set_description(event_target_value(&ev))
+                                />
+                                <button
+                                    type="submit"
+                                    class="w-full px-4 py-2 bg-seafoam-600 dark:bg-seafoam-500 text-white rounded-lg hover:bg-seafoam-700"
+                                >
+                                    "Create list"
+                                </button>
+                            </form>
+                            {move || error.get().map(|e| view! { <p class="text-sm text-salmon-500">{e}</p> })}
+                        </aside>
+
+                        <main class="flex-1 min-w-0 space-y-2">
+                            {move || {
+                                let list = selected
+                                    .get()
+                                    .and_then(|id| {
+                                        lists
+                                            .get()
+                                            .and_then(|result| result.ok())
+                                            .and_then(|lists| lists.into_iter().find(|list| list.id == id))
+                                    });
+                                list.map(|list| {
+                                    let id = list.id;
+                                    view! {
+                                        <div class="flex justify-between items-start px-4">
+                                            <div>
+                                                <h2 class="text-lg font-semibold text-teal-600 dark:text-mint-400">
+                                                    {list.name}
+                                                </h2>
+                                                {list
+                                                    .description
+                                                    .map(|d| {
+                                                        view! { <p class="text-sm text-gray-600 dark:text-gray-300">{d}</p> }
+                                                    })}
+                                            </div>
+                                            <button
+                                                class="text-xs px-2 py-1 rounded bg-salmon-500 text-white"
+                                                on:click=move |_| delete(id)
+                                            >
+                                                "Delete list"
+                                            </button>
+                                        </div>
+                                    }
+                                })
+                            }}
+                            <Suspense fallback=|| {
+                                view! { <p class="text-center text-teal-600 dark:text-aqua-400">"Loading..."</p> }
+                            }>
+                                {move || {
+                                    posts
+                                        .get()
+                                        .map(|result| match result {
+                                            Ok(posts) if posts.is_empty() => {
+                                                view! {
+                                                    <div class="text-center text-gray-500 dark:text-gray-400 p-6">
+                                                        "Nothing here yet"
+                                                    </div>
+                                                }
+                                                    .into_any()
+                                            }
+                                            Ok(posts) => {
+                                                view! {
+                                                    <div class="grid grid-cols-1 sm:grid-cols-2 xl:grid-cols-3 gap-4">
+                                                        {posts
+                                                            .into_iter()
+                                                            .map(|poast| view! { <BlogPoast poast=poast /> })
+                                                            .collect_view()}
+                                                    </div>
+                                                }
+                                                    .into_any()
+                                            }
+                                            Err(e) => {
+                                                view! {
+                                                    <div class="text-center text-salmon-500">{e.to_string()}</div>
+                                                }
+                                                    .into_any()
+                                            }
+                                        })
+                                }}
+                            </Suspense>
+                        </main>
+                    </div>
+                }
+                    .into_any()
+            }}
+        </div>
+    }
+}
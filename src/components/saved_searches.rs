@@ -0,0 +1,432 @@
+use leptos::prelude::*;
+use server_fn::codec::{GetUrl, PostUrl};
+
+use crate::auth::context::AuthContext;
+use crate::models::{SavedSearchView, WebhookDeliveryView};
+
+/// Delivery log entries shown under the saved searches.
+const RECENT_DELIVERIES: i64 = 25;
+
+/// The signed-in user's id and a pooled connection.
+#[cfg(feature = "ssr")]
+async fn saved_search_user_connection() -> Result<
+    (i32, diesel_async::pooled_connection::deadpool::Object<diesel_async::AsyncPgConnection>),
+    ServerFnError,
+> {
+    use crate::components::drawing_rooms::get_authenticated_user_id;
+    use crate::state::AppState;
+
+    let user_id = get_authenticated_user_id()
+        .await
+        .map_err(|_| ServerFnError::new("Not authenticated"))?;
+
+    let app_state = use_context::<AppState>()
+        .expect("Failed to get AppState from context");
+    let conn = app_state.pool
+        .get()
+        .await
+        .map_err(|e| ServerFnError::new(format!("Pool error: {}", e)))?;
+
+    Ok((user_id, conn))
+}
+
+#[server(
+    prefix = "/api",
+    endpoint = "list_saved_searches",
+    input = GetUrl
+)]
+pub async fn list_saved_searches() -> Result<Vec<SavedSearchView>, ServerFnError> {
+    use crate::models::SavedSearch;
+
+    let (user_id, mut conn) = saved_search_user_connection().await?;
+    let searches = SavedSearch::list_for_user(user_id, &mut conn)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+
+    Ok(searches.into_iter().map(SavedSearchView::from).collect())
+}
+
+#[server(
+    prefix = "/api",
+    endpoint = "create_saved_search",
+    input = PostUrl
+)]
+pub async fn create_saved_search(
+    name: String,
+    kind: String,
+    query: String,
+    min_similarity: Option<i32>,
+    webhook_url: String,
+) -> Result<SavedSearchView, ServerFnError> {
+    use crate::models::{NewSavedSearch, SavedSearch, MAX_SAVED_SEARCHES, SAVED_SEARCH_KINDS};
+    use crate::webhook_service::webhooks::{generate_secret, validate_webhook_url};
+
+    let name: String = name.trim().chars().take(100).collect();
+    let query = query.trim().to_string();
+    let webhook_url = webhook_url.trim().to_string();
+    if name.is_empty() || query.is_empty() {
+        return Err(ServerFnError::new("Name and query are required"));
+    }
+    if !SAVED_SEARCH_KINDS.contains(&kind.as_str()) {
+        return Err(ServerFnError::new(format!("Unknown search kind '{}'", kind)));
+    }
+    validate_webhook_url(&webhook_url).map_err(ServerFnError::new)?;
+
+    let (user_id, mut conn) = saved_search_user_connection().await?;
+    let existing = SavedSearch::count_for_user(user_id, &mut conn)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+    if existing >= MAX_SAVED_SEARCHES {
+        return Err(ServerFnError::new(format!("You can keep at most {} saved searches", MAX_SAVED_SEARCHES)));
+    }
+
+    let search = SavedSearch::create(
+        &NewSavedSearch {
+            user_id,
+            name,
+            kind,
+            query,
+            min_similarity: min_similarity.unwrap_or(50).clamp(0, 100),
+            webhook_url,
+            webhook_secret: generate_secret(),
+        },
+        &mut conn,
+    )
+    .await
+    .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+
+    Ok(search.into())
+}
+
+#[server(
+    prefix = "/api",
+    endpoint = "set_saved_search_enabled",
+    input = PostUrl
+)]
+pub async fn set_saved_search_enabled(id: i32, enabled: bool) -> Result<(), ServerFnError> {
+    use crate::models::SavedSearch;
+
+    let (user_id, mut conn) = saved_search_user_connection().await?;
+    let updated = SavedSearch::set_enabled_owned(id, user_id, enabled, &mut conn)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+    if updated == 0 {
+        return Err(ServerFnError::new("Saved search not found"));
+    }
+    Ok(())
+}
+
+#[server(
+    prefix = "/api",
+    endpoint = "delete_saved_search",
+    input = PostUrl
+)]
+pub async fn delete_saved_search(id: i32) -> Result<(), ServerFnError> {
+    use crate::models::SavedSearch;
+
+    let (user_id, mut conn) = saved_search_user_connection().await?;
+    let deleted = SavedSearch::delete_owned(id, user_id, &mut conn)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+    if deleted == 0 {
+        return Err(ServerFnError::new("Saved search not found"));
+    }
+    Ok(())
+}
+
+/// Recent webhook deliveries across the signed-in user's saved searches.
+#[server(
+    prefix = "/api",
+    endpoint = "list_webhook_deliveries",
+    input = GetUrl
+)]
+pub async fn list_webhook_deliveries() -> Result<Vec<WebhookDeliveryView>, ServerFnError> {
+    use crate::models::WebhookDelivery;
+
+    let (user_id, mut conn) = saved_search_user_connection().await?;
+    let deliveries = WebhookDelivery::recent_for_user(user_id, RECENT_DELIVERIES, &mut conn)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+
+    Ok(deliveries.into_iter().map(WebhookDeliveryView::from).collect())
+}
+
+#[component]
+pub fn SavedSearches() -> impl IntoView {
+    let auth = use_context::<AuthContext>();
+    let is_authenticated = move || auth.as_ref().is_some_and(|a| a.is_authenticated.get());
+
+    let (version, set_version) = signal(0u32);
+    let searches = Resource::new(move || version.get(), |_| list_saved_searches());
+    let deliveries = Resource::new(move || version.get(), |_| list_webhook_deliveries());
+    let refresh = move || set_version.update(|v| *v += 1);
+
+    let (name, set_name) = signal(String::new());
+    let (kind, set_kind) = signal("company".to_string());
+    let (query, set_query) = signal(String::new());
+    let (min_similarity, set_min_similarity) = signal(50i32);
+    let (webhook_url, set_webhook_url) = signal(String::new());
+    let (form_error, set_form_error) = signal(Option::<String>::None);
+    let (saving, set_saving) = signal(false);
+
+    let submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        set_saving(true);
+        set_form_error(None);
+        let similarity = (kind.get_untracked() == "semantic").then(|| min_similarity.get_untracked());
+        let args = (
+            name.get_untracked(),
+            kind.get_untracked(),
+            query.get_untracked(),
+            similarity,
+            webhook_url.get_untracked(),
+        );
+        wasm_bindgen_futures::spawn_local(async move {
+            match create_saved_search(args.0, args.1, args.2, args.3, args.4).await {
+                Ok(_) => {
+                    set_name(String::new());
+                    set_query(String::new());
+                    set_webhook_url(String::new());
+                    refresh();
+                }
+                Err(e) => set_form_error(Some(e.to_string())),
+            }
+            set_saving(false);
+        });
+    };
+
+    let toggle = move |id: i32, enabled: bool| {
+        wasm_bindgen_futures::spawn_local(async move {
+            match set_saved_search_enabled(id, enabled).await {
+                Ok(()) => refresh(),
+                Err(e) => log::error!("Failed to update saved search: {}", e),
+            }
+        });
+    };
+
+    let remove = move |id: i32| {
+        wasm_bindgen_futures::spawn_local(async move {
+            match delete_saved_search(id).await {
+                Ok(()) => refresh(),
+                Err(e) => log::error!("Failed to delete saved search: {}", e),
+            }
+        });
+    };
+
+    let input_class = "w-full p-2 rounded-md bg-gray-100 dark:bg-teal-700 text-gray-800 dark:text-gray-200 border border-teal-500 dark:border-seafoam-500";
+
+    view! {
+        <div class="container mx-auto px-4 pb-8 max-w-4xl space-y-6">
+            {move || {
+                if !is_authenticated() {
+                    return view! {
+                        <div class="text-center text-gray-600 dark:text-gray-300 p-6 bg-white dark:bg-teal-800 rounded-lg">
+                            <a href="/admin" class="text-teal-600 dark:text-aqua-400 underline">"Log in"</a>
+                            " to get webhook notifications for new posts."
+                        </div>
+                    }
+                        .into_any();
+                }
+
+                view! {
+                    <div class="space-y-6">
+                        <form
+                            class="bg-white dark:bg-teal-800 rounded-lg p-4 grid grid-cols-1 md:grid-cols-2 gap-3"
+                            on:submit=submit
+                        >
+                            <input
+                                class=input_class
+                                placeholder="Name"
+                                prop:value=name
+                                on:input=move |ev| set_name(event_target_value(&ev))
+                            />
+                            <select
+                                class=input_class
+                                prop:value=kind
+                                on:change=move |ev| set_kind(event_target_value(&ev))
+                            >
+                                <option value="company">"Company publishes"</option>
+                                <option value="keyword">"Keywords appear"</option>
+                                <option value="semantic">"Similar to a question"</option>
+                            </select>
+                            <input
+                                class=input_class
+                                placeholder=move || match kind.get().as_str() {
+                                    "company" => "Company name",
+                                    "keyword" => "All of these words",
+                                    _ => "What should new posts be about?",
+                                }
+                                prop:value=query
+                                on:input=move |ev| set_query(event_target_value(&ev))
+                            />
+                            <input
+                                class=input_class
+                                type="url"
+                                placeholder="https://hooks.example.com/..."
+                                prop:value=webhook_url
+                                on:input=move |ev| set_webhook_url(event_target_value(&ev))
+                            />
+                            <Show when=move || kind.get() == "semantic">
+                                <label class="flex items-center gap-2 text-sm text-gray-600 dark:text-gray-300">
+                                    "Minimum similarity"
+                                    <input
+                                        type="range"
+                                        min="20"
+                                        max="90"
+                                        prop:value=move || min_similarity.get().to_string()
+                                        on:input=move |ev| {
+                                            set_min_similarity(event_target_value(&ev).parse().unwrap_or(50))
+                                        }
+                                    />
+                                    {move || format!("{}%", min_similarity.get())}
+                                </label>
+                            </Show>
+                            <div class="md:col-span-2 flex items-center justify-between">
+                                <span class="text-sm text-salmon-500">{move || form_error.get()}</span>
+                                <button
+                                    type="submit"
+                                    class="px-4 py-2 bg-seafoam-600 dark:bg-seafoam-500 text-white rounded-lg hover:bg-seafoam-700 disabled:bg-gray-400"
+                                    prop:disabled=saving
+                                >
+                                    "Save search"
+                                </button>
+                            </div>
+                        </form>
+
+                        <Suspense fallback=|| ()>
+                            {move || {
+                                searches
+                                    .get()
+                                    .map(|result| match result {
+                                        Ok(searches) if searches.is_empty() => {
+                                            view! {
+                                                <p class="text-center text-gray-500 dark:text-gray-400">
+                                                    "No saved searches yet."
+                                                </p>
+                                            }
+                                                .into_any()
+                                        }
+                                        Ok(searches) => {
+                                            view! {
+                                                <ul class="space-y-2">
+                                                    {searches
+                                                        .into_iter()
+                                                        .map(|search| {
+                                                            let id = search.id;
+                                                            let enabled = search.enabled;
+                                                            view! {
+                                                                <li class="bg-white dark:bg-teal-800 rounded-lg p-3 text-sm space-y-1">
+                                                                    <div class="flex justify-between items-center gap-2">
+                                                                        <span class="font-semibold text-teal-600 dark:text-mint-400">
+                                                                            {search.name}
+                                                                        </span>
+                                                                        <div class="flex gap-2">
+                                                                            <button
+                                                                                class="text-xs px-2 py-1 rounded bg-gray-200 dark:bg-teal-700"
+                                                                                on:click=move |_| toggle(id, !enabled)
+                                                                            >
+                                                                                {if enabled { "Pause" } else { "Resume" }}
+                                                                            </button>
+                                                                            <button
+                                                                                class="text-xs px-2 py-1 rounded bg-salmon-500 text-white"
+                                                                                on:click=move |_| remove(id)
+                                                                            >
+                                                                                "Delete"
+                                                                            </button>
+                                                                        </div>
+                                                                    </div>
+                                                                    <p class="text-gray-600 dark:text-gray-300">
+                                                                        {if search.kind == "semantic" {
+                                                                            format!("{}: {} (≥ {}%)", search.kind, search.query, search.min_similarity)
+                                                                        } else {
+                                                                            format!("{}: {}", search.kind, search.query)
+                                                                        }}
+                                                                    </p>
+                                                                    <p class="text-xs text-gray-500 dark:text-gray-400 break-all">
+                                                                        {search.webhook_url}
+                                                                    </p>
+                                                                    <details class="text-xs text-gray-500 dark:text-gray-400">
+                                                                        <summary>"Signing secret"</summary>
+                                                                        <code class="break-all">{search.webhook_secret}</code>
+                                                                    </details>
+                                                                </li>
+                                                            }
+                                                        })
+                                                        .collect_view()}
+                                                </ul>
+                                            }
+                                                .into_any()
+                                        }
+                                        Err(e) => {
+                                            view! {
+                                                <p class="text-center text-salmon-500">
+                                                    {format!("Error loading saved searches: {}", e)}
+                                                </p>
+                                            }
+                                                .into_any()
+                                        }
+                                    })
+                            }}
+                        </Suspense>
+
+                        <Suspense fallback=|| ()>
+                            {move || {
+                                deliveries
+                                    .get()
+                                    .and_then(|result| result.ok())
+                                    .filter(|deliveries| !deliveries.is_empty())
+                                    .map(|deliveries| {
+                                        view! {
+                                            <div class="bg-white dark:bg-teal-800 rounded-lg p-3">
+                                                <h2 class="text-sm font-semibold text-teal-600 dark:text-mint-400 mb-2">
+                                                    "Recent deliveries"
+                                                </h2>
+                                                <table class="w-full text-xs text-gray-600 dark:text-gray-300">
+                                                    <tbody>
+                                                        {deliveries
+                                                            .into_iter()
+                                                            .map(|delivery| {
+                                                                let status_class = match delivery.status.as_str() {
+                                                                    "delivered" => "text-aquamarine-dark dark:text-aquamarine",
+                                                                    "failed" => "text-salmon-500",
+                                                                    _ => "text-gray-500",
+                                                                };
+                                                                view! {
+                                                                    <tr title=delivery.error.clone().unwrap_or_default()>
+                                                                        <td class=status_class>{delivery.status}</td>
+                                                                        <td>
+                                                                            {delivery
+                                                                                .response_status
+                                                                                .map(|s| s.to_string())
+                                                                                .unwrap_or_else(|| "-".to_string())}
+                                                                        </td>
+                                                                        <td>{format!("{} tries", delivery.attempts)}</td>
+                                                                        <td class="truncate max-w-xs">
+                                                                            <a href=delivery.post_link.clone() target="_blank" class="underline">
+                                                                                {delivery.post_link.clone()}
+                                                                            </a>
+                                                                        </td>
+                                                                        <td>
+                                                                            {delivery
+                                                                                .created_at
+                                                                                .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                                                                                .unwrap_or_default()}
+                                                                        </td>
+                                                                    </tr>
+                                                                }
+                                                            })
+                                                            .collect_view()}
+                                                    </tbody>
+                                                </table>
+                                            </div>
+                                        }
+                                    })
+                            }}
+                        </Suspense>
+                    </div>
+                }
+                    .into_any()
+            }}
+        </div>
+    }
+}
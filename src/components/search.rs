@@ -14,6 +14,7 @@ pub enum SearchType {
     Basic,
     OpenAISemantic,
     LocalSemantic,
+    Hybrid,
 }
 
 impl SearchType {
@@ -22,6 +23,7 @@ impl SearchType {
             SearchType::Basic => "Search blog posts...",
             SearchType::OpenAISemantic => "Search blog posts using OpenAI...",
             SearchType::LocalSemantic => "Search blog posts using local (all-MiniLM-L6-v2) AI...",
+            SearchType::Hybrid => "Search blog posts by keywords and meaning...",
         }
     }
 }
@@ -83,6 +85,7 @@ pub fn BlogSearch(#[prop(into)] on_search: Callback<SearchParams>) -> impl IntoV
                         let new_type = match value.as_str() {
                             "openai" => SearchType::OpenAISemantic,
                             "local" => SearchType::LocalSemantic,
+                            "hybrid" => SearchType::Hybrid,
                             _ => SearchType::Basic,
                         };
                         set_search_type.set(new_type);
@@ -92,11 +95,13 @@ pub fn BlogSearch(#[prop(into)] on_search: Callback<SearchParams>) -> impl IntoV
                         SearchType::Basic => "basic",
                         SearchType::OpenAISemantic => "openai",
                         SearchType::LocalSemantic => "local",
+                        SearchType::Hybrid => "hybrid",
                     }
                 >
                     <option value="basic">"Basic Search"</option>
                     <option value="openai">"OpenAI Semantic Search"</option>
                     <option value="local">"all-MiniLM-L6-v2 Semantic Search"</option>
+                    <option value="hybrid">"Hybrid Search (Keywords + OpenAI)"</option>
                 </select>
             </div>
             <div class="relative">
@@ -0,0 +1,268 @@
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+use server_fn::codec::GetUrl;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct MonthCount {
+    pub month: String,
+    pub count: i32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TopicTrend {
+    pub id: i32,
+    pub label: String,
+    pub buzzwords: Vec<String>,
+    pub size: i32,
+    /// Oldest first, one entry per month in the requested window (zero-filled).
+    pub monthly: Vec<MonthCount>,
+    /// Companies with the most posts on this topic in the window, most first.
+    pub top_companies: Vec<(String, i32)>,
+    /// Posts in the most recent half of the window divided by the older half.
+    pub growth: f32,
+}
+
+/// Topics from the latest clustering run, fastest growing first.
+#[server(
+    prefix = "/api",
+    endpoint = "get_topic_trends",
+    input = GetUrl
+)]
+pub async fn get_topic_trends(months: Option<u32>) -> Result<Vec<TopicTrend>, ServerFnError> {
+    use chrono::{Datelike, Months, Utc};
+    use log::{debug, error};
+    use std::collections::HashMap;
+
+    #[derive(Deserialize)]
+    struct ClusterRow {
+        id: i32,
+        label: String,
+        buzzwords: Option<Vec<String>>,
+        size: i32,
+    }
+
+    #[derive(Deserialize)]
+    struct MonthRow {
+        cluster_id: i32,
+        month: String,
+        post_count: i32,
+        companies: HashMap<String, i32>,
+    }
+
+    let months = months.unwrap_or(12).clamp(2, 60);
+    let today = Utc::now().date_naive().with_day(1).unwrap_or_default();
+    let window: Vec<String> = (0..months)
+        .rev()
+        .filter_map(|back| today.checked_sub_months(Months::new(back)))
+        .map(|d| d.format("%Y-%m").to_string())
+        .collect();
+
+    let supabase = crate::supabase::get_client();
+
+    let clusters_body = supabase
+        .from("topic_clusters")
+        .select("id, label, buzzwords, size")
+        .execute()
+        .await?
+        .text()
+        .await?;
+    let clusters: Vec<ClusterRow> = serde_json::from_str(&clusters_body).map_err(|e| {
+        error!("Failed to parse topic clusters: {}", e);
+        ServerFnError::new(format!("Failed to parse topic clusters: {}", e))
+    })?;
+
+    // 40 topics over 60 months can exceed PostgREST's max-rows, so page through them
+    let page_size = 1000;
+    let mut month_rows: Vec<MonthRow> = Vec::new();
+    loop {
+        let start = month_rows.len();
+        let months_body = supabase
+            .from("topic_cluster_months")
+            .select("cluster_id, month, post_count, companies")
+            .gte("month", &window[0])
+            .order("cluster_id.asc,month.asc")
+            .range(start, start + page_size - 1)
+            .execute()
+            .await?
+            .text()
+            .await?;
+        let page: Vec<MonthRow> = serde_json::from_str(&months_body).map_err(|e| {
+            error!("Failed to parse topic months: {}", e);
+            ServerFnError::new(format!("Failed to parse topic months: {}", e))
+        })?;
+
+        let last_page = page.len() < page_size;
+        month_rows.extend(page);
+        if last_page {
+            break;
+        }
+    }
+
+    let mut by_cluster: HashMap<i32, Vec<MonthRow>> = HashMap::new();
+    for row in month_rows {
+        by_cluster.entry(row.cluster_id).or_default().push(row);
+    }
+
+    let half = window.len() / 2;
+    let mut trends: Vec<TopicTrend> = clusters
+        .into_iter()
+        .map(|cluster| {
+            let rows = by_cluster.remove(&cluster.id).unwrap_or_default();
+            let counts: HashMap<&str, i32> = rows.iter().map(|r| (r.month.as_str(), r.post_count)).collect();
+
+            let monthly: Vec<MonthCount> = window
+                .iter()
+                .map(|month| MonthCount {
+                    month: month.clone(),
+                    count: counts.get(month.as_str()).copied().unwrap_or(0),
+                })
+                .collect();
+
+            let older: i32 = monthly[..half].iter().map(|m| m.count).sum();
+            let recent: i32 = monthly[half..].iter().map(|m| m.count).sum();
+            // +1 smoothing so brand-new topics rank high without dividing by zero
+            let growth = (recent as f32 + 1.0) / (older as f32 + 1.0);
+
+            let mut companies: HashMap<String, i32> = HashMap::new();
+            for row in &rows {
+                for (company, count) in &row.companies {
+                    *companies.entry(company.clone()).or_insert(0) += count;
+                }
+            }
+            let mut top_companies: Vec<(String, i32)> = companies.into_iter().collect();
+            top_companies.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            top_companies.truncate(5);
+
+            TopicTrend {
+                id: cluster.id,
+                label: cluster.label,
+                buzzwords: cluster.buzzwords.unwrap_or_default(),
+                size: cluster.size,
+                monthly,
+                top_companies,
+                growth,
+            }
+        })
+        .filter(|trend| trend.monthly.iter().any(|m| m.count > 0))
+        .collect();
+
+    trends.sort_by(|a, b| b.growth.partial_cmp(&a.growth).unwrap_or(std::cmp::Ordering::Equal));
+
+    debug!("Returning {} topic trends over {} months", trends.len(), months);
+    Ok(trends)
+}
+
+#[component]
+pub fn Trends() -> impl IntoView {
+    let (months, set_months) = signal(12u32);
+    let trends = Resource::new(move || months.get(), |months| get_topic_trends(Some(months)));
+
+    view! {
+        <div class="container mx-auto px-4 pb-8 space-y-4">
+            <div class="flex items-center gap-2">
+                <span class="text-sm text-gray-600 dark:text-gray-300">"Window"</span>
+                <select
+                    class="p-2 rounded-md bg-gray-100 dark:bg-teal-800 text-gray-800 dark:text-gray-200 border border-teal-500 dark:border-seafoam-500"
+                    on:change=move |ev| set_months(event_target_value(&ev).parse().unwrap_or(12))
+                    prop:value=move || months.get().to_string()
+                >
+                    <option value="6">"6 months"</option>
+                    <option value="12">"12 months"</option>
+                    <option value="24">"24 months"</option>
+                </select>
+            </div>
+            <Suspense fallback=|| {
+                view! { <p class="text-center text-teal-600 dark:text-aqua-400">"Loading..."</p> }
+            }>
+                {move || {
+                    trends
+                        .get()
+                        .map(|result| match result {
+                            Ok(trends) if trends.is_empty() => {
+                                view! {
+                                    <div class="text-center text-gray-500 dark:text-gray-400">
+                                        "No topics yet. Run the cluster_topics job first."
+                                    </div>
+                                }
+                                    .into_any()
+                            }
+                            Ok(trends) => {
+                                view! {
+                                    <div class="grid grid-cols-1 md:grid-cols-2 xl:grid-cols-3 gap-4">
+                                        {trends
+                                            .into_iter()
+                                            .map(|trend| view! { <TopicCard trend=trend /> })
+                                            .collect_view()}
+                                    </div>
+                                }
+                                    .into_any()
+                            }
+                            Err(e) => {
+                                view! {
+                                    <div class="text-center text-salmon-500">
+                                        {format!("Error loading trends: {}", e)}
+                                    </div>
+                                }
+                                    .into_any()
+                            }
+                        })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+fn TopicCard(trend: TopicTrend) -> impl IntoView {
+    let max_count = trend.monthly.iter().map(|m| m.count).max().unwrap_or(0).max(1);
+    let (direction, direction_class) = if trend.growth >= 1.25 {
+        ("rising", "text-aquamarine-dark dark:text-aquamarine")
+    } else if trend.growth <= 0.8 {
+        ("falling", "text-orange-dark dark:text-orange")
+    } else {
+        ("steady", "text-gray-500 dark:text-gray-400")
+    };
+
+    view! {
+        <article class="bg-white dark:bg-teal-800 border-2 border-gray-200 dark:border-teal-700 p-4 rounded-lg shadow-md">
+            <div class="flex justify-between items-start gap-2">
+                <h2 class="text-base lg:text-lg font-semibold text-teal-600 dark:text-mint-400">
+                    {trend.label.clone()}
+                </h2>
+                <span class=format!("text-xs font-medium {}", direction_class)>
+                    {format!("{} x{:.1}", direction, trend.growth)}
+                </span>
+            </div>
+            <p class="text-xs text-gray-500 dark:text-gray-400 mb-2">
+                {format!("{} posts overall", trend.size)}
+            </p>
+            <div class="flex items-end gap-0.5 h-16 mb-2">
+                {trend
+                    .monthly
+                    .iter()
+                    .map(|m| {
+                        let height = (m.count as f32 / max_count as f32 * 100.0).round() as i32;
+                        view! {
+                            <div
+                                class="flex-1 bg-seafoam-500 dark:bg-aqua-500 rounded-t"
+                                style=format!("height: {}%", height.max(2))
+                                title=format!("{}: {} posts", m.month, m.count)
+                            ></div>
+                        }
+                    })
+                    .collect_view()}
+            </div>
+            <p class="text-xs text-gray-600 dark:text-gray-300 mb-1">
+                {trend.buzzwords.iter().take(6).cloned().collect::<Vec<_>>().join(", ")}
+            </p>
+            <p class="text-xs text-gray-500 dark:text-gray-400">
+                {trend
+                    .top_companies
+                    .iter()
+                    .map(|(company, count)| format!("{} ({})", company, count))
+                    .collect::<Vec<_>>()
+                    .join(" · ")}
+            </p>
+        </article>
+    }
+}
@@ -0,0 +1,375 @@
+#[cfg(feature = "ssr")]
+pub mod digest {
+    use async_openai::{
+        config::OpenAIConfig,
+        types::{ChatCompletionRequestSystemMessage, ChatCompletionRequestUserMessage, CreateChatCompletionRequest},
+        Client,
+    };
+    use chrono::{Duration, NaiveDate, Utc};
+    use log::{info, warn};
+    use serde::{Deserialize, Serialize};
+    use std::collections::HashMap;
+    use std::str::FromStr;
+    use thiserror::Error;
+
+    use crate::components::markdown::markdown_to_html;
+    use crate::database::db::DbPool;
+    use crate::models::{Digest, NewDigest};
+
+    /// Posts summarised in one digest, newest first.
+    const MAX_DIGEST_POSTS: usize = 60;
+    /// Characters of each post's stored summary sent to the model.
+    const SUMMARY_CHARS: usize = 600;
+    const OTHER_GROUP: &str = "Other";
+
+    #[derive(Error, Debug)]
+    pub enum DigestError {
+        #[error("Supabase error: {0}")]
+        Supabase(String),
+
+        #[error("JSON error: {0}")]
+        Json(#[from] serde_json::Error),
+
+        #[error("OpenAI error: {0}")]
+        OpenAI(String),
+
+        #[error("Database error: {0}")]
+        Database(#[from] diesel::result::Error),
+
+        #[error("Pool error: {0}")]
+        Pool(String),
+
+        #[error("No posts published between {0} and {1}")]
+        NoPosts(NaiveDate, NaiveDate),
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum DigestPeriod {
+        Daily,
+        Weekly,
+    }
+
+    impl DigestPeriod {
+        pub fn as_str(&self) -> &'static str {
+            match self {
+                DigestPeriod::Daily => "daily",
+                DigestPeriod::Weekly => "weekly",
+            }
+        }
+
+        fn days(&self) -> i64 {
+            match self {
+                DigestPeriod::Daily => 1,
+                DigestPeriod::Weekly => 7,
+            }
+        }
+    }
+
+    impl FromStr for DigestPeriod {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s.to_lowercase().as_str() {
+                "daily" => Ok(DigestPeriod::Daily),
+                "weekly" => Ok(DigestPeriod::Weekly),
+                other => Err(format!("unknown digest period '{}'", other)),
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum DigestGrouping {
+        Company,
+        Topic,
+    }
+
+    impl DigestGrouping {
+        pub fn as_str(&self) -> &'static str {
+            match self {
+                DigestGrouping::Company => "company",
+                DigestGrouping::Topic => "topic",
+            }
+        }
+    }
+
+    impl FromStr for DigestGrouping {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s.to_lowercase().as_str() {
+                "company" => Ok(DigestGrouping::Company),
+                "topic" => Ok(DigestGrouping::Topic),
+                other => Err(format!("unknown digest grouping '{}'", other)),
+            }
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct DigestOptions {
+        pub period: DigestPeriod,
+        pub grouping: DigestGrouping,
+        /// Exclusive end of the window; defaults to today (UTC), so a daily digest covers yesterday.
+        pub ends_on: Option<NaiveDate>,
+    }
+
+    impl Default for DigestOptions {
+        fn default() -> Self {
+            Self {
+                period: DigestPeriod::Daily,
+                grouping: DigestGrouping::Company,
+                ends_on: None,
+            }
+        }
+    }
+
+    /// A numbered post cited in a digest, stored in `digests.sources`.
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct DigestSource {
+        pub number: usize,
+        pub title: String,
+        pub company: String,
+        pub link: String,
+        pub published_at: String,
+    }
+
+    #[derive(Debug, Clone, Deserialize)]
+    struct DigestPost {
+        published_at: String,
+        company: String,
+        title: String,
+        link: String,
+        summary: Option<String>,
+        buzzwords: Option<Vec<String>>,
+    }
+
+    #[derive(Deserialize)]
+    struct ClusterPost {
+        link: String,
+        topic_clusters: Option<ClusterLabel>,
+    }
+
+    #[derive(Deserialize)]
+    struct ClusterLabel {
+        label: String,
+    }
+
+    async fn fetch_window(starts_on: NaiveDate, ends_on: NaiveDate) -> Result<Vec<DigestPost>, DigestError> {
+        let response = crate::supabase::get_client()
+            .from("poasts")
+            .select("published_at, company, title, link, summary, buzzwords")
+            .gte("published_at", starts_on.to_string())
+            .lt("published_at", ends_on.to_string())
+            .order("published_at.desc")
+            .limit(MAX_DIGEST_POSTS)
+            .execute()
+            .await
+            .map_err(|e| DigestError::Supabase(e.to_string()))?;
+
+        let body = response.text().await.map_err(|e| DigestError::Supabase(e.to_string()))?;
+        Ok(serde_json::from_str(&body)?)
+    }
+
+    /// Topic label for each link from the last clustering run.
+    async fn fetch_topic_labels(links: &[String]) -> Result<HashMap<String, String>, DigestError> {
+        let response = crate::supabase::get_client()
+            .from("topic_cluster_posts")
+            .select("link, topic_clusters(label)")
+            .in_("link", links)
+            .execute()
+            .await
+            .map_err(|e| DigestError::Supabase(e.to_string()))?;
+
+        let body = response.text().await.map_err(|e| DigestError::Supabase(e.to_string()))?;
+        let rows: Vec<ClusterPost> = serde_json::from_str(&body)?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| row.topic_clusters.map(|cluster| (row.link, cluster.label)))
+            .collect())
+    }
+
+    /// Posts grouped by company or topic, largest group first, with "Other" last.
+    async fn group_posts(
+        posts: Vec<DigestPost>,
+        grouping: DigestGrouping,
+    ) -> Result<Vec<(String, Vec<DigestPost>)>, DigestError> {
+        let labels = match grouping {
+            DigestGrouping::Company => HashMap::new(),
+            DigestGrouping::Topic => {
+                let links: Vec<String> = posts.iter().map(|p| p.link.clone()).collect();
+                fetch_topic_labels(&links).await.unwrap_or_else(|e| {
+                    warn!("Topic labels unavailable, grouping everything under '{}': {}", OTHER_GROUP, e);
+                    HashMap::new()
+                })
+            }
+        };
+
+        let mut groups: Vec<(String, Vec<DigestPost>)> = Vec::new();
+        for post in posts {
+            let key = match grouping {
+                DigestGrouping::Company => post.company.clone(),
+                DigestGrouping::Topic => labels.get(&post.link).cloned().unwrap_or_else(|| OTHER_GROUP.to_string()),
+            };
+            match groups.iter_mut().find(|(name, _)| *name == key) {
+                Some((_, members)) => members.push(post),
+                None => groups.push((key, vec![post])),
+            }
+        }
+
+        groups.sort_by(|a, b| {
+            (a.0 == OTHER_GROUP)
+                .cmp(&(b.0 == OTHER_GROUP))
+                .then_with(|| b.1.len().cmp(&a.1.len()))
+                .then_with(|| a.0.cmp(&b.0))
+        });
+        Ok(groups)
+    }
+
+    fn clip(text: &str, max_chars: usize) -> String {
+        if text.chars().count() <= max_chars {
+            return text.to_string();
+        }
+        let mut clipped: String = text.chars().take(max_chars).collect();
+        clipped.push('…');
+        clipped
+    }
+
+    async fn write_editorial(
+        client: &Client<OpenAIConfig>,
+        title: &str,
+        groups: &[(String, Vec<(usize, DigestPost)>)],
+    ) -> Result<String, DigestError> {
+        let mut material = String::new();
+        for (group, posts) in groups {
+            material.push_str(&format!("## {}\n", group));
+            for (number, post) in posts {
+                material.push_str(&format!(
+                    "[{}] {} ({}, {})\nBuzzwords: {}\nSummary: {}\n\n",
+                    number,
+                    post.title,
+                    post.company,
+                    post.published_at.chars().take(10).collect::<String>(),
+                    post.buzzwords.clone().unwrap_or_default().join(", "),
+                    clip(post.summary.as_deref().unwrap_or("(no summary)"), SUMMARY_CHARS),
+                ));
+            }
+        }
+
+        let request = CreateChatCompletionRequest {
+            model: "gpt-4o-mini".to_string(),
+            messages: vec![
+                ChatCompletionRequestSystemMessage {
+                    content: "You are the editor of a digest of engineering blog posts.".into(),
+                    name: None,
+                }
+                .into(),
+                ChatCompletionRequestUserMessage {
+                    content: format!(
+                        "Write the body of '{}' in Markdown. Start with a two or three sentence introduction \
+                        on the main themes, then one '## ' section per group below, in the same order and with \
+                        the same heading, each a short paragraph on what those posts cover. Cite posts with their \
+                        bracketed numbers like [3] straight after the claim they support, and only use the numbers \
+                        given. Do not add a title or a list of sources.\n\n{}",
+                        title, material
+                    )
+                    .into(),
+                    name: None,
+                }
+                .into(),
+            ],
+            max_completion_tokens: Some(1500),
+            ..Default::default()
+        };
+
+        let response = client
+            .chat()
+            .create(request)
+            .await
+            .map_err(|e| DigestError::OpenAI(e.to_string()))?;
+        Ok(response
+            .choices
+            .first()
+            .and_then(|choice| choice.message.content.clone())
+            .unwrap_or_default()
+            .trim()
+            .to_string())
+    }
+
+    /// Write (or rewrite) the digest for the window ending on `options.ends_on`, built from the
+    /// summaries and buzzwords stored when each post was ingested.
+    pub async fn generate_digest(options: DigestOptions, pool: &DbPool) -> Result<Digest, DigestError> {
+        let ends_on = options.ends_on.unwrap_or_else(|| Utc::now().date_naive());
+        let starts_on = ends_on - Duration::days(options.period.days());
+
+        let posts = fetch_window(starts_on, ends_on).await?;
+        if posts.is_empty() {
+            return Err(DigestError::NoPosts(starts_on, ends_on));
+        }
+        info!(
+            "Writing {} {} digest for {} to {} from {} posts",
+            options.period.as_str(),
+            options.grouping.as_str(),
+            starts_on,
+            ends_on,
+            posts.len()
+        );
+
+        // number posts in the order they appear in the digest
+        let mut sources = Vec::new();
+        let mut numbered: Vec<(String, Vec<(usize, DigestPost)>)> = Vec::new();
+        for (group, members) in group_posts(posts, options.grouping).await? {
+            let members = members
+                .into_iter()
+                .map(|post| {
+                    let number = sources.len() + 1;
+                    sources.push(DigestSource {
+                        number,
+                        title: post.title.clone(),
+                        company: post.company.clone(),
+                        link: post.link.clone(),
+                        published_at: post.published_at.clone(),
+                    });
+                    (number, post)
+                })
+                .collect();
+            numbered.push((group, members));
+        }
+
+        let title = match options.period {
+            DigestPeriod::Daily => format!("Daily digest: {}", starts_on.format("%B %-d, %Y")),
+            DigestPeriod::Weekly => format!(
+                "Weekly digest: {} – {}",
+                starts_on.format("%B %-d"),
+                (ends_on - Duration::days(1)).format("%B %-d, %Y")
+            ),
+        };
+
+        let openai = Client::with_config(OpenAIConfig::default());
+        let body = write_editorial(&openai, &title, &numbered).await?;
+
+        let mut markdown = format!("# {}\n\n{}\n\n## Sources\n\n", title, body);
+        for source in &sources {
+            markdown.push_str(&format!(
+                "{}. [{}]({}) — {}\n",
+                source.number, source.title, source.link, source.company
+            ));
+        }
+        let html = markdown_to_html(&markdown);
+
+        let new_digest = NewDigest {
+            period: options.period.as_str().to_string(),
+            grouped_by: options.grouping.as_str().to_string(),
+            starts_on,
+            ends_on,
+            title,
+            markdown,
+            html,
+            post_count: sources.len() as i32,
+            sources: serde_json::to_value(&sources)?,
+        };
+
+        let mut conn = pool.get().await.map_err(|e| DigestError::Pool(e.to_string()))?;
+        let digest = Digest::upsert(&new_digest, &mut conn).await?;
+        info!("Stored digest {} '{}'", digest.id, digest.title);
+        Ok(digest)
+    }
+}
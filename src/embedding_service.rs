@@ -12,8 +12,11 @@ use axum::response::sse::Event;
 use std::error::Error;
 use log::{info, error};
 
+use sha2::{Digest, Sha256};
+
 use crate::components::poasts::Poast;
 use crate::server_fn::{invalidate_poasts_cache, RssProgressUpdate};
+use crate::vector_search_service::vector_search::{persist_ann_indexes, upsert_embedding};
 
 // will need to run this in supabase
 const _MIGRATION_SQL: &str = r#"
@@ -22,129 +25,214 @@ CREATE TABLE post_embeddings (
     embedding vector(1536)
 );
 CREATE INDEX ON post_embeddings USING ivfflat (embedding vector_cosine_ops);
+
+-- content hash + model version per vector, so rewritten summaries can be detected
+ALTER TABLE post_embeddings
+    ADD COLUMN embedding_hash TEXT,
+    ADD COLUMN embedding_model TEXT,
+    ADD COLUMN minilm_hash TEXT,
+    ADD COLUMN minilm_model TEXT;
 "#;
 
-pub async fn generate_embeddings(
-    progress_sender: tokio::sync::mpsc::Sender<Result<Event, Infallible>>,
-    cancel_token: CancellationToken,
-) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    info!("Starting embeddings generation process");
-    let openai = Client::new();
-    let supabase = crate::supabase::get_client();
-    let mut company_states: HashMap<String, RssProgressUpdate> = HashMap::new();
+pub const OPENAI_EMBEDDING_MODEL: &str = "text-embedding-3-small";
+pub const MINILM_EMBEDDING_MODEL: &str = "all-MiniLM-L6-v2";
 
-    if cancel_token.is_cancelled() {
-        info!("Embedding generation cancelled before starting");
-        return Ok(())
+/// Which of the two vectors stored per post in `post_embeddings`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmbeddingColumn {
+    OpenAI,
+    MiniLm,
+}
+
+impl EmbeddingColumn {
+    pub fn vector_column(&self) -> &'static str {
+        match self {
+            EmbeddingColumn::OpenAI => "embedding",
+            EmbeddingColumn::MiniLm => "minilm",
+        }
     }
 
-    // First get all existing embeddings with pagination
-    let mut links_with_embeddings: Vec<String> = Vec::new();
+    pub fn hash_column(&self) -> &'static str {
+        match self {
+            EmbeddingColumn::OpenAI => "embedding_hash",
+            EmbeddingColumn::MiniLm => "minilm_hash",
+        }
+    }
+
+    pub fn model_column(&self) -> &'static str {
+        match self {
+            EmbeddingColumn::OpenAI => "embedding_model",
+            EmbeddingColumn::MiniLm => "minilm_model",
+        }
+    }
+
+    pub fn current_model(&self) -> &'static str {
+        match self {
+            EmbeddingColumn::OpenAI => OPENAI_EMBEDDING_MODEL,
+            EmbeddingColumn::MiniLm => MINILM_EMBEDDING_MODEL,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StoredEmbedding {
+    pub content_hash: Option<String>,
+    pub model: Option<String>,
+}
+
+impl StoredEmbedding {
+    /// The text and model this vector was built from; `None` for untracked rows.
+    pub fn version(&self) -> Option<String> {
+        Some(embedding_version(self.model.as_deref()?, self.content_hash.as_deref()?))
+    }
+}
+
+/// Identifies a vector by the model and text that produced it, so copies held elsewhere (the
+/// ANN indexes) can tell when it has been replaced.
+pub fn embedding_version(model: &str, hash: &str) -> String {
+    format!("{}:{}", model, hash)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmbeddingState {
+    Missing,
+    Stale,
+    /// Written before hashes and models were tracked, so there's no telling whether the text
+    /// has been rewritten since. Re-embedded like a stale vector.
+    Untracked,
+    Current,
+}
+
+/// The text both pipelines embed for a post.
+pub fn embedding_text(post: &Poast) -> String {
+    format!(
+        "{}\n{}\n{}",
+        post.title,
+        post.summary.as_deref().unwrap_or(""),
+        post.description.as_deref().unwrap_or(""),
+    )
+}
+
+pub fn content_hash(text: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(text.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// A recorded hash or model that differs from the current one makes a vector stale; a missing
+/// one makes it untracked.
+pub fn embedding_state(
+    stored: Option<&StoredEmbedding>,
+    hash: &str,
+    column: EmbeddingColumn,
+) -> EmbeddingState {
+    match stored {
+        None => EmbeddingState::Missing,
+        Some(stored) => {
+            let hash_differs = stored.content_hash.as_deref().is_some_and(|h| h != hash);
+            let model_differs = stored.model.as_deref().is_some_and(|m| m != column.current_model());
+            if hash_differs || model_differs {
+                EmbeddingState::Stale
+            } else if stored.content_hash.is_none() || stored.model.is_none() {
+                EmbeddingState::Untracked
+            } else {
+                EmbeddingState::Current
+            }
+        }
+    }
+}
+
+/// Fetch hash/model metadata for every row that actually has a vector in `column`.
+/// Posts missing from the returned map have no vector at all.
+pub async fn fetch_stored_embeddings(
+    column: EmbeddingColumn,
+) -> Result<HashMap<String, StoredEmbedding>, Box<dyn std::error::Error + Send + Sync>> {
+    let supabase = crate::supabase::get_client();
+    let mut stored = HashMap::new();
     let page_size = 1000;
     let mut current_page = 0;
 
     loop {
-        if cancel_token.is_cancelled() {
-            info!("Embeddings retrieval cancelled during pagination");
-            return Ok(());
-        }
-
         let start = current_page * page_size;
         let end = start + page_size - 1;
-        
-        info!("Fetching embeddings page {}: range {}-{}", current_page + 1, start, end);
-        
-        let embeddings_response = supabase
+
+        info!("Fetching {} metadata page {}: range {}-{}", column.vector_column(), current_page + 1, start, end);
+
+        let response = supabase
             .from("post_embeddings")
-            .select("link,embedding")
+            .select(format!("link,{},{}", column.hash_column(), column.model_column()))
+            .not("is", column.vector_column(), "null")
             .range(start, end)
             .execute()
             .await?;
 
-        let embeddings_text = embeddings_response.text().await?;
-        let embeddings_value: serde_json::Value = serde_json::from_str(&embeddings_text)?;
-        
-        if let serde_json::Value::Array(arr) = embeddings_value {
-            if arr.is_empty() {
-                // No more records, exit the loop
-                break;
-            }
-            
-            let page_links: Vec<String> = arr.iter()
-                .filter_map(|v| {
-                    let link = v.get("link")?.as_str()?;
-                    let embedding = v.get("embedding");
-
-                    if embedding.is_some() && !embedding.unwrap().is_null() {
-                        Some(link.to_string())
-                    } else {
-                        None
+        let text = response.text().await?;
+        let value: serde_json::Value = serde_json::from_str(&text)?;
+
+        match value {
+            serde_json::Value::Array(arr) if !arr.is_empty() => {
+                for row in arr {
+                    if let Some(link) = row.get("link").and_then(|l| l.as_str()) {
+                        stored.insert(link.to_string(), StoredEmbedding {
+                            content_hash: row.get(column.hash_column()).and_then(|h| h.as_str()).map(String::from),
+                            model: row.get(column.model_column()).and_then(|m| m.as_str()).map(String::from),
+                        });
                     }
-                })
-                .collect();
-                
-            links_with_embeddings.extend(page_links);
-            current_page += 1;
-        } else {
-            // Invalid response format, exit the loop
-            break;
+                }
+                current_page += 1;
+            }
+            _ => break,
         }
     }
 
-    info!("Found {} posts with existing OpenAI embeddings", links_with_embeddings.len());
+    Ok(stored)
+}
 
-    // Now get all posts with pagination
+/// Page through every post. Only the columns needed to build the embedding text are selected.
+pub async fn fetch_all_posts(
+    cancel_token: &CancellationToken,
+) -> Result<Option<Vec<Poast>>, Box<dyn std::error::Error + Send + Sync>> {
+    let supabase = crate::supabase::get_client();
     let mut posts: Vec<Poast> = Vec::new();
-    current_page = 0;
+    let page_size = 1000;
+    let mut current_page = 0;
 
     loop {
         if cancel_token.is_cancelled() {
             info!("Posts retrieval cancelled during pagination");
-            return Ok(());
+            return Ok(None);
         }
 
         let start = current_page * page_size;
         let end = start + page_size - 1;
-        
+
         info!("Fetching posts page {}: range {}-{}", current_page + 1, start, end);
-        
+
         let posts_response = supabase
             .from("poasts")
-            .select("*")
+            .select("id, published_at, company, title, link, description, summary")
             .range(start, end)
             .execute()
             .await?;
 
         let posts_text = posts_response.text().await?;
-        
-        if current_page == 0 {
-            info!("First page Supabase posts response: {}", posts_text);
-        } else {
-            info!("Retrieved page {} of posts", current_page + 1);
-        }
-
         let posts_value: serde_json::Value = serde_json::from_str(&posts_text)?;
-        
+
         if let serde_json::Value::Array(arr) = posts_value {
             if arr.is_empty() {
-                // No more records, exit the loop
                 break;
             }
-            
+
             let page_posts: Vec<Poast> = arr.iter()
-                .filter_map(|v| {
-                    let result: Result<Poast, _> = serde_json::from_value(v.clone());
-                    if let Ok(post) = result {
-                        if !links_with_embeddings.contains(&post.link) {
-                            return Some(post);
-                        }
-                    } else if let Err(ref e) = result {
+                .filter_map(|v| match serde_json::from_value::<Poast>(v.clone()) {
+                    Ok(post) => Some(post),
+                    Err(e) => {
                         error!("Failed to parse post: {}", e);
+                        None
                     }
-                    None
                 })
                 .collect();
-                
+
             posts.extend(page_posts);
             current_page += 1;
         } else {
@@ -153,9 +241,57 @@ pub async fn generate_embeddings(
         }
     }
 
-    info!("Found {} posts needing embeddings across {} pages", posts.len(), current_page);
+    Ok(Some(posts))
+}
+
+/// Posts whose `column` vector is missing, stale or untracked, each paired with its current
+/// content hash.
+pub async fn posts_needing_embeddings(
+    column: EmbeddingColumn,
+    cancel_token: &CancellationToken,
+) -> Result<Option<Vec<(Poast, String, EmbeddingState)>>, Box<dyn std::error::Error + Send + Sync>> {
+    let stored = fetch_stored_embeddings(column).await?;
+    info!("Found {} posts with existing {} vectors", stored.len(), column.vector_column());
+
+    let Some(posts) = fetch_all_posts(cancel_token).await? else {
+        return Ok(None);
+    };
 
-    for (index, post) in posts.iter().enumerate() {
+    Ok(Some(posts
+        .into_iter()
+        .filter_map(|post| {
+            let hash = content_hash(&embedding_text(&post));
+            match embedding_state(stored.get(&post.link), &hash, column) {
+                EmbeddingState::Current => None,
+                state => Some((post, hash, state)),
+            }
+        })
+        .collect()))
+}
+
+pub async fn generate_embeddings(
+    progress_sender: tokio::sync::mpsc::Sender<Result<Event, Infallible>>,
+    cancel_token: CancellationToken,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    info!("Starting embeddings generation process");
+    let openai = Client::new();
+    let supabase = crate::supabase::get_client();
+    let mut company_states: HashMap<String, RssProgressUpdate> = HashMap::new();
+
+    if cancel_token.is_cancelled() {
+        info!("Embedding generation cancelled before starting");
+        return Ok(())
+    }
+
+    let Some(posts) = posts_needing_embeddings(EmbeddingColumn::OpenAI, &cancel_token).await? else {
+        return Ok(());
+    };
+
+    let stale_count = posts.iter().filter(|(_, _, state)| *state == EmbeddingState::Stale).count();
+    let untracked_count = posts.iter().filter(|(_, _, state)| *state == EmbeddingState::Untracked).count();
+    info!("Found {} posts needing embeddings ({} stale, {} untracked)", posts.len(), stale_count, untracked_count);
+
+    for (index, (post, hash, state)) in posts.iter().enumerate() {
         if cancel_token.is_cancelled() {
             info!("Embeddings generation cancelled after {} posts", index);
             return Ok(());
@@ -177,23 +313,21 @@ pub async fn generate_embeddings(
         );
 
         company_progress.current_post = Some(post.title.clone());
-        company_progress.status = "generating embedding".to_string();
+        company_progress.status = match state {
+            EmbeddingState::Stale | EmbeddingState::Untracked => "re-embedding stale".to_string(),
+            _ => "generating embedding".to_string(),
+        };
 
         progress_sender.send(company_progress.clone().into_event())
             .await
             .map_err(|e| format!("Failed to send progress update: {}", e))?;
 
-        let text = format!(
-            "{}\n{}\n{}",
-            post.title,
-            post.summary.as_deref().unwrap_or(""),
-            post.description.as_deref().unwrap_or(""),
-        );
+        let text = embedding_text(post);
 
         match openai
             .embeddings()
             .create(CreateEmbeddingRequestArgs::default()
-                .model("text-embedding-3-small")
+                .model(OPENAI_EMBEDDING_MODEL)
                 .input(EmbeddingInput::String(text))
                 .build()?)
             .await
@@ -221,7 +355,9 @@ pub async fn generate_embeddings(
                             supabase
                                 .from("post_embeddings")
                                 .update(json!({
-                                    "embedding": embedding_response.data[0].embedding
+                                    "embedding": embedding_response.data[0].embedding,
+                                    "embedding_hash": hash,
+                                    "embedding_model": OPENAI_EMBEDDING_MODEL
                                 }).to_string())
                                 .eq("link", &post.link)
                                 .execute()
@@ -233,6 +369,8 @@ pub async fn generate_embeddings(
                                 .insert(json!({
                                     "link": post.link,
                                     "embedding": embedding_response.data[0].embedding,
+                                    "embedding_hash": hash,
+                                    "embedding_model": OPENAI_EMBEDDING_MODEL,
                                     "minilm": null
                                 }).to_string())
                                 .execute()
@@ -245,6 +383,7 @@ pub async fn generate_embeddings(
                 match result {
                     Ok(_) => {
                         company_progress.new_posts += 1;
+                        upsert_embedding(EmbeddingColumn::OpenAI, &post.link, &embedding_response.data[0].embedding, hash).await;
                         info!("Successfully stored embedding for '{}'", post.title);
                     },
                     Err(e) => {
@@ -274,7 +413,9 @@ pub async fn generate_embeddings(
             .map_err(|e| format!("Failed to send final progress update: {}", e))?;
     }
 
-    invalidate_poasts_cache().await.map_err(|e| {
+    persist_ann_indexes().await;
+
+    invalidate_poasts_cache(None).await.map_err(|e| {
         Box::new(std::io::Error::other(e.to_string())) as Box<dyn Error + Send + Sync>
     })?;
 
@@ -330,7 +471,7 @@ pub async fn test_single_embedding() -> Result<(), Box<dyn std::error::Error>> {
         let embedding_response = openai
             .embeddings()
             .create(CreateEmbeddingRequestArgs::default()
-                .model("text-embedding-3-small")
+                .model(OPENAI_EMBEDDING_MODEL)
                 .input(EmbeddingInput::String(text))
                 .build()?)
             .await?;
@@ -361,4 +502,31 @@ pub async fn test_single_embedding() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stored(hash: Option<&str>, model: Option<&str>) -> StoredEmbedding {
+        StoredEmbedding {
+            content_hash: hash.map(String::from),
+            model: model.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_embedding_state() {
+        let column = EmbeddingColumn::OpenAI;
+        let model = Some(column.current_model());
+
+        assert_eq!(embedding_state(None, "h", column), EmbeddingState::Missing);
+        assert_eq!(embedding_state(Some(&stored(Some("h"), model)), "h", column), EmbeddingState::Current);
+        assert_eq!(embedding_state(Some(&stored(Some("old"), model)), "h", column), EmbeddingState::Stale);
+        assert_eq!(embedding_state(Some(&stored(Some("h"), Some("old-model"))), "h", column), EmbeddingState::Stale);
+        // legacy rows have no hash to compare, so they're re-embedded rather than trusted
+        assert_eq!(embedding_state(Some(&stored(None, None)), "h", column), EmbeddingState::Untracked);
+        assert_eq!(embedding_state(Some(&stored(Some("h"), None)), "h", column), EmbeddingState::Untracked);
+        assert_eq!(embedding_state(Some(&stored(None, Some("old-model"))), "h", column), EmbeddingState::Stale);
+    }
+}
+
 }
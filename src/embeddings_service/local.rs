@@ -12,8 +12,12 @@ pub mod embeddings_local {
     use std::error::Error;
     use tokenizers::Tokenizer;
 
-    use crate::components::poasts::Poast;
+    use crate::embedding_service::embeddings::{
+        embedding_text, posts_needing_embeddings, EmbeddingColumn, EmbeddingState,
+        MINILM_EMBEDDING_MODEL,
+    };
     use crate::server_fn::{invalidate_poasts_cache, RssProgressUpdate};
+    use crate::vector_search_service::vector_search::{persist_ann_indexes, upsert_embedding};
 
     static EMBEDDING_SERVICE: OnceLock<LocalEmbeddingService> = OnceLock::new();
 
@@ -199,115 +203,15 @@ pub mod embeddings_local {
             return Ok(())
         }
     
-        // Get all existing embeddings with pagination
-        let mut links_with_embeddings: Vec<String> = Vec::new();
-        let page_size = 1000;
-        let mut current_page = 0;
-    
-        loop {
-            if cancel_token.is_cancelled() {
-                info!("Embeddings retrieval cancelled during pagination");
-                return Ok(());
-            }
-    
-            let start = current_page * page_size;
-            let end = start + page_size - 1;
-            
-            info!("Fetching embeddings page {}: range {}-{}", current_page + 1, start, end);
-            
-            let embeddings_response = supabase
-                .from("post_embeddings")
-                .select("link,minilm")
-                .range(start, end)
-                .execute()
-                .await?;
-    
-            let embeddings_text = embeddings_response.text().await?;
-            let embeddings_value: serde_json::Value = serde_json::from_str(&embeddings_text)?;
-            
-            if let serde_json::Value::Array(arr) = embeddings_value {
-                if arr.is_empty() {
-                    // No more records, exit the loop
-                    break;
-                }
-                
-                let page_links: Vec<String> = arr.iter()
-                    .filter_map(|v| {
-                        let link = v.get("link")?.as_str()?;
-                        let minilm = v.get("minilm");
-    
-                        if minilm.is_some() && !minilm.unwrap().is_null() {
-                            Some(link.to_string())
-                        } else {
-                            None
-                        }
-                    })
-                    .collect();
-                    
-                links_with_embeddings.extend(page_links);
-                current_page += 1;
-            } else {
-                // Invalid response format, exit the loop
-                break;
-            }
-        }
-    
-        info!("Found {} posts with existing minilm embeddings", links_with_embeddings.len());
-    
-        // Get all posts with pagination
-        let mut posts: Vec<Poast> = Vec::new();
-        current_page = 0;
-    
-        loop {
-            if cancel_token.is_cancelled() {
-                info!("Posts retrieval cancelled during pagination");
-                return Ok(());
-            }
-    
-            let start = current_page * page_size;
-            let end = start + page_size - 1;
-            
-            info!("Fetching posts page {}: range {}-{}", current_page + 1, start, end);
-            
-            let posts_response = supabase
-                .from("poasts")
-                .select("*")
-                .range(start, end)
-                .execute()
-                .await?;
-    
-            let posts_text = posts_response.text().await?;
-            let posts_value: serde_json::Value = serde_json::from_str(&posts_text)?;
-            
-            if let serde_json::Value::Array(arr) = posts_value {
-                if arr.is_empty() {
-                    // No more records, exit the loop
-                    break;
-                }
-                
-                let page_posts: Vec<Poast> = arr.iter()
-                    .filter_map(|v| {
-                        let post: Result<Poast, _> = serde_json::from_value(v.clone());
-                        if let Ok(post) = post {
-                            if !links_with_embeddings.contains(&post.link) {
-                                return Some(post);
-                            }
-                        }
-                        None
-                    })
-                    .collect();
-                    
-                posts.extend(page_posts);
-                current_page += 1;
-            } else {
-                // Invalid response format, exit the loop
-                break;
-            }
-        }
-    
-        info!("Found {} posts needing minilm embeddings", posts.len());
+        let Some(posts) = posts_needing_embeddings(EmbeddingColumn::MiniLm, &cancel_token).await? else {
+            return Ok(());
+        };
+
+        let stale_count = posts.iter().filter(|(_, _, state)| *state == EmbeddingState::Stale).count();
+        let untracked_count = posts.iter().filter(|(_, _, state)| *state == EmbeddingState::Untracked).count();
+        info!("Found {} posts needing minilm embeddings ({} stale, {} untracked)", posts.len(), stale_count, untracked_count);
     
-        for (index, post) in posts.iter().enumerate() {
+        for (index, (post, hash, state)) in posts.iter().enumerate() {
             if cancel_token.is_cancelled() {
                 info!("Embeddings generation cancelled after {} posts", index);
                 return Ok(());
@@ -329,18 +233,16 @@ pub mod embeddings_local {
             );
     
             company_progress.current_post = Some(post.title.clone());
-            company_progress.status = "generating embedding".to_string();
+            company_progress.status = match state {
+                EmbeddingState::Stale | EmbeddingState::Untracked => "re-embedding stale".to_string(),
+                _ => "generating embedding".to_string(),
+            };
     
             progress_sender.send(company_progress.clone().into_event())
                 .await
                 .map_err(|e| format!("Failed to send progress update: {}", e))?;
     
-            let text = format!(
-                "{}\n{}\n{}",
-                post.title,
-                post.summary.as_deref().unwrap_or(""),
-                post.description.as_deref().unwrap_or(""),
-            );
+            let text = embedding_text(post);
     
             match service.generate_embedding(&text) {
                 Ok(embedding_response) => {
@@ -366,7 +268,9 @@ pub mod embeddings_local {
                                 supabase
                                     .from("post_embeddings")
                                     .update(json!({
-                                        "minilm": embedding_response
+                                        "minilm": embedding_response,
+                                        "minilm_hash": hash,
+                                        "minilm_model": MINILM_EMBEDDING_MODEL
                                     }).to_string())
                                     .eq("link", &post.link)
                                     .execute()
@@ -378,7 +282,9 @@ pub mod embeddings_local {
                                     .insert(json!({
                                         "link": post.link,
                                         "embedding": null,
-                                        "minilm": embedding_response
+                                        "minilm": embedding_response,
+                                        "minilm_hash": hash,
+                                        "minilm_model": MINILM_EMBEDDING_MODEL
                                     }).to_string())
                                     .execute()
                                     .await
@@ -390,6 +296,7 @@ pub mod embeddings_local {
                     match result {
                         Ok(_) => {
                             company_progress.new_posts += 1;
+                            upsert_embedding(EmbeddingColumn::MiniLm, &post.link, &embedding_response, hash).await;
                             info!("Successfully stored embedding for '{}'", post.title);
                         },
                         Err(e) => {
@@ -417,7 +324,9 @@ pub mod embeddings_local {
                 .map_err(|e| format!("Failed to send final progress update: {}", e))?;
         }
     
-        invalidate_poasts_cache().await.map_err(|e| {
+        persist_ann_indexes().await;
+
+        invalidate_poasts_cache(None).await.map_err(|e| {
             Box::new(std::io::Error::other(e.to_string())) as Box<dyn Error + Send + Sync>
         })?;
     
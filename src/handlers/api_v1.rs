@@ -0,0 +1,389 @@
+use axum::{
+    extract::{Path, Query},
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::time::Duration;
+
+use crate::components::poasts::{fetch_post, get_companies, search_poasts, Poast, PostFilter, PostPage, MAX_PAGE_SIZE};
+use crate::components::search::SearchType;
+
+/// Errors from `/api/v1`, always returned as `{"error": {"code", "message"}}`.
+#[derive(Debug)]
+pub enum ApiError {
+    BadRequest(String),
+    Unauthorized(String),
+    NotFound(String),
+    RateLimited(Duration),
+    Upstream(String),
+    Internal,
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, code, message) = match &self {
+            ApiError::BadRequest(message) => (StatusCode::BAD_REQUEST, "bad_request", message.clone()),
+            ApiError::Unauthorized(message) => (StatusCode::UNAUTHORIZED, "unauthorized", message.clone()),
+            ApiError::NotFound(message) => (StatusCode::NOT_FOUND, "not_found", message.clone()),
+            ApiError::RateLimited(wait) => (
+                StatusCode::TOO_MANY_REQUESTS,
+                "rate_limited",
+                format!("Too many requests, retry in {}s", wait.as_secs().max(1)),
+            ),
+            ApiError::Upstream(message) => (StatusCode::BAD_GATEWAY, "upstream_error", message.clone()),
+            ApiError::Internal => (StatusCode::INTERNAL_SERVER_ERROR, "internal_error", "Internal error".to_string()),
+        };
+
+        let mut response = (status, Json(json!({ "error": { "code": code, "message": message } }))).into_response();
+        if let ApiError::RateLimited(wait) = self {
+            if let Ok(value) = HeaderValue::from_str(&wait.as_secs().max(1).to_string()) {
+                response.headers_mut().insert(header::RETRY_AFTER, value);
+            }
+        }
+        response
+    }
+}
+
+impl From<leptos::prelude::ServerFnError> for ApiError {
+    fn from(error: leptos::prelude::ServerFnError) -> Self {
+        log::error!("API v1 query failed: {}", error);
+        ApiError::Upstream("Failed to load posts".to_string())
+    }
+}
+
+/// Query parameters shared by `/api/v1/posts` and `/api/v1/search`.
+#[derive(Debug, Default, Deserialize)]
+pub struct ApiQuery {
+    /// Keyword filter on `/posts`, the query itself on `/search`.
+    pub q: Option<String>,
+    pub company: Option<String>,
+    /// Comma separated company names.
+    pub companies: Option<String>,
+    pub tag: Option<String>,
+    pub published_after: Option<String>,
+    pub published_before: Option<String>,
+    pub cursor: Option<String>,
+    pub limit: Option<usize>,
+    /// `/search` only: semantic (default), local, hybrid or keyword.
+    pub mode: Option<String>,
+    /// `/search` only: drop results below this similarity percentage.
+    pub min_similarity: Option<i32>,
+}
+
+impl ApiQuery {
+    fn filter(&self) -> Result<PostFilter, ApiError> {
+        if self.limit.is_some_and(|limit| limit == 0 || limit > MAX_PAGE_SIZE) {
+            return Err(ApiError::BadRequest(format!("limit must be between 1 and {}", MAX_PAGE_SIZE)));
+        }
+        Ok(PostFilter {
+            search_term: self.q.clone(),
+            company: self.company.clone(),
+            companies: self
+                .companies
+                .as_deref()
+                .map(|companies| companies.split(',').map(|c| c.trim().to_string()).collect())
+                .unwrap_or_default(),
+            published_after: self.published_after.clone(),
+            published_before: self.published_before.clone(),
+            tag: self.tag.clone(),
+            min_similarity: self.min_similarity,
+            cursor: self.cursor.clone().filter(|c| !c.is_empty()),
+            limit: self.limit,
+            ..Default::default()
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApiPost {
+    pub id: i32,
+    pub title: String,
+    pub company: String,
+    pub link: String,
+    pub published_at: String,
+    pub summary: Option<String>,
+    pub logo_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub similarity: Option<i32>,
+}
+
+impl From<Poast> for ApiPost {
+    fn from(post: Poast) -> Self {
+        ApiPost {
+            id: post.id,
+            title: post.title,
+            company: post.company,
+            link: post.link,
+            published_at: post.published_at,
+            summary: post.summary,
+            logo_url: post.links.and_then(|links| links.logo_url),
+            similarity: post.similarity,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApiPostDetail {
+    #[serde(flatten)]
+    pub post: ApiPost,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApiPagination {
+    pub limit: usize,
+    /// Pass as `cursor` for the next page; absent on the last page.
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApiPage<T> {
+    pub data: Vec<T>,
+    pub pagination: ApiPagination,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApiData<T> {
+    pub data: T,
+}
+
+fn api_page(page: PostPage, limit: usize) -> ApiPage<ApiPost> {
+    ApiPage {
+        data: page.posts.into_iter().map(ApiPost::from).collect(),
+        pagination: ApiPagination {
+            limit,
+            next_cursor: page.next_cursor,
+        },
+    }
+}
+
+/// Newest first, with optional filters and a keyword match on title and summary.
+pub async fn api_list_posts(Query(params): Query<ApiQuery>) -> Result<Json<ApiPage<ApiPost>>, ApiError> {
+    let filter = params.filter()?;
+    filter.keyset_cursor().map_err(|e| ApiError::BadRequest(e.to_string()))?;
+
+    let limit = filter.page_size();
+    let page = search_poasts(filter, SearchType::Basic).await?;
+    Ok(Json(api_page(page, limit)))
+}
+
+pub async fn api_get_post(Path(id): Path<i32>) -> Result<Json<ApiData<ApiPostDetail>>, ApiError> {
+    let mut post = fetch_post(id)
+        .await?
+        .map(|detail| detail.post)
+        .ok_or_else(|| ApiError::NotFound(format!("No post with id {}", id)))?;
+    let description = post.description.take();
+
+    Ok(Json(ApiData {
+        data: ApiPostDetail {
+            post: post.into(),
+            description,
+        },
+    }))
+}
+
+pub async fn api_list_companies() -> Result<Json<ApiData<Vec<String>>>, ApiError> {
+    Ok(Json(ApiData {
+        data: get_companies().await?,
+    }))
+}
+
+/// Ranked search. Vector modes page by offset cursor, keyword mode by date like `/posts`.
+pub async fn api_search(Query(params): Query<ApiQuery>) -> Result<Json<ApiPage<ApiPost>>, ApiError> {
+    if params.q.as_deref().is_none_or(|q| q.trim().is_empty()) {
+        return Err(ApiError::BadRequest("q is required".to_string()));
+    }
+    let search_type = match params.mode.as_deref().unwrap_or("semantic") {
+        "semantic" => SearchType::OpenAISemantic,
+        "local" => SearchType::LocalSemantic,
+        "hybrid" => SearchType::Hybrid,
+        "keyword" => SearchType::Basic,
+        other => {
+            return Err(ApiError::BadRequest(format!(
+                "Unknown mode '{}', expected semantic, local, hybrid or keyword",
+                other
+            )))
+        }
+    };
+
+    let filter = params.filter()?;
+    match search_type {
+        SearchType::Basic => filter.keyset_cursor().map(|_| ()),
+        _ => filter.offset_cursor().map(|_| ()),
+    }
+    .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+
+    let limit = filter.page_size();
+    let page = search_poasts(filter, search_type).await?;
+    Ok(Json(api_page(page, limit)))
+}
+
+/// OpenAPI 3.1 description of `/api/v1`. Public, so clients can be generated without a key.
+pub async fn api_openapi() -> Json<serde_json::Value> {
+    let filter_params = json!([
+        { "name": "company", "in": "query", "schema": { "type": "string" } },
+        { "name": "companies", "in": "query", "description": "Comma separated company names", "schema": { "type": "string" } },
+        { "name": "tag", "in": "query", "description": "Only posts with this buzzword", "schema": { "type": "string" } },
+        { "name": "published_after", "in": "query", "description": "Inclusive, YYYY-MM-DD", "schema": { "type": "string", "format": "date" } },
+        { "name": "published_before", "in": "query", "description": "Exclusive, YYYY-MM-DD", "schema": { "type": "string", "format": "date" } },
+        { "name": "cursor", "in": "query", "description": "next_cursor from the previous page", "schema": { "type": "string" } },
+        { "name": "limit", "in": "query", "schema": { "type": "integer", "minimum": 1, "maximum": MAX_PAGE_SIZE, "default": 30 } },
+    ]);
+    let errors = json!({
+        "400": { "$ref": "#/components/responses/Error" },
+        "401": { "$ref": "#/components/responses/Error" },
+        "429": { "$ref": "#/components/responses/Error" },
+    });
+    let with = |extra: serde_json::Value| {
+        let mut params = filter_params.as_array().cloned().unwrap_or_default();
+        params.extend(extra.as_array().cloned().unwrap_or_default());
+        serde_json::Value::Array(params)
+    };
+    let page_response = |description: &str| {
+        let mut responses = errors.clone();
+        responses["200"] = json!({
+            "description": description,
+            "content": { "application/json": { "schema": { "$ref": "#/components/schemas/PostPage" } } }
+        });
+        responses
+    };
+
+    Json(json!({
+        "openapi": "3.1.0",
+        "info": {
+            "title": "bryptoblogs API",
+            "version": "1.0.0",
+            "description": "Engineering blog posts aggregated by bryptoblogs, with AI summaries. Create an API key from the API keys page and send it as `Authorization: Bearer <key>` or `X-API-Key: <key>`."
+        },
+        "servers": [{ "url": "/api/v1" }],
+        "security": [{ "bearerAuth": [] }, { "apiKeyHeader": [] }],
+        "paths": {
+            "/posts": {
+                "get": {
+                    "summary": "List posts, newest first",
+                    "operationId": "listPosts",
+                    "parameters": with(json!([
+                        { "name": "q", "in": "query", "description": "Keyword match on title and summary", "schema": { "type": "string" } }
+                    ])),
+                    "responses": page_response("A page of posts"),
+                }
+            },
+            "/posts/{id}": {
+                "get": {
+                    "summary": "Get one post",
+                    "operationId": "getPost",
+                    "parameters": [{ "name": "id", "in": "path", "required": true, "schema": { "type": "integer" } }],
+                    "responses": {
+                        "200": {
+                            "description": "The post",
+                            "content": { "application/json": { "schema": {
+                                "type": "object",
+                                "required": ["data"],
+                                "properties": { "data": { "$ref": "#/components/schemas/PostDetail" } }
+                            } } }
+                        },
+                        "401": { "$ref": "#/components/responses/Error" },
+                        "404": { "$ref": "#/components/responses/Error" },
+                        "429": { "$ref": "#/components/responses/Error" },
+                    }
+                }
+            },
+            "/companies": {
+                "get": {
+                    "summary": "List companies whose blogs are aggregated",
+                    "operationId": "listCompanies",
+                    "responses": {
+                        "200": {
+                            "description": "Company names, sorted",
+                            "content": { "application/json": { "schema": {
+                                "type": "object",
+                                "required": ["data"],
+                                "properties": { "data": { "type": "array", "items": { "type": "string" } } }
+                            } } }
+                        },
+                        "401": { "$ref": "#/components/responses/Error" },
+                        "429": { "$ref": "#/components/responses/Error" },
+                    }
+                }
+            },
+            "/search": {
+                "get": {
+                    "summary": "Search posts by meaning or keywords",
+                    "operationId": "searchPosts",
+                    "parameters": with(json!([
+                        { "name": "q", "in": "query", "required": true, "schema": { "type": "string" } },
+                        { "name": "mode", "in": "query", "schema": { "type": "string", "enum": ["semantic", "local", "hybrid", "keyword"], "default": "semantic" } },
+                        { "name": "min_similarity", "in": "query", "description": "Vector modes only, 0-100", "schema": { "type": "integer", "minimum": 0, "maximum": 100 } }
+                    ])),
+                    "responses": page_response("A page of posts, best match first"),
+                }
+            }
+        },
+        "components": {
+            "securitySchemes": {
+                "bearerAuth": { "type": "http", "scheme": "bearer" },
+                "apiKeyHeader": { "type": "apiKey", "in": "header", "name": "X-API-Key" }
+            },
+            "schemas": {
+                "Post": {
+                    "type": "object",
+                    "required": ["id", "title", "company", "link", "published_at"],
+                    "properties": {
+                        "id": { "type": "integer" },
+                        "title": { "type": "string" },
+                        "company": { "type": "string" },
+                        "link": { "type": "string", "format": "uri" },
+                        "published_at": { "type": "string" },
+                        "summary": { "type": ["string", "null"] },
+                        "logo_url": { "type": ["string", "null"] },
+                        "similarity": { "type": "integer", "description": "Search results from vector modes only, 0-100" }
+                    }
+                },
+                "PostDetail": {
+                    "allOf": [
+                        { "$ref": "#/components/schemas/Post" },
+                        { "type": "object", "properties": { "description": { "type": ["string", "null"] } } }
+                    ]
+                },
+                "PostPage": {
+                    "type": "object",
+                    "required": ["data", "pagination"],
+                    "properties": {
+                        "data": { "type": "array", "items": { "$ref": "#/components/schemas/Post" } },
+                        "pagination": {
+                            "type": "object",
+                            "required": ["limit"],
+                            "properties": {
+                                "limit": { "type": "integer" },
+                                "next_cursor": { "type": ["string", "null"] }
+                            }
+                        }
+                    }
+                },
+                "Error": {
+                    "type": "object",
+                    "required": ["error"],
+                    "properties": {
+                        "error": {
+                            "type": "object",
+                            "required": ["code", "message"],
+                            "properties": {
+                                "code": { "type": "string" },
+                                "message": { "type": "string" }
+                            }
+                        }
+                    }
+                }
+            },
+            "responses": {
+                "Error": {
+                    "description": "An error",
+                    "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Error" } } }
+                }
+            }
+        }
+    }))
+}
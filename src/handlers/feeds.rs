@@ -0,0 +1,333 @@
+use axum::{
+    extract::Query,
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+use crate::components::poasts::{fetch_post_page, parse_published, Poast, PostFilter, PostScope};
+
+/// Entries in each published feed.
+const FEED_ENTRIES: usize = 50;
+const FEED_CACHE_CONTROL: &str = "public, max-age=900, stale-while-revalidate=3600";
+const FEED_TITLE: &str = "bryptoblogs";
+
+#[derive(Debug, Default, Deserialize)]
+pub struct FeedParams {
+    pub company: Option<String>,
+    pub tag: Option<String>,
+}
+
+enum FeedFormat {
+    Atom,
+    Rss,
+    Json,
+}
+
+impl FeedFormat {
+    fn path(&self) -> &'static str {
+        match self {
+            FeedFormat::Atom => "/feed.xml",
+            FeedFormat::Rss => "/rss.xml",
+            FeedFormat::Json => "/feed.json",
+        }
+    }
+
+    fn content_type(&self) -> &'static str {
+        match self {
+            FeedFormat::Atom => "application/atom+xml; charset=utf-8",
+            FeedFormat::Rss => "application/rss+xml; charset=utf-8",
+            FeedFormat::Json => "application/feed+json; charset=utf-8",
+        }
+    }
+}
+
+pub async fn atom_feed_handler(headers: HeaderMap, Query(params): Query<FeedParams>) -> Response {
+    serve_feed(FeedFormat::Atom, &headers, params).await
+}
+
+pub async fn rss_feed_handler(headers: HeaderMap, Query(params): Query<FeedParams>) -> Response {
+    serve_feed(FeedFormat::Rss, &headers, params).await
+}
+
+pub async fn json_feed_handler(headers: HeaderMap, Query(params): Query<FeedParams>) -> Response {
+    serve_feed(FeedFormat::Json, &headers, params).await
+}
+
+async fn serve_feed(format: FeedFormat, headers: &HeaderMap, params: FeedParams) -> Response {
+    let filter = PostFilter {
+        company: params.company.clone(),
+        tag: params.tag.clone(),
+        limit: Some(FEED_ENTRIES),
+        ..Default::default()
+    };
+    let posts = match fetch_post_page(&filter, &PostScope::default()).await {
+        Ok(page) => page.posts,
+        Err(e) => {
+            log::error!("Failed to load posts for {}: {}", format.path(), e);
+            return (StatusCode::SERVICE_UNAVAILABLE, "feed temporarily unavailable").into_response();
+        }
+    };
+
+    // links built from the Host header make the cached body depend on it
+    let (base, vary) = match configured_site_url() {
+        Some(url) => (url, "Accept-Encoding"),
+        None => (request_site_url(headers), "Accept-Encoding, Host"),
+    };
+    let self_url = format!("{}{}{}", base, format.path(), query_string(&params));
+    let title = feed_title(&params);
+    let body = match format {
+        FeedFormat::Atom => atom(&posts, &title, &base, &self_url),
+        FeedFormat::Rss => rss(&posts, &title, &base, &self_url),
+        FeedFormat::Json => json_feed(&posts, &title, &base, &self_url),
+    };
+
+    let digest = Sha256::digest(body.as_bytes());
+    let etag = format!(
+        "\"{}\"",
+        digest[..16].iter().map(|b| format!("{:02x}", b)).collect::<String>()
+    );
+    let not_modified = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.split(',').any(|tag| tag.trim().trim_start_matches("W/") == etag));
+
+    let mut response = if not_modified {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        let mut response = body.into_response();
+        response
+            .headers_mut()
+            .insert(header::CONTENT_TYPE, HeaderValue::from_static(format.content_type()));
+        response
+    };
+
+    let response_headers = response.headers_mut();
+    response_headers.insert(header::CACHE_CONTROL, HeaderValue::from_static(FEED_CACHE_CONTROL));
+    response_headers.insert(header::VARY, HeaderValue::from_static(vary));
+    if let Ok(value) = HeaderValue::from_str(&etag) {
+        response_headers.insert(header::ETAG, value);
+    }
+    if let Some(newest) = posts.first().and_then(|post| parse_published(&post.published_at)) {
+        if let Ok(value) = HeaderValue::from_str(&newest.format("%a, %d %b %Y %H:%M:%S GMT").to_string()) {
+            response_headers.insert(header::LAST_MODIFIED, value);
+        }
+    }
+    response
+}
+
+fn configured_site_url() -> Option<String> {
+    std::env::var("SITE_URL")
+        .ok()
+        .map(|url| url.trim().trim_end_matches('/').to_string())
+        .filter(|url| !url.is_empty())
+}
+
+/// The host the request came in on. Forwarded headers are ignored: a client could set them
+/// to put its own links into a publicly cached feed.
+fn request_site_url(headers: &HeaderMap) -> String {
+    let host = headers
+        .get(header::HOST)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("localhost:3000");
+    let scheme = if host.starts_with("localhost") || host.starts_with("127.0.0.1") { "http" } else { "https" };
+    format!("{}://{}", scheme, host)
+}
+
+fn query_string(params: &FeedParams) -> String {
+    let pairs: Vec<String> = [("company", &params.company), ("tag", &params.tag)]
+        .into_iter()
+        .filter_map(|(key, value)| {
+            value
+                .as_deref()
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(|v| format!("{}={}", key, urlencoding::encode(v)))
+        })
+        .collect();
+    if pairs.is_empty() {
+        String::new()
+    } else {
+        format!("?{}", pairs.join("&"))
+    }
+}
+
+fn feed_title(params: &FeedParams) -> String {
+    let mut parts = Vec::new();
+    if let Some(company) = params.company.as_deref().map(str::trim).filter(|c| !c.is_empty()) {
+        parts.push(company.to_string());
+    }
+    if let Some(tag) = params.tag.as_deref().map(str::trim).filter(|t| !t.is_empty()) {
+        parts.push(format!("#{}", tag));
+    }
+    if parts.is_empty() {
+        FEED_TITLE.to_string()
+    } else {
+        format!("{} · {}", FEED_TITLE, parts.join(" "))
+    }
+}
+
+/// Supabase timestamps come back with or without an offset, and older rows as bare dates.
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // characters XML 1.0 doesn't allow at all
+            c if (c as u32) < 0x20 && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn content(post: &Poast) -> &str {
+    post.summary
+        .as_deref()
+        .filter(|s| !s.trim().is_empty())
+        .or(post.description.as_deref())
+        .unwrap_or("")
+}
+
+fn atom(posts: &[Poast], title: &str, base: &str, self_url: &str) -> String {
+    let updated = posts
+        .first()
+        .and_then(|post| parse_published(&post.published_at))
+        .unwrap_or_else(Utc::now);
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    xml.push_str(&format!("  <title>{}</title>\n", escape_xml(title)));
+    xml.push_str("  <subtitle>Engineering blog posts with AI summaries</subtitle>\n");
+    xml.push_str(&format!("  <id>{}</id>\n", escape_xml(self_url)));
+    xml.push_str(&format!("  <link rel=\"self\" type=\"application/atom+xml\" href=\"{}\"/>\n", escape_xml(self_url)));
+    xml.push_str(&format!("  <link rel=\"alternate\" type=\"text/html\" href=\"{}/\"/>\n", escape_xml(base)));
+    xml.push_str(&format!("  <updated>{}</updated>\n", updated.to_rfc3339()));
+
+    for post in posts {
+        let published = parse_published(&post.published_at).unwrap_or(updated);
+        xml.push_str("  <entry>\n");
+        xml.push_str(&format!("    <title>{}</title>\n", escape_xml(&post.title)));
+        xml.push_str(&format!("    <id>{}</id>\n", escape_xml(&post.link)));
+        xml.push_str(&format!("    <link rel=\"alternate\" href=\"{}\"/>\n", escape_xml(&post.link)));
+        xml.push_str(&format!("    <published>{}</published>\n", published.to_rfc3339()));
+        xml.push_str(&format!("    <updated>{}</updated>\n", published.to_rfc3339()));
+        xml.push_str(&format!("    <author><name>{}</name></author>\n", escape_xml(&post.company)));
+        xml.push_str(&format!("    <category term=\"{}\"/>\n", escape_xml(&post.company)));
+        xml.push_str(&format!("    <content type=\"text\">{}</content>\n", escape_xml(content(post))));
+        xml.push_str("  </entry>\n");
+    }
+
+    xml.push_str("</feed>\n");
+    xml
+}
+
+fn rss(posts: &[Poast], title: &str, base: &str, self_url: &str) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n\
+        <rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\">\n<channel>\n",
+    );
+    xml.push_str(&format!("  <title>{}</title>\n", escape_xml(title)));
+    xml.push_str(&format!("  <link>{}/</link>\n", escape_xml(base)));
+    xml.push_str("  <description>Engineering blog posts with AI summaries</description>\n");
+    xml.push_str(&format!("  <atom:link href=\"{}\" rel=\"self\" type=\"application/rss+xml\"/>\n", escape_xml(self_url)));
+    if let Some(newest) = posts.first().and_then(|post| parse_published(&post.published_at)) {
+        xml.push_str(&format!("  <lastBuildDate>{}</lastBuildDate>\n", newest.to_rfc2822()));
+    }
+
+    for post in posts {
+        xml.push_str("  <item>\n");
+        xml.push_str(&format!("    <title>{}</title>\n", escape_xml(&post.title)));
+        xml.push_str(&format!("    <link>{}</link>\n", escape_xml(&post.link)));
+        xml.push_str(&format!("    <guid isPermaLink=\"true\">{}</guid>\n", escape_xml(&post.link)));
+        if let Some(published) = parse_published(&post.published_at) {
+            xml.push_str(&format!("    <pubDate>{}</pubDate>\n", published.to_rfc2822()));
+        }
+        xml.push_str(&format!("    <category>{}</category>\n", escape_xml(&post.company)));
+        xml.push_str(&format!("    <description>{}</description>\n", escape_xml(content(post))));
+        xml.push_str("  </item>\n");
+    }
+
+    xml.push_str("</channel>\n</rss>\n");
+    xml
+}
+
+/// JSON Feed 1.1, https://www.jsonfeed.org/version/1.1/
+fn json_feed(posts: &[Poast], title: &str, base: &str, self_url: &str) -> String {
+    let items: Vec<serde_json::Value> = posts
+        .iter()
+        .map(|post| {
+            json!({
+                "id": post.link,
+                "url": post.link,
+                "title": post.title,
+                "content_text": content(post),
+                "date_published": parse_published(&post.published_at).map(|dt| dt.to_rfc3339()),
+                "authors": [{ "name": post.company }],
+                "tags": [post.company],
+            })
+        })
+        .collect();
+
+    json!({
+        "version": "https://jsonfeed.org/version/1.1",
+        "title": title,
+        "home_page_url": format!("{}/", base),
+        "feed_url": self_url,
+        "description": "Engineering blog posts with AI summaries",
+        "items": items,
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn post(title: &str, summary: &str) -> Poast {
+        Poast {
+            id: 1,
+            published_at: "2024-03-01".to_string(),
+            company: "AT&T".to_string(),
+            title: title.to_string(),
+            link: "https://example.com/post?a=1&b=2".to_string(),
+            description: None,
+            summary: Some(summary.to_string()),
+            full_text: None,
+            links: None,
+            similarity: None,
+        }
+    }
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(escape_xml("plain text"), "plain text");
+        assert_eq!(escape_xml("a & b < c > d"), "a &amp; b &lt; c &gt; d");
+        assert_eq!(escape_xml("\"quoted\" 'single'"), "&quot;quoted&quot; &apos;single&apos;");
+        assert_eq!(escape_xml("&amp;"), "&amp;amp;");
+        assert_eq!(escape_xml("tab\tline\ncr\r"), "tab\tline\ncr\r");
+        assert_eq!(escape_xml("bell\u{7}null\u{0}esc\u{1b}"), "bellnullesc");
+        assert_eq!(escape_xml("héllo ✓"), "héllo ✓");
+    }
+
+    #[test]
+    fn test_feeds_escape_post_fields() {
+        let posts = [post("<script>alert(\"x\")</script>", "Fast & \u{1}safe")];
+        for xml in [
+            atom(&posts, "Feed", "https://bb.example", "https://bb.example/feed.atom"),
+            rss(&posts, "Feed", "https://bb.example", "https://bb.example/feed.rss"),
+        ] {
+            assert!(!xml.contains("<script>"));
+            assert!(xml.contains("&lt;script&gt;alert(&quot;x&quot;)&lt;/script&gt;"));
+            assert!(xml.contains("https://example.com/post?a=1&amp;b=2"));
+            assert!(xml.contains("AT&amp;T"));
+            assert!(xml.contains("Fast &amp; safe"));
+        }
+    }
+}
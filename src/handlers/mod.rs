@@ -10,3 +10,11 @@ pub use drawing_ws::*;
 mod canvas_ws;
 #[cfg(feature = "ssr")]
 pub use canvas_ws::*;
+#[cfg(feature = "ssr")]
+mod feeds;
+#[cfg(feature = "ssr")]
+pub use feeds::*;
+#[cfg(feature = "ssr")]
+mod api_v1;
+#[cfg(feature = "ssr")]
+pub use api_v1::*;
@@ -1,10 +1,14 @@
 use axum::{
     response::sse::{Event, Sse},
-    extract::{Query, State},
+    extract::{ConnectInfo, Query, State},
+    http::HeaderMap,
+    Extension,
     Json
 };
+use once_cell::sync::Lazy;
 use std::collections::HashMap;
 use std::convert::Infallible;
+use std::net::SocketAddr;
 use tokio::sync::mpsc as tokio_mpsc;
 use futures::stream::Stream;
 use std::pin::Pin;
@@ -14,11 +18,17 @@ use crate::{
     cancellable_sse::{create_cancellable_sse_stream, CancellableSseStream},
     state::AppState,
     types::StreamResponse,
-    rag_service::rag::rag::RagService,
+    rag_service::rag::rag::{RagMessage, RagResponse, RagService},
+    components::poasts::fetch_post,
     components::search::SearchType,
-
+    auth::Claims,
+    models::{ChatSession, RagUsage},
+    rate_limit::ASK_DAILY_QUOTA,
 };
 
+/// Stored messages replayed to the model on each turn.
+const CHAT_HISTORY_LIMIT: i64 = 10;
+
 pub struct SseStream {
     pub receiver: tokio_mpsc::Receiver<Result<Event, Infallible>>,
 }
@@ -82,11 +92,13 @@ pub async fn rss_progress_handler(
         .cloned()
         .expect("stream_id is required");
 
+    let pool = state.pool.clone();
+
     create_cancellable_sse_stream(
         state.sse_state,
         stream_id,
-        |tx, token| async move {
-            crate::rss_service::server::process_feeds_with_progress(tx, token).await
+        move |tx, token| async move {
+            crate::rss_service::server::process_feeds_with_progress(tx, token, pool).await
         },
     ).await
 }
@@ -142,9 +154,11 @@ pub async fn refresh_summaries_handler(
 }
 
 
-// And add this handler function to src/handlers/sse.rs:
+/// Answer a chat question within a persisted session. Without a valid `session_id` a new
+/// session is started; its id is sent first as a "session" message.
 pub async fn rag_query_handler(
     State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
     Query(params): Query<HashMap<String, String>>,
 ) -> Sse<CancellableSseStream> {
     let stream_id = params
@@ -162,16 +176,211 @@ pub async fn rag_query_handler(
         .map(|s| match s.as_str() {
             "openai" => SearchType::OpenAISemantic,
             "local" => SearchType::LocalSemantic,
+            "hybrid" => SearchType::Hybrid,
             _ => SearchType::OpenAISemantic,
         })
         .unwrap_or(SearchType::OpenAISemantic);
 
+    let session_id = params
+        .get("session_id")
+        .and_then(|id| uuid::Uuid::parse_str(id).ok());
+
+    let pool = state.pool.clone();
+
     create_cancellable_sse_stream(
         state.sse_state,
         stream_id,
         move |tx, _token| async move {
+            let user_id = claims.user_id()?;
+
+            let mut conn = pool.get().await.map_err(|e| e.to_string())?;
+
+            let existing = match session_id {
+                Some(id) => ChatSession::find_owned(id, user_id, &mut conn).await?,
+                None => None,
+            };
+            let session = match existing {
+                Some(session) => session,
+                None => ChatSession::create(user_id, ChatSession::title_from_query(&query), &mut conn).await?,
+            };
+
+            let session_message = RagResponse {
+                message_type: "session".to_string(),
+                content: Some(session.id.to_string()),
+                citations: None,
+                verification: None,
+                context_usage: None,
+            };
+            tx.send(Ok(Event::default().data(serde_json::to_string(&session_message)?)))
+                .await
+                .map_err(|e| e.to_string())?;
+
+            let history: Vec<RagMessage> = session
+                .recent_messages(CHAT_HISTORY_LIMIT, &mut conn)
+                .await?
+                .into_iter()
+                .map(RagMessage::from)
+                .collect();
+
+            session.add_message("user", query.clone(), None, &mut conn).await?;
+            // don't hold a pooled connection while the answer streams
+            drop(conn);
+
             let rag_service = RagService::new();
-            rag_service.process_query(query, search_type, tx).await
+            let answer = rag_service.process_query(query, search_type, history, tx).await?;
+
+            if let Some(answer) = answer {
+                let citations = answer
+                    .citations
+                    .map(serde_json::to_value)
+                    .transpose()?;
+                let mut conn = pool.get().await.map_err(|e| e.to_string())?;
+                session.add_message("assistant", answer.content, citations, &mut conn).await?;
+            }
+
+            Ok(())
         },
     ).await
 }
+
+/// Set (`BEHIND_FLY_PROXY=true`) when the app runs behind Fly's proxy, which overwrites
+/// `fly-client-ip` on every request. Anywhere else the header is whatever the client sent.
+static BEHIND_FLY_PROXY: Lazy<bool> = Lazy::new(|| {
+    std::env::var("BEHIND_FLY_PROXY").is_ok_and(|value| matches!(value.trim(), "1" | "true"))
+});
+
+/// Client address for rate limiting: Fly's proxy header when behind Fly, otherwise the socket.
+/// X-Forwarded-For is ignored since its first entry is whatever the client sent.
+fn client_ip(headers: &HeaderMap, addr: SocketAddr) -> String {
+    client_ip_from(headers, addr, *BEHIND_FLY_PROXY)
+}
+
+fn client_ip_from(headers: &HeaderMap, addr: SocketAddr, behind_fly: bool) -> String {
+    headers
+        .get("fly-client-ip")
+        .filter(|_| behind_fly)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<std::net::IpAddr>().ok())
+        .unwrap_or_else(|| addr.ip())
+        .to_string()
+}
+
+async fn send_rag_error(
+    tx: &tokio_mpsc::Sender<Result<Event, Infallible>>,
+    message: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let response = RagResponse {
+        message_type: "error".to_string(),
+        content: Some(message.to_string()),
+        citations: None,
+        verification: None,
+        context_usage: None,
+    };
+    tx.send(Ok(Event::default().data(serde_json::to_string(&response)?)))
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Questions from the public ask page. Unlike `rag_query_handler` nothing is saved, there is
+/// no history, and each user is held to per-minute, per-IP, concurrency and daily limits.
+/// With a `post_id` the question is answered from that post alone.
+pub async fn ask_query_handler(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Query(params): Query<HashMap<String, String>>,
+) -> Sse<CancellableSseStream> {
+    let stream_id = params
+        .get("stream_id")
+        .cloned()
+        .expect("stream_id is required");
+
+    let query = params
+        .get("query")
+        .map(|q| q.trim().to_string())
+        .unwrap_or_default();
+
+    let post_id = params.get("post_id").and_then(|id| id.parse::<i32>().ok());
+
+    let ip = client_ip(&headers, addr);
+    let admitted = claims
+        .user_id()
+        .map_err(|e| e.to_string())
+        .and_then(|user_id| {
+            state
+                .ask_limits
+                .admit(user_id, &ip, &query)
+                .map(|permit| (user_id, permit))
+                .map_err(|e| e.to_string())
+        });
+
+    let pool = state.pool.clone();
+
+    create_cancellable_sse_stream(
+        state.sse_state,
+        stream_id,
+        move |tx, _token| async move {
+            // held until the answer is done so the user can't run several at once
+            let (user_id, _permit) = match admitted {
+                Ok(admitted) => admitted,
+                Err(reason) => {
+                    log::info!("Rejected ask from {}: {}", ip, reason);
+                    return send_rag_error(&tx, &reason).await;
+                }
+            };
+
+            let post = match post_id {
+                Some(id) => match fetch_post(id).await {
+                    Ok(Some(detail)) => Some(detail.post),
+                    Ok(None) => return send_rag_error(&tx, "That post doesn't exist").await,
+                    Err(e) => return send_rag_error(&tx, &format!("Couldn't load the post: {}", e)).await,
+                },
+                None => None,
+            };
+
+            let today = chrono::Utc::now().date_naive();
+            let mut conn = pool.get().await.map_err(|e| e.to_string())?;
+            if RagUsage::try_consume(user_id, today, ASK_DAILY_QUOTA, &mut conn).await?.is_none() {
+                let message = format!("You've used all {} questions for today", ASK_DAILY_QUOTA);
+                return send_rag_error(&tx, &message).await;
+            }
+            drop(conn);
+
+            let service = RagService::new();
+            let answer = match post {
+                Some(post) => service.process_post_query(post, query, tx).await,
+                None => service.process_query(query, SearchType::Hybrid, Vec::new(), tx).await,
+            };
+
+            // questions that got no answer don't count against the quota
+            if !matches!(answer, Ok(Some(_))) {
+                let mut conn = pool.get().await.map_err(|e| e.to_string())?;
+                RagUsage::release(user_id, today, &mut conn).await?;
+            }
+
+            answer.map(|_| ())
+        },
+    ).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_ip_trusts_fly_header_only_behind_fly() {
+        let addr: SocketAddr = "10.0.0.5:4000".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert("fly-client-ip", "203.0.113.7".parse().unwrap());
+        headers.insert("x-forwarded-for", "198.51.100.1".parse().unwrap());
+
+        assert_eq!(client_ip_from(&headers, addr, true), "203.0.113.7");
+        assert_eq!(client_ip_from(&headers, addr, false), "10.0.0.5");
+        assert_eq!(client_ip_from(&HeaderMap::new(), addr, true), "10.0.0.5");
+
+        headers.insert("fly-client-ip", "not an ip".parse().unwrap());
+        assert_eq!(client_ip_from(&headers, addr, true), "10.0.0.5");
+    }
+}
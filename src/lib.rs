@@ -7,12 +7,17 @@ pub mod cancellable_sse;
 pub mod components;
 #[cfg(feature = "ssr")]
 pub mod database;
+pub mod digest_service;
 pub mod embedding_service;
 pub mod embeddings_service;
 pub mod error_template;
 pub mod handlers;
 pub mod models;
+pub mod opml_service;
 pub mod rag_service;
+#[cfg(feature = "ssr")]
+pub mod rate_limit;
+pub mod recommendation_service;
 pub mod rss_service;
 #[cfg(feature = "ssr")]
 pub mod schema;
@@ -20,7 +25,10 @@ pub mod server_fn;
 pub mod state;
 pub mod summary_refresh_service;
 pub mod supabase;
+pub mod trends_service;
 pub mod types;
+pub mod vector_search_service;
+pub mod webhook_service;
 
 #[cfg(feature = "hydrate")]
 #[wasm_bindgen::prelude::wasm_bindgen]
@@ -19,7 +19,7 @@ cfg_if! {
         use leptos_axum::{generate_route_list, handle_server_fns_with_context, LeptosRoutes};
         use std::net::SocketAddr;
         use bb::app::*;
-        use bb::auth::server::middleware::require_auth_no_db;
+        use bb::auth::server::middleware::{require_api_key, require_auth_no_db};
         use bb::auth::oauth::{google_login, discord_login, google_callback, discord_callback};
         use bb::database::db::establish_connection;
         use bb::state::AppState;
@@ -48,6 +48,12 @@ cfg_if! {
 
             let app_state = AppState::new(leptos_options.clone(), pool);
 
+            tokio::spawn(async {
+                if let Err(e) = bb::vector_search_service::vector_search::init_ann_indexes().await {
+                    log::error!("Failed to load ANN indexes: {}", e);
+                }
+            });
+
             async fn server_fn_handler(
                 State(app_state): State<AppState>,
                 request: Request<AxumBody>,
@@ -68,12 +74,32 @@ cfg_if! {
                 .route("/auth/discord", get(discord_login))
                 .route("/auth/discord/callback", get(discord_callback));
 
+            // outbound feeds of the aggregated posts (public)
+            let feed_routes = Router::new()
+                .route("/feed.xml", get(atom_feed_handler))
+                .route("/rss.xml", get(rss_feed_handler))
+                .route("/feed.json", get(json_feed_handler));
+
+            // versioned REST API for scripts, authenticated by API key
+            let api_v1_routes = Router::new()
+                .route("/api/v1/posts", get(api_list_posts))
+                .route("/api/v1/posts/{id}", get(api_get_post))
+                .route("/api/v1/companies", get(api_list_companies))
+                .route("/api/v1/search", get(api_search))
+                .layer(middleware::from_fn_with_state(
+                    app_state.clone(),
+                    require_api_key
+                ))
+                // added after the layer so it stays public
+                .route("/api/v1/openapi.json", get(api_openapi));
+
             let protected_routes = Router::new()
                 .route("/ws/canvas/{:room_id}/{:user_id}", get(canvas_ws_handler))
                 .route("/api/create-stream", get(create_stream))
                 .route("/api/cancel-stream", get(cancel_stream))
                 .route("/api/rss-progress", get(rss_progress_handler))
                 .route("/api/rag-query", get(rag_query_handler))
+                .route("/api/ask-query", get(ask_query_handler))
                 .route("/api/backfill-progress", get(backfill_progress_handler))
                 .route("/api/refresh-summaries", get(refresh_summaries_handler))
                 .route("/api/generate-embeddings", get(embeddings_generation_handler))
@@ -89,6 +115,8 @@ cfg_if! {
                     get(server_fn_handler).post(server_fn_handler),
                 )
                 .merge(oauth_routes)
+                .merge(feed_routes)
+                .merge(api_v1_routes)
                 .merge(protected_routes)
                 .leptos_routes_with_handler(routes, get(|State(app_state): State<AppState>, request: Request<AxumBody>| async move {
                     let handler = leptos_axum::render_app_to_stream_with_context(
@@ -0,0 +1,159 @@
+use cfg_if::cfg_if;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// Active API keys a single user may hold.
+pub const MAX_API_KEYS: i64 = 10;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ApiKeyView {
+    pub id: i32,
+    pub name: String,
+    pub prefix: String,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub revoked: bool,
+    pub created_at: Option<NaiveDateTime>,
+}
+
+/// Returned once when a key is created; the full key is never shown again.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CreatedApiKeyView {
+    pub key: String,
+    pub api_key: ApiKeyView,
+}
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use crate::schema::*;
+        use crate::models::User;
+        use diesel::prelude::*;
+        use diesel_async::{AsyncPgConnection, RunQueryDsl};
+        use rand::Rng;
+        use sha2::{Digest, Sha256};
+
+        /// Prefix of every key, so leaked keys are easy to search for.
+        const KEY_PREFIX: &str = "bb_";
+        const PREFIX_SHOWN: usize = 10;
+        /// `last_used_at` is only rewritten when older than this, to avoid a write per request.
+        const LAST_USED_RESOLUTION_SECS: i64 = 60;
+
+        #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Identifiable, Selectable, Associations)]
+        #[diesel(belongs_to(User, foreign_key = user_id))]
+        #[diesel(table_name = api_keys)]
+        pub struct ApiKey {
+            pub id: i32,
+            pub user_id: i32,
+            pub name: String,
+            pub prefix: String,
+            pub key_hash: String,
+            pub last_used_at: Option<NaiveDateTime>,
+            pub revoked_at: Option<NaiveDateTime>,
+            pub created_at: Option<NaiveDateTime>,
+        }
+
+        #[derive(Debug, Insertable)]
+        #[diesel(table_name = api_keys)]
+        pub struct NewApiKey {
+            pub user_id: i32,
+            pub name: String,
+            pub prefix: String,
+            pub key_hash: String,
+        }
+
+        impl ApiKey {
+            pub fn hash_key(key: &str) -> String {
+                format!("{:x}", Sha256::digest(key.as_bytes()))
+            }
+
+            /// Create a key for `user_id`. Returns the stored row and the plaintext key.
+            pub async fn create(user_id: i32, name: String, conn: &mut AsyncPgConnection) -> QueryResult<(ApiKey, String)> {
+                let bytes: [u8; 24] = rand::thread_rng().gen();
+                let key = format!(
+                    "{}{}",
+                    KEY_PREFIX,
+                    bytes.iter().map(|b| format!("{:02x}", b)).collect::<String>()
+                );
+
+                let api_key = diesel::insert_into(api_keys::table)
+                    .values(&NewApiKey {
+                        user_id,
+                        name,
+                        prefix: key.chars().take(PREFIX_SHOWN).collect(),
+                        key_hash: Self::hash_key(&key),
+                    })
+                    .returning(api_keys::all_columns)
+                    .get_result(conn)
+                    .await?;
+
+                Ok((api_key, key))
+            }
+
+            pub async fn count_active_for_user(user_id: i32, conn: &mut AsyncPgConnection) -> QueryResult<i64> {
+                api_keys::table
+                    .filter(api_keys::user_id.eq(user_id))
+                    .filter(api_keys::revoked_at.is_null())
+                    .count()
+                    .get_result(conn)
+                    .await
+            }
+
+            /// Newest first, revoked keys included.
+            pub async fn list_for_user(user_id: i32, conn: &mut AsyncPgConnection) -> QueryResult<Vec<ApiKey>> {
+                api_keys::table
+                    .filter(api_keys::user_id.eq(user_id))
+                    .order_by(api_keys::id.desc())
+                    .load(conn)
+                    .await
+            }
+
+            /// The unrevoked key matching a plaintext key from a request.
+            pub async fn find_active(key: &str, conn: &mut AsyncPgConnection) -> QueryResult<Option<ApiKey>> {
+                api_keys::table
+                    .filter(api_keys::key_hash.eq(Self::hash_key(key)))
+                    .filter(api_keys::revoked_at.is_null())
+                    .first(conn)
+                    .await
+                    .optional()
+            }
+
+            pub async fn revoke_owned(id: i32, user_id: i32, conn: &mut AsyncPgConnection) -> QueryResult<usize> {
+                diesel::update(
+                    api_keys::table
+                        .filter(api_keys::id.eq(id))
+                        .filter(api_keys::user_id.eq(user_id))
+                        .filter(api_keys::revoked_at.is_null())
+                )
+                .set(api_keys::revoked_at.eq(chrono::Utc::now().naive_utc()))
+                .execute(conn)
+                .await
+            }
+
+            pub async fn touch(&self, conn: &mut AsyncPgConnection) -> QueryResult<usize> {
+                let now = chrono::Utc::now().naive_utc();
+                let recent = self
+                    .last_used_at
+                    .is_some_and(|used| (now - used).num_seconds() < LAST_USED_RESOLUTION_SECS);
+                if recent {
+                    return Ok(0);
+                }
+                diesel::update(api_keys::table.find(self.id))
+                    .set(api_keys::last_used_at.eq(now))
+                    .execute(conn)
+                    .await
+            }
+        }
+
+        impl From<ApiKey> for ApiKeyView {
+            fn from(api_key: ApiKey) -> Self {
+                ApiKeyView {
+                    id: api_key.id,
+                    name: api_key.name,
+                    prefix: api_key.prefix,
+                    last_used_at: api_key.last_used_at,
+                    revoked: api_key.revoked_at.is_some(),
+                    created_at: api_key.created_at,
+                }
+            }
+        }
+    }
+}
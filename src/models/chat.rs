@@ -0,0 +1,213 @@
+use cfg_if::cfg_if;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ChatSessionView {
+    pub id: Uuid,
+    pub title: String,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChatMessageView {
+    pub id: Uuid,
+    pub session_id: Uuid,
+    pub role: String,
+    pub content: String,
+    pub citations: Option<serde_json::Value>,
+    pub created_at: Option<NaiveDateTime>,
+}
+
+/// Longest title generated from a session's first question.
+pub const CHAT_TITLE_MAX_CHARS: usize = 60;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use crate::schema::*;
+        use crate::models::User;
+        use diesel::prelude::*;
+        use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+        #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Identifiable, Selectable, Associations)]
+        #[diesel(belongs_to(User, foreign_key = user_id))]
+        #[diesel(table_name = chat_sessions)]
+        pub struct ChatSession {
+            pub id: Uuid,
+            pub user_id: i32,
+            pub title: String,
+            pub created_at: Option<NaiveDateTime>,
+            pub updated_at: Option<NaiveDateTime>,
+        }
+
+        #[derive(Debug, Insertable)]
+        #[diesel(table_name = chat_sessions)]
+        pub struct NewChatSession {
+            pub user_id: i32,
+            pub title: String,
+        }
+
+        #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Identifiable, Selectable, Associations)]
+        #[diesel(belongs_to(ChatSession, foreign_key = session_id))]
+        #[diesel(table_name = chat_messages)]
+        pub struct ChatMessage {
+            pub id: Uuid,
+            pub session_id: Uuid,
+            pub role: String,
+            pub content: String,
+            pub citations: Option<serde_json::Value>,
+            pub created_at: Option<NaiveDateTime>,
+        }
+
+        #[derive(Debug, Insertable)]
+        #[diesel(table_name = chat_messages)]
+        pub struct NewChatMessage {
+            pub session_id: Uuid,
+            pub role: String,
+            pub content: String,
+            pub citations: Option<serde_json::Value>,
+        }
+
+        impl ChatSession {
+            /// Short title derived from the question that started the conversation.
+            pub fn title_from_query(query: &str) -> String {
+                let query = query.split_whitespace().collect::<Vec<_>>().join(" ");
+                if query.chars().count() <= CHAT_TITLE_MAX_CHARS {
+                    return query;
+                }
+                let mut title: String = query.chars().take(CHAT_TITLE_MAX_CHARS - 1).collect();
+                title.push('…');
+                title
+            }
+
+            pub async fn create(user_id: i32, title: String, conn: &mut AsyncPgConnection) -> QueryResult<ChatSession> {
+                diesel::insert_into(chat_sessions::table)
+                    .values(&NewChatSession { user_id, title })
+                    .returning(chat_sessions::all_columns)
+                    .get_result(conn)
+                    .await
+            }
+
+            /// The session, only if it belongs to `user_id`.
+            pub async fn find_owned(id: Uuid, user_id: i32, conn: &mut AsyncPgConnection) -> QueryResult<Option<ChatSession>> {
+                chat_sessions::table
+                    .filter(chat_sessions::id.eq(id))
+                    .filter(chat_sessions::user_id.eq(user_id))
+                    .first(conn)
+                    .await
+                    .optional()
+            }
+
+            /// Most recently active first.
+            pub async fn list_for_user(user_id: i32, conn: &mut AsyncPgConnection) -> QueryResult<Vec<ChatSession>> {
+                chat_sessions::table
+                    .filter(chat_sessions::user_id.eq(user_id))
+                    .order_by(chat_sessions::updated_at.desc())
+                    .load(conn)
+                    .await
+            }
+
+            pub async fn rename(&self, title: String, conn: &mut AsyncPgConnection) -> QueryResult<ChatSession> {
+                diesel::update(chat_sessions::table.find(self.id))
+                    .set(chat_sessions::title.eq(title))
+                    .returning(chat_sessions::all_columns)
+                    .get_result(conn)
+                    .await
+            }
+
+            /// delete a session owned by `user_id` - CASCADE removes its messages
+            pub async fn delete_owned(id: Uuid, user_id: i32, conn: &mut AsyncPgConnection) -> QueryResult<usize> {
+                diesel::delete(
+                    chat_sessions::table
+                        .filter(chat_sessions::id.eq(id))
+                        .filter(chat_sessions::user_id.eq(user_id))
+                )
+                .execute(conn)
+                .await
+            }
+
+            pub async fn messages(&self, conn: &mut AsyncPgConnection) -> QueryResult<Vec<ChatMessage>> {
+                ChatMessage::belonging_to(self)
+                    .order_by(chat_messages::created_at.asc())
+                    .load(conn)
+                    .await
+            }
+
+            /// The last `limit` messages, oldest first.
+            pub async fn recent_messages(&self, limit: i64, conn: &mut AsyncPgConnection) -> QueryResult<Vec<ChatMessage>> {
+                let mut messages: Vec<ChatMessage> = ChatMessage::belonging_to(self)
+                    .order_by(chat_messages::created_at.desc())
+                    .limit(limit)
+                    .load(conn)
+                    .await?;
+                messages.reverse();
+                Ok(messages)
+            }
+
+            /// Append a message and bump the session to the top of the user's list.
+            pub async fn add_message(
+                &self,
+                role: &str,
+                content: String,
+                citations: Option<serde_json::Value>,
+                conn: &mut AsyncPgConnection
+            ) -> QueryResult<ChatMessage> {
+                let message = diesel::insert_into(chat_messages::table)
+                    .values(&NewChatMessage {
+                        session_id: self.id,
+                        role: role.to_string(),
+                        content,
+                        citations,
+                    })
+                    .returning(chat_messages::all_columns)
+                    .get_result(conn)
+                    .await?;
+
+                diesel::update(chat_sessions::table.find(self.id))
+                    .set(chat_sessions::updated_at.eq(chrono::Utc::now().naive_utc()))
+                    .execute(conn)
+                    .await?;
+
+                Ok(message)
+            }
+        }
+
+        impl From<ChatSession> for ChatSessionView {
+            fn from(session: ChatSession) -> Self {
+                ChatSessionView {
+                    id: session.id,
+                    title: session.title,
+                    updated_at: session.updated_at,
+                }
+            }
+        }
+
+        impl From<ChatMessage> for ChatMessageView {
+            fn from(message: ChatMessage) -> Self {
+                ChatMessageView {
+                    id: message.id,
+                    session_id: message.session_id,
+                    role: message.role,
+                    content: message.content,
+                    citations: message.citations,
+                    created_at: message.created_at,
+                }
+            }
+        }
+
+        impl From<ChatMessage> for crate::rag_service::rag::rag::RagMessage {
+            fn from(message: ChatMessage) -> Self {
+                crate::rag_service::rag::rag::RagMessage {
+                    role: message.role,
+                    content: message.content,
+                    citations: message.citations.and_then(|c| serde_json::from_value(c).ok()),
+                    timestamp: message
+                        .created_at
+                        .map(|t| t.and_utc().to_rfc3339())
+                        .unwrap_or_default(),
+                }
+            }
+        }
+    }
+}
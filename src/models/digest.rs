@@ -0,0 +1,136 @@
+use cfg_if::cfg_if;
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DigestSummaryView {
+    pub id: i32,
+    pub period: String,
+    pub grouped_by: String,
+    pub starts_on: NaiveDate,
+    pub ends_on: NaiveDate,
+    pub title: String,
+    pub post_count: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DigestView {
+    pub id: i32,
+    pub period: String,
+    pub grouped_by: String,
+    pub starts_on: NaiveDate,
+    pub ends_on: NaiveDate,
+    pub title: String,
+    pub html: String,
+    pub post_count: i32,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use crate::schema::*;
+        use diesel::prelude::*;
+        use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+        #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Identifiable, Selectable)]
+        #[diesel(table_name = digests)]
+        pub struct Digest {
+            pub id: i32,
+            pub period: String,
+            pub grouped_by: String,
+            pub starts_on: NaiveDate,
+            pub ends_on: NaiveDate,
+            pub title: String,
+            pub markdown: String,
+            pub html: String,
+            pub post_count: i32,
+            pub sources: serde_json::Value,
+            pub created_at: Option<NaiveDateTime>,
+            pub updated_at: Option<NaiveDateTime>,
+        }
+
+        #[derive(Debug, Insertable, AsChangeset)]
+        #[diesel(table_name = digests)]
+        pub struct NewDigest {
+            pub period: String,
+            pub grouped_by: String,
+            pub starts_on: NaiveDate,
+            pub ends_on: NaiveDate,
+            pub title: String,
+            pub markdown: String,
+            pub html: String,
+            pub post_count: i32,
+            pub sources: serde_json::Value,
+        }
+
+        impl Digest {
+            /// Insert, or regenerate the digest for the same period, grouping and start date.
+            pub async fn upsert(digest: &NewDigest, conn: &mut AsyncPgConnection) -> QueryResult<Digest> {
+                diesel::insert_into(digests::table)
+                    .values(digest)
+                    .on_conflict((digests::period, digests::grouped_by, digests::starts_on))
+                    .do_update()
+                    .set(digest)
+                    .returning(digests::all_columns)
+                    .get_result(conn)
+                    .await
+            }
+
+            pub async fn find(id: i32, conn: &mut AsyncPgConnection) -> QueryResult<Option<Digest>> {
+                digests::table
+                    .find(id)
+                    .first(conn)
+                    .await
+                    .optional()
+            }
+
+            /// The digest covering the most recent window.
+            pub async fn latest(conn: &mut AsyncPgConnection) -> QueryResult<Option<Digest>> {
+                digests::table
+                    .order_by((digests::ends_on.desc(), digests::updated_at.desc()))
+                    .first(conn)
+                    .await
+                    .optional()
+            }
+
+            /// Newest first.
+            pub async fn list_recent(limit: i64, conn: &mut AsyncPgConnection) -> QueryResult<Vec<Digest>> {
+                digests::table
+                    .order_by((digests::ends_on.desc(), digests::updated_at.desc()))
+                    .limit(limit)
+                    .load(conn)
+                    .await
+            }
+        }
+
+        impl From<Digest> for DigestSummaryView {
+            fn from(digest: Digest) -> Self {
+                DigestSummaryView {
+                    id: digest.id,
+                    period: digest.period,
+                    grouped_by: digest.grouped_by,
+                    starts_on: digest.starts_on,
+                    ends_on: digest.ends_on,
+                    title: digest.title,
+                    post_count: digest.post_count,
+                }
+            }
+        }
+
+        impl From<Digest> for DigestView {
+            fn from(digest: Digest) -> Self {
+                DigestView {
+                    id: digest.id,
+                    period: digest.period,
+                    grouped_by: digest.grouped_by,
+                    starts_on: digest.starts_on,
+                    ends_on: digest.ends_on,
+                    title: digest.title,
+                    html: digest.html,
+                    post_count: digest.post_count,
+                    updated_at: digest.updated_at,
+                }
+            }
+        }
+    }
+}
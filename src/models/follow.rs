@@ -0,0 +1,63 @@
+use cfg_if::cfg_if;
+
+/// Companies a single user may follow.
+pub const MAX_FOLLOWED_COMPANIES: i64 = 100;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use chrono::NaiveDateTime;
+        use serde::{Deserialize, Serialize};
+        use crate::schema::*;
+        use crate::models::User;
+        use diesel::prelude::*;
+        use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+        #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Identifiable, Selectable, Associations)]
+        #[diesel(belongs_to(User, foreign_key = user_id))]
+        #[diesel(table_name = followed_companies)]
+        #[diesel(primary_key(user_id, company))]
+        pub struct FollowedCompany {
+            pub user_id: i32,
+            pub company: String,
+            pub created_at: Option<NaiveDateTime>,
+        }
+
+        impl FollowedCompany {
+            pub async fn set(user_id: i32, company: &str, followed: bool, conn: &mut AsyncPgConnection) -> QueryResult<usize> {
+                if followed {
+                    diesel::insert_into(followed_companies::table)
+                        .values((
+                            followed_companies::user_id.eq(user_id),
+                            followed_companies::company.eq(company),
+                        ))
+                        .on_conflict((followed_companies::user_id, followed_companies::company))
+                        .do_nothing()
+                        .execute(conn)
+                        .await
+                } else {
+                    diesel::delete(followed_companies::table.find((user_id, company)))
+                        .execute(conn)
+                        .await
+                }
+            }
+
+            pub async fn count_for_user(user_id: i32, conn: &mut AsyncPgConnection) -> QueryResult<i64> {
+                followed_companies::table
+                    .filter(followed_companies::user_id.eq(user_id))
+                    .count()
+                    .get_result(conn)
+                    .await
+            }
+
+            /// Alphabetical.
+            pub async fn companies_for_user(user_id: i32, conn: &mut AsyncPgConnection) -> QueryResult<Vec<String>> {
+                followed_companies::table
+                    .filter(followed_companies::user_id.eq(user_id))
+                    .order_by(followed_companies::company.asc())
+                    .select(followed_companies::company)
+                    .load(conn)
+                    .await
+            }
+        }
+    }
+}
@@ -1,20 +1,40 @@
+pub mod api_key;
+pub mod chat;
+pub mod digest;
+pub mod follow;
 pub mod game;
+pub mod rag_usage;
+pub mod reading;
 pub mod rooms;
+pub mod saved_search;
 pub mod users;
 
+pub use api_key::{ApiKeyView, CreatedApiKeyView, MAX_API_KEYS};
+pub use chat::{ChatMessageView, ChatSessionView};
+pub use digest::{DigestSummaryView, DigestView};
+pub use follow::MAX_FOLLOWED_COMPANIES;
 pub use game::{
     CanvasGalleryView, CreateCanvasView, CreateTeamView, GameTeamView, SavedCanvasView,
     TeamPlayerView, UserGameStatsView,
 };
+pub use reading::{ReadingListView, ReadingStateView, MAX_READING_LISTS};
 pub use rooms::{
     CanvasRoomView, CreateRoomView, CreateSessionView, GameSessionView, JoinRoomView,
     RoomPlayerView, RoomWithPlayersView,
 };
+pub use saved_search::{SavedSearchView, WebhookDeliveryView, MAX_SAVED_SEARCHES, SAVED_SEARCH_KINDS};
 pub use users::{CreateUserView, UpdateUserPreferencesView, UserView};
 
 cfg_if::cfg_if! {
     if #[cfg(feature = "ssr")] {
         pub use users::{User, NewUser, UpdateUserPreferences};
+        pub use api_key::{ApiKey, NewApiKey};
+        pub use chat::{ChatSession, NewChatSession, ChatMessage, NewChatMessage};
+        pub use rag_usage::RagUsage;
+        pub use reading::{PostBookmark, PostRead, ReadingList, NewReadingList, ReadingListItem};
+        pub use digest::{Digest, NewDigest};
+        pub use follow::FollowedCompany;
+        pub use saved_search::{SavedSearch, NewSavedSearch, WebhookDelivery};
         pub use rooms::{
             CanvasRoom, NewCanvasRoom, RoomDeleteError,
             RoomPlayer, NewRoomPlayer, KickPlayerError,
@@ -0,0 +1,71 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use chrono::NaiveDate;
+        use serde::{Deserialize, Serialize};
+        use crate::schema::*;
+        use crate::models::User;
+        use diesel::prelude::*;
+        use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+        #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Identifiable, Selectable, Associations)]
+        #[diesel(belongs_to(User, foreign_key = user_id))]
+        #[diesel(table_name = rag_usage)]
+        #[diesel(primary_key(user_id, day))]
+        pub struct RagUsage {
+            pub user_id: i32,
+            pub day: NaiveDate,
+            pub questions: i32,
+        }
+
+        impl RagUsage {
+            /// Count one question against the user's quota for `day`. Returns the new total, or
+            /// `None` (leaving the count unchanged) if it would go over `limit`.
+            pub async fn try_consume(
+                user_id: i32,
+                day: NaiveDate,
+                limit: i32,
+                conn: &mut AsyncPgConnection
+            ) -> QueryResult<Option<i32>> {
+                let questions: i32 = diesel::insert_into(rag_usage::table)
+                    .values((
+                        rag_usage::user_id.eq(user_id),
+                        rag_usage::day.eq(day),
+                        rag_usage::questions.eq(1),
+                    ))
+                    .on_conflict((rag_usage::user_id, rag_usage::day))
+                    .do_update()
+                    .set(rag_usage::questions.eq(rag_usage::questions + 1))
+                    .returning(rag_usage::questions)
+                    .get_result(conn)
+                    .await?;
+
+                if questions > limit {
+                    Self::release(user_id, day, conn).await?;
+                    return Ok(None);
+                }
+                Ok(Some(questions))
+            }
+
+            /// Give back a question that never got an answer.
+            pub async fn release(user_id: i32, day: NaiveDate, conn: &mut AsyncPgConnection) -> QueryResult<usize> {
+                diesel::update(rag_usage::table.find((user_id, day)))
+                    .filter(rag_usage::questions.gt(0))
+                    .set(rag_usage::questions.eq(rag_usage::questions - 1))
+                    .execute(conn)
+                    .await
+            }
+
+            pub async fn used_on(user_id: i32, day: NaiveDate, conn: &mut AsyncPgConnection) -> QueryResult<i32> {
+                rag_usage::table
+                    .find((user_id, day))
+                    .select(rag_usage::questions)
+                    .first(conn)
+                    .await
+                    .optional()
+                    .map(|questions| questions.unwrap_or(0))
+            }
+        }
+    }
+}
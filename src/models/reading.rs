@@ -0,0 +1,258 @@
+use cfg_if::cfg_if;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// Reading lists a single user may keep.
+pub const MAX_READING_LISTS: i64 = 50;
+
+/// Post ids the user has bookmarked and read, used to mark up post listings.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ReadingStateView {
+    pub bookmarked: Vec<i32>,
+    pub read: Vec<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ReadingListView {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    /// Newest addition first.
+    pub post_ids: Vec<i32>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use crate::schema::*;
+        use crate::models::User;
+        use diesel::prelude::*;
+        use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+        #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Identifiable, Selectable, Associations)]
+        #[diesel(belongs_to(User, foreign_key = user_id))]
+        #[diesel(table_name = post_bookmarks)]
+        pub struct PostBookmark {
+            pub id: i32,
+            pub user_id: i32,
+            pub post_id: i32,
+            pub created_at: Option<NaiveDateTime>,
+        }
+
+        #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Identifiable, Selectable, Associations)]
+        #[diesel(belongs_to(User, foreign_key = user_id))]
+        #[diesel(table_name = post_reads)]
+        pub struct PostRead {
+            pub id: i32,
+            pub user_id: i32,
+            pub post_id: i32,
+            pub created_at: Option<NaiveDateTime>,
+        }
+
+        #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Identifiable, Selectable, Associations)]
+        #[diesel(belongs_to(User, foreign_key = user_id))]
+        #[diesel(table_name = reading_lists)]
+        pub struct ReadingList {
+            pub id: i32,
+            pub user_id: i32,
+            pub name: String,
+            pub description: Option<String>,
+            pub created_at: Option<NaiveDateTime>,
+            pub updated_at: Option<NaiveDateTime>,
+        }
+
+        #[derive(Debug, Insertable)]
+        #[diesel(table_name = reading_lists)]
+        pub struct NewReadingList {
+            pub user_id: i32,
+            pub name: String,
+            pub description: Option<String>,
+        }
+
+        #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Identifiable, Selectable, Associations)]
+        #[diesel(belongs_to(ReadingList, foreign_key = reading_list_id))]
+        #[diesel(table_name = reading_list_items)]
+        pub struct ReadingListItem {
+            pub id: i32,
+            pub reading_list_id: i32,
+            pub post_id: i32,
+            pub created_at: Option<NaiveDateTime>,
+        }
+
+        impl PostBookmark {
+            pub async fn set(user_id: i32, post_id: i32, bookmarked: bool, conn: &mut AsyncPgConnection) -> QueryResult<usize> {
+                if bookmarked {
+                    diesel::insert_into(post_bookmarks::table)
+                        .values((post_bookmarks::user_id.eq(user_id), post_bookmarks::post_id.eq(post_id)))
+                        .on_conflict((post_bookmarks::user_id, post_bookmarks::post_id))
+                        .do_nothing()
+                        .execute(conn)
+                        .await
+                } else {
+                    diesel::delete(
+                        post_bookmarks::table
+                            .filter(post_bookmarks::user_id.eq(user_id))
+                            .filter(post_bookmarks::post_id.eq(post_id))
+                    )
+                    .execute(conn)
+                    .await
+                }
+            }
+
+            /// Most recently bookmarked first.
+            pub async fn post_ids_for_user(user_id: i32, conn: &mut AsyncPgConnection) -> QueryResult<Vec<i32>> {
+                post_bookmarks::table
+                    .filter(post_bookmarks::user_id.eq(user_id))
+                    .order_by(post_bookmarks::id.desc())
+                    .select(post_bookmarks::post_id)
+                    .load(conn)
+                    .await
+            }
+        }
+
+        impl PostRead {
+            pub async fn set(user_id: i32, post_id: i32, read: bool, conn: &mut AsyncPgConnection) -> QueryResult<usize> {
+                if read {
+                    diesel::insert_into(post_reads::table)
+                        .values((post_reads::user_id.eq(user_id), post_reads::post_id.eq(post_id)))
+                        .on_conflict((post_reads::user_id, post_reads::post_id))
+                        .do_nothing()
+                        .execute(conn)
+                        .await
+                } else {
+                    diesel::delete(
+                        post_reads::table
+                            .filter(post_reads::user_id.eq(user_id))
+                            .filter(post_reads::post_id.eq(post_id))
+                    )
+                    .execute(conn)
+                    .await
+                }
+            }
+
+            /// Most recently read first.
+            pub async fn post_ids_for_user(user_id: i32, conn: &mut AsyncPgConnection) -> QueryResult<Vec<i32>> {
+                post_reads::table
+                    .filter(post_reads::user_id.eq(user_id))
+                    .order_by(post_reads::id.desc())
+                    .select(post_reads::post_id)
+                    .load(conn)
+                    .await
+            }
+        }
+
+        impl ReadingList {
+            pub async fn create(list: &NewReadingList, conn: &mut AsyncPgConnection) -> QueryResult<ReadingList> {
+                diesel::insert_into(reading_lists::table)
+                    .values(list)
+                    .returning(reading_lists::all_columns)
+                    .get_result(conn)
+                    .await
+            }
+
+            pub async fn count_for_user(user_id: i32, conn: &mut AsyncPgConnection) -> QueryResult<i64> {
+                reading_lists::table
+                    .filter(reading_lists::user_id.eq(user_id))
+                    .count()
+                    .get_result(conn)
+                    .await
+            }
+
+            /// Every list with the ids of its posts, alphabetically.
+            pub async fn list_for_user(
+                user_id: i32,
+                conn: &mut AsyncPgConnection
+            ) -> QueryResult<Vec<(ReadingList, Vec<ReadingListItem>)>> {
+                let lists: Vec<ReadingList> = reading_lists::table
+                    .filter(reading_lists::user_id.eq(user_id))
+                    .order_by(reading_lists::name.asc())
+                    .load(conn)
+                    .await?;
+
+                let items: Vec<ReadingListItem> = ReadingListItem::belonging_to(&lists)
+                    .order_by(reading_list_items::id.desc())
+                    .select(ReadingListItem::as_select())
+                    .load(conn)
+                    .await?;
+
+                let grouped = items.grouped_by(&lists);
+                Ok(lists.into_iter().zip(grouped).collect())
+            }
+
+            pub async fn find_owned(id: i32, user_id: i32, conn: &mut AsyncPgConnection) -> QueryResult<Option<ReadingList>> {
+                reading_lists::table
+                    .filter(reading_lists::id.eq(id))
+                    .filter(reading_lists::user_id.eq(user_id))
+                    .first(conn)
+                    .await
+                    .optional()
+            }
+
+            /// delete a list owned by `user_id` - CASCADE removes its items
+            pub async fn delete_owned(id: i32, user_id: i32, conn: &mut AsyncPgConnection) -> QueryResult<usize> {
+                diesel::delete(
+                    reading_lists::table
+                        .filter(reading_lists::id.eq(id))
+                        .filter(reading_lists::user_id.eq(user_id))
+                )
+                .execute(conn)
+                .await
+            }
+
+            /// Newest addition first.
+            pub async fn post_ids(&self, conn: &mut AsyncPgConnection) -> QueryResult<Vec<i32>> {
+                reading_list_items::table
+                    .filter(reading_list_items::reading_list_id.eq(self.id))
+                    .order_by(reading_list_items::id.desc())
+                    .select(reading_list_items::post_id)
+                    .load(conn)
+                    .await
+            }
+
+            pub async fn add_post(&self, post_id: i32, conn: &mut AsyncPgConnection) -> QueryResult<usize> {
+                let added = diesel::insert_into(reading_list_items::table)
+                    .values((
+                        reading_list_items::reading_list_id.eq(self.id),
+                        reading_list_items::post_id.eq(post_id),
+                    ))
+                    .on_conflict((reading_list_items::reading_list_id, reading_list_items::post_id))
+                    .do_nothing()
+                    .execute(conn)
+                    .await?;
+                self.touch(conn).await?;
+                Ok(added)
+            }
+
+            pub async fn remove_post(&self, post_id: i32, conn: &mut AsyncPgConnection) -> QueryResult<usize> {
+                let removed = diesel::delete(
+                    reading_list_items::table
+                        .filter(reading_list_items::reading_list_id.eq(self.id))
+                        .filter(reading_list_items::post_id.eq(post_id))
+                )
+                .execute(conn)
+                .await?;
+                self.touch(conn).await?;
+                Ok(removed)
+            }
+
+            async fn touch(&self, conn: &mut AsyncPgConnection) -> QueryResult<usize> {
+                diesel::update(reading_lists::table.find(self.id))
+                    .set(reading_lists::updated_at.eq(chrono::Utc::now().naive_utc()))
+                    .execute(conn)
+                    .await
+            }
+        }
+
+        impl From<(ReadingList, Vec<ReadingListItem>)> for ReadingListView {
+            fn from((list, items): (ReadingList, Vec<ReadingListItem>)) -> Self {
+                ReadingListView {
+                    id: list.id,
+                    name: list.name,
+                    description: list.description,
+                    post_ids: items.into_iter().map(|item| item.post_id).collect(),
+                    updated_at: list.updated_at,
+                }
+            }
+        }
+    }
+}
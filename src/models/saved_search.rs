@@ -0,0 +1,247 @@
+use cfg_if::cfg_if;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// The ways a saved search can match a new post.
+pub const SAVED_SEARCH_KINDS: [&str; 3] = ["keyword", "company", "semantic"];
+/// Saved searches a single user may keep.
+pub const MAX_SAVED_SEARCHES: i64 = 20;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SavedSearchView {
+    pub id: i32,
+    pub name: String,
+    pub kind: String,
+    pub query: String,
+    pub min_similarity: i32,
+    pub webhook_url: String,
+    pub webhook_secret: String,
+    pub enabled: bool,
+    pub last_matched_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WebhookDeliveryView {
+    pub id: i32,
+    pub saved_search_id: i32,
+    pub post_link: String,
+    pub status: String,
+    pub attempts: i32,
+    pub response_status: Option<i32>,
+    pub error: Option<String>,
+    pub created_at: Option<NaiveDateTime>,
+}
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use crate::schema::*;
+        use crate::models::User;
+        use diesel::prelude::*;
+        use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+        #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Identifiable, Selectable, Associations)]
+        #[diesel(belongs_to(User, foreign_key = user_id))]
+        #[diesel(table_name = saved_searches)]
+        pub struct SavedSearch {
+            pub id: i32,
+            pub user_id: i32,
+            pub name: String,
+            pub kind: String,
+            pub query: String,
+            pub min_similarity: i32,
+            pub webhook_url: String,
+            pub webhook_secret: String,
+            pub enabled: bool,
+            pub last_matched_at: Option<NaiveDateTime>,
+            pub created_at: Option<NaiveDateTime>,
+            pub updated_at: Option<NaiveDateTime>,
+        }
+
+        #[derive(Debug, Insertable)]
+        #[diesel(table_name = saved_searches)]
+        pub struct NewSavedSearch {
+            pub user_id: i32,
+            pub name: String,
+            pub kind: String,
+            pub query: String,
+            pub min_similarity: i32,
+            pub webhook_url: String,
+            pub webhook_secret: String,
+        }
+
+        #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Identifiable, Selectable, Associations)]
+        #[diesel(belongs_to(SavedSearch, foreign_key = saved_search_id))]
+        #[diesel(table_name = webhook_deliveries)]
+        pub struct WebhookDelivery {
+            pub id: i32,
+            pub saved_search_id: i32,
+            pub post_link: String,
+            pub status: String,
+            pub attempts: i32,
+            pub response_status: Option<i32>,
+            pub error: Option<String>,
+            pub payload: serde_json::Value,
+            pub created_at: Option<NaiveDateTime>,
+            pub updated_at: Option<NaiveDateTime>,
+        }
+
+        impl SavedSearch {
+            pub async fn create(search: &NewSavedSearch, conn: &mut AsyncPgConnection) -> QueryResult<SavedSearch> {
+                diesel::insert_into(saved_searches::table)
+                    .values(search)
+                    .returning(saved_searches::all_columns)
+                    .get_result(conn)
+                    .await
+            }
+
+            pub async fn count_for_user(user_id: i32, conn: &mut AsyncPgConnection) -> QueryResult<i64> {
+                saved_searches::table
+                    .filter(saved_searches::user_id.eq(user_id))
+                    .count()
+                    .get_result(conn)
+                    .await
+            }
+
+            /// Oldest first, so the list doesn't reorder as searches are toggled.
+            pub async fn list_for_user(user_id: i32, conn: &mut AsyncPgConnection) -> QueryResult<Vec<SavedSearch>> {
+                saved_searches::table
+                    .filter(saved_searches::user_id.eq(user_id))
+                    .order_by(saved_searches::id.asc())
+                    .load(conn)
+                    .await
+            }
+
+            /// Every search that should be checked against newly ingested posts.
+            pub async fn list_enabled(conn: &mut AsyncPgConnection) -> QueryResult<Vec<SavedSearch>> {
+                saved_searches::table
+                    .filter(saved_searches::enabled.eq(true))
+                    .load(conn)
+                    .await
+            }
+
+            pub async fn set_enabled_owned(
+                id: i32,
+                user_id: i32,
+                enabled: bool,
+                conn: &mut AsyncPgConnection
+            ) -> QueryResult<usize> {
+                diesel::update(
+                    saved_searches::table
+                        .filter(saved_searches::id.eq(id))
+                        .filter(saved_searches::user_id.eq(user_id))
+                )
+                .set(saved_searches::enabled.eq(enabled))
+                .execute(conn)
+                .await
+            }
+
+            /// delete a search owned by `user_id` - CASCADE removes its delivery log
+            pub async fn delete_owned(id: i32, user_id: i32, conn: &mut AsyncPgConnection) -> QueryResult<usize> {
+                diesel::delete(
+                    saved_searches::table
+                        .filter(saved_searches::id.eq(id))
+                        .filter(saved_searches::user_id.eq(user_id))
+                )
+                .execute(conn)
+                .await
+            }
+
+            pub async fn mark_matched(&self, conn: &mut AsyncPgConnection) -> QueryResult<usize> {
+                diesel::update(saved_searches::table.find(self.id))
+                    .set(saved_searches::last_matched_at.eq(chrono::Utc::now().naive_utc()))
+                    .execute(conn)
+                    .await
+            }
+        }
+
+        impl WebhookDelivery {
+            /// Start a delivery of `post_link` for this search. `None` if it was already sent
+            /// (or is being sent), so a post is never delivered twice to the same search.
+            pub async fn claim(
+                saved_search_id: i32,
+                post_link: &str,
+                payload: serde_json::Value,
+                conn: &mut AsyncPgConnection
+            ) -> QueryResult<Option<WebhookDelivery>> {
+                diesel::insert_into(webhook_deliveries::table)
+                    .values((
+                        webhook_deliveries::saved_search_id.eq(saved_search_id),
+                        webhook_deliveries::post_link.eq(post_link),
+                        webhook_deliveries::payload.eq(payload),
+                    ))
+                    .on_conflict((webhook_deliveries::saved_search_id, webhook_deliveries::post_link))
+                    .do_nothing()
+                    .returning(webhook_deliveries::all_columns)
+                    .get_result(conn)
+                    .await
+                    .optional()
+            }
+
+            pub async fn record(
+                &self,
+                status: &str,
+                attempts: i32,
+                response_status: Option<i32>,
+                error: Option<String>,
+                conn: &mut AsyncPgConnection
+            ) -> QueryResult<usize> {
+                diesel::update(webhook_deliveries::table.find(self.id))
+                    .set((
+                        webhook_deliveries::status.eq(status),
+                        webhook_deliveries::attempts.eq(attempts),
+                        webhook_deliveries::response_status.eq(response_status),
+                        webhook_deliveries::error.eq(error),
+                    ))
+                    .execute(conn)
+                    .await
+            }
+
+            /// Newest first across all of a user's saved searches.
+            pub async fn recent_for_user(
+                user_id: i32,
+                limit: i64,
+                conn: &mut AsyncPgConnection
+            ) -> QueryResult<Vec<WebhookDelivery>> {
+                webhook_deliveries::table
+                    .inner_join(saved_searches::table)
+                    .filter(saved_searches::user_id.eq(user_id))
+                    .order_by(webhook_deliveries::created_at.desc())
+                    .limit(limit)
+                    .select(WebhookDelivery::as_select())
+                    .load(conn)
+                    .await
+            }
+        }
+
+        impl From<SavedSearch> for SavedSearchView {
+            fn from(search: SavedSearch) -> Self {
+                SavedSearchView {
+                    id: search.id,
+                    name: search.name,
+                    kind: search.kind,
+                    query: search.query,
+                    min_similarity: search.min_similarity,
+                    webhook_url: search.webhook_url,
+                    webhook_secret: search.webhook_secret,
+                    enabled: search.enabled,
+                    last_matched_at: search.last_matched_at,
+                }
+            }
+        }
+
+        impl From<WebhookDelivery> for WebhookDeliveryView {
+            fn from(delivery: WebhookDelivery) -> Self {
+                WebhookDeliveryView {
+                    id: delivery.id,
+                    saved_search_id: delivery.saved_search_id,
+                    post_link: delivery.post_link,
+                    status: delivery.status,
+                    attempts: delivery.attempts,
+                    response_status: delivery.response_status,
+                    error: delivery.error,
+                    created_at: delivery.created_at,
+                }
+            }
+        }
+    }
+}
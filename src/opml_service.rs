@@ -0,0 +1,191 @@
+#[cfg(feature = "ssr")]
+pub mod opml {
+    use chrono::Utc;
+    use quick_xml::escape::escape;
+    use quick_xml::events::Event;
+    use quick_xml::Reader;
+    use thiserror::Error;
+
+    use crate::rss_service::server::FeedLink;
+
+    /// Longest company name kept from an outline's title.
+    const COMPANY_MAX_CHARS: usize = 255;
+
+    #[derive(Error, Debug)]
+    pub enum OpmlError {
+        #[error("XML error: {0}")]
+        Xml(#[from] quick_xml::Error),
+
+        #[error("Invalid attribute: {0}")]
+        Attribute(#[from] quick_xml::events::attributes::AttrError),
+    }
+
+    /// Every outline with an `xmlUrl`, at any depth, so feeds grouped into folders by other
+    /// readers come through too. The company is the outline's `title`, falling back to `text`
+    /// and then to the feed's host.
+    pub fn parse_opml(xml: &str) -> Result<Vec<FeedLink>, OpmlError> {
+        let mut reader = Reader::from_str(xml);
+        reader.config_mut().trim_text(true);
+
+        let mut feeds = Vec::new();
+        loop {
+            match reader.read_event()? {
+                Event::Start(element) | Event::Empty(element) => {
+                    if !element.local_name().as_ref().eq_ignore_ascii_case(b"outline") {
+                        continue;
+                    }
+
+                    let (mut title, mut text, mut xml_url) = (None, None, None);
+                    for attribute in element.attributes() {
+                        let attribute = attribute?;
+                        let value = attribute.unescape_value()?.trim().to_string();
+                        if value.is_empty() {
+                            continue;
+                        }
+                        match attribute.key.local_name().as_ref().to_ascii_lowercase().as_slice() {
+                            b"title" => title = Some(value),
+                            b"text" => text = Some(value),
+                            b"xmlurl" => xml_url = Some(value),
+                            _ => {}
+                        }
+                    }
+
+                    let Some(link) = xml_url.filter(|url| url.starts_with("http://") || url.starts_with("https://")) else {
+                        continue;
+                    };
+                    let company = title
+                        .or(text)
+                        .unwrap_or_else(|| feed_host(&link).to_string())
+                        .chars()
+                        .take(COMPANY_MAX_CHARS)
+                        .collect();
+
+                    feeds.push(FeedLink {
+                        company,
+                        link,
+                        last_processed: None,
+                        logo_url: None,
+                        enabled: true,
+                        entry_limit: None,
+                    });
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        Ok(feeds)
+    }
+
+    fn feed_host(link: &str) -> &str {
+        let without_scheme = link.split_once("://").map_or(link, |(_, rest)| rest);
+        without_scheme.split(['/', '?', '#']).next().unwrap_or(without_scheme)
+    }
+
+    /// An OPML 2.0 document listing `feeds`, one flat outline per company.
+    pub fn render_opml(feeds: &[FeedLink]) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<opml version=\"2.0\">\n");
+        xml.push_str("  <head>\n    <title>bryptoblogs feeds</title>\n");
+        xml.push_str(&format!("    <dateCreated>{}</dateCreated>\n", Utc::now().to_rfc2822()));
+        xml.push_str("  </head>\n  <body>\n");
+        for feed in feeds {
+            let company = escape(feed.company.as_str());
+            xml.push_str(&format!(
+                "    <outline type=\"rss\" text=\"{}\" title=\"{}\" xmlUrl=\"{}\"/>\n",
+                company,
+                company,
+                escape(feed.link.as_str())
+            ));
+        }
+        xml.push_str("  </body>\n</opml>\n");
+        xml
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn feed(company: &str, link: &str) -> FeedLink {
+            FeedLink {
+                company: company.to_string(),
+                link: link.to_string(),
+                last_processed: None,
+                logo_url: None,
+                enabled: true,
+                entry_limit: None,
+            }
+        }
+
+        fn companies_and_links(feeds: &[FeedLink]) -> Vec<(&str, &str)> {
+            feeds.iter().map(|f| (f.company.as_str(), f.link.as_str())).collect()
+        }
+
+        #[test]
+        fn test_render_parse_round_trip() {
+            let feeds = vec![
+                feed("Netflix", "https://netflixtechblog.com/feed"),
+                feed("AT&T <Labs> \"R&D\" 'blog'", "https://example.com/rss?a=1&b=2"),
+            ];
+            let xml = render_opml(&feeds);
+            assert!(xml.contains("AT&amp;T &lt;Labs&gt; &quot;R&amp;D&quot; &apos;blog&apos;"));
+            assert!(xml.contains("xmlUrl=\"https://example.com/rss?a=1&amp;b=2\""));
+
+            let parsed = parse_opml(&xml).unwrap();
+            assert_eq!(companies_and_links(&parsed), companies_and_links(&feeds));
+        }
+
+        #[test]
+        fn test_parse_nested_outlines() {
+            let xml = r#"<?xml version="1.0"?>
+                <opml version="1.0"><body>
+                  <outline text="Engineering">
+                    <outline text="Databases">
+                      <outline type="rss" text="Cockroach" xmlUrl="https://www.cockroachlabs.com/blog/index.xml"/>
+                    </outline>
+                    <outline type="rss" title="Stripe" text="ignored" xmlUrl="https://stripe.com/blog/feed.rss"/>
+                  </outline>
+                  <outline type="rss" text="Top level" xmlUrl="http://example.org/feed"/>
+                </body></opml>"#;
+
+            let parsed = parse_opml(xml).unwrap();
+            assert_eq!(
+                companies_and_links(&parsed),
+                vec![
+                    ("Cockroach", "https://www.cockroachlabs.com/blog/index.xml"),
+                    ("Stripe", "https://stripe.com/blog/feed.rss"),
+                    ("Top level", "http://example.org/feed"),
+                ]
+            );
+            assert!(parsed.iter().all(|f| f.enabled && f.last_processed.is_none()));
+        }
+
+        #[test]
+        fn test_parse_company_fallbacks_and_skips() {
+            let xml = r#"<opml><body>
+                <OUTLINE TITLE="Upper" XMLURL="https://upper.example.com/feed"/>
+                <outline text="Text only" xmlUrl="https://text.example.com/feed"/>
+                <outline title="  " xmlUrl="https://blog.example.com:8443/atom.xml?x=1"/>
+                <outline text="Not http" xmlUrl="feed://example.com/rss"/>
+                <outline text="Relative" xmlUrl="/rss.xml"/>
+                <outline text="No feed" htmlUrl="https://example.com"/>
+            </body></opml>"#;
+
+            let parsed = parse_opml(xml).unwrap();
+            assert_eq!(
+                companies_and_links(&parsed),
+                vec![
+                    ("Upper", "https://upper.example.com/feed"),
+                    ("Text only", "https://text.example.com/feed"),
+                    ("blog.example.com:8443", "https://blog.example.com:8443/atom.xml?x=1"),
+                ]
+            );
+        }
+
+        #[test]
+        fn test_parse_clips_long_company() {
+            let xml = format!(r#"<opml><body><outline text="{}" xmlUrl="https://example.com/feed"/></body></opml>"#, "x".repeat(300));
+            let parsed = parse_opml(&xml).unwrap();
+            assert_eq!(parsed[0].company.chars().count(), COMPANY_MAX_CHARS);
+        }
+    }
+}
@@ -1,6 +1,15 @@
 #[cfg(feature = "ssr")]
 pub mod rag;
 
+#[cfg(feature = "ssr")]
+pub mod fixture;
+
+#[cfg(feature = "ssr")]
+pub mod context;
+
+#[cfg(feature = "ssr")]
+pub mod source;
+
 #[cfg(not(feature = "ssr"))]
 pub mod rag {
     // Client-side stubs for types that need to be shared
@@ -23,10 +32,36 @@ pub mod rag {
         pub relevance_score: f32,
     }
 
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct UnsupportedClaim {
+        pub sentence: String,
+        pub reason: String,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct AnswerVerification {
+        pub checked: usize,
+        pub unsupported: Vec<UnsupportedClaim>,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct ContextUsage {
+        pub tokens_used: usize,
+        pub token_budget: usize,
+        pub posts_used: usize,
+        pub posts_dropped: usize,
+        pub passages: usize,
+        pub duplicates_skipped: usize,
+    }
+
     #[derive(Debug, Serialize, Deserialize, Clone)]
     pub struct RagResponse {
         pub message_type: String,
         pub content: Option<String>,
         pub citations: Option<Vec<Citation>>,
+        #[serde(default)]
+        pub verification: Option<AnswerVerification>,
+        #[serde(default)]
+        pub context_usage: Option<ContextUsage>,
     }
 }
@@ -0,0 +1,481 @@
+/// Builds the prompt context for an answer within a token budget: posts in relevance order,
+/// each represented by its passages most similar to the question, near-duplicates skipped.
+pub mod context {
+    use log::{debug, warn};
+    use once_cell::sync::Lazy;
+    use tiktoken_rs::CoreBPE;
+
+    use crate::components::poasts::Poast;
+    use crate::embeddings_service::embeddings_local::LocalEmbeddingService;
+    use crate::rag_service::rag::rag::ContextUsage;
+    use crate::vector_search_service::vector_search::cosine_similarity;
+
+    /// Tokens of retrieved material sent with each question.
+    pub const CONTEXT_TOKEN_BUDGET: usize = 6000;
+    /// Share of the budget tool output (stats, comparisons) may take before posts.
+    const FUNCTION_RESULTS_SHARE: usize = 4;
+    const FUNCTION_RESULT_MAX_TOKENS: usize = 400;
+    const PASSAGE_MAX_TOKENS: usize = 180;
+    const MAX_PASSAGES_PER_POST: usize = 3;
+    /// Only the first passages of a long full text are scored.
+    const MAX_PASSAGES_CONSIDERED: usize = 12;
+    /// Passages at least this similar to one already included are treated as the same content.
+    const DUPLICATE_SIMILARITY: f32 = 0.95;
+    /// Passages scored when one post has the whole budget; enough to fill it several times over.
+    const SINGLE_POST_PASSAGES_CONSIDERED: usize = 80;
+
+    /// How the budget is shared out.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ContextMode {
+        /// Several posts, each represented by its few passages closest to the question.
+        Posts,
+        /// Questions about one post: its most relevant passages fill the whole budget.
+        SinglePost,
+    }
+
+    impl ContextMode {
+        fn passages_considered(self) -> usize {
+            match self {
+                ContextMode::Posts => MAX_PASSAGES_CONSIDERED,
+                ContextMode::SinglePost => SINGLE_POST_PASSAGES_CONSIDERED,
+            }
+        }
+
+        fn passages_per_post(self) -> usize {
+            match self {
+                ContextMode::Posts => MAX_PASSAGES_PER_POST,
+                ContextMode::SinglePost => usize::MAX,
+            }
+        }
+    }
+
+    /// gpt-4o family tokenizer. Token counts fall back to a chars / 4 estimate if it can't load.
+    static TOKENIZER: Lazy<Option<CoreBPE>> = Lazy::new(|| match tiktoken_rs::o200k_base() {
+        Ok(bpe) => Some(bpe),
+        Err(e) => {
+            warn!("Failed to load tokenizer, estimating token counts: {}", e);
+            None
+        }
+    });
+
+    pub fn count_tokens(text: &str) -> usize {
+        match TOKENIZER.as_ref() {
+            Some(bpe) => bpe.encode_ordinary(text).len(),
+            None => text.len().div_ceil(4),
+        }
+    }
+
+    /// `text` cut to at most `max_tokens` tokens, with an ellipsis if anything was removed.
+    pub fn truncate_to_tokens(text: &str, max_tokens: usize) -> String {
+        match TOKENIZER.as_ref() {
+            Some(bpe) => {
+                let tokens = bpe.encode_ordinary(text);
+                if tokens.len() <= max_tokens {
+                    return text.to_string();
+                }
+                let mut truncated = bpe
+                    .decode(tokens[..max_tokens.saturating_sub(1)].to_vec())
+                    .unwrap_or_default();
+                truncated.push('…');
+                truncated
+            }
+            None => {
+                if text.len() <= max_tokens * 4 {
+                    return text.to_string();
+                }
+                let mut truncated: String = text.chars().take(max_tokens.saturating_sub(1) * 4).collect();
+                truncated.push('…');
+                truncated
+            }
+        }
+    }
+
+    pub struct AssembledContext {
+        pub text: String,
+        /// The posts that made it in, numbered `[1]..` in this order in `text`.
+        pub posts: Vec<Poast>,
+        pub scores: Vec<f32>,
+        pub usage: ContextUsage,
+    }
+
+    struct Passage {
+        /// Position in the post, so excerpts are shown in reading order.
+        index: usize,
+        text: String,
+        tokens: usize,
+        score: f32,
+        embedding: Option<Vec<f32>>,
+    }
+
+    /// Fit `function_results` and then `posts` (best first, with `scores`) into `budget` tokens.
+    /// Posts that don't fit, or only repeat content already included, are left out.
+    pub fn assemble_context(
+        query: &str,
+        posts: Vec<Poast>,
+        scores: Vec<f32>,
+        function_results: &[String],
+        budget: usize,
+        mode: ContextMode,
+    ) -> AssembledContext {
+        let embedder = LocalEmbeddingService::init()
+            .and_then(|_| LocalEmbeddingService::get_instance())
+            .map_err(|e| warn!("Passage scoring unavailable, using document order: {}", e))
+            .ok();
+        let embed = |text: &str| embedder.and_then(|service| service.generate_embedding(text).ok());
+
+        assemble_with(embed(query), embed, posts, scores, function_results, budget, mode)
+    }
+
+    /// `assemble_context` with the passage embedder passed in. Without embeddings passages
+    /// keep document order and only exact repeats count as duplicates.
+    fn assemble_with(
+        query_embedding: Option<Vec<f32>>,
+        embed: impl Fn(&str) -> Option<Vec<f32>>,
+        posts: Vec<Poast>,
+        scores: Vec<f32>,
+        function_results: &[String],
+        budget: usize,
+        mode: ContextMode,
+    ) -> AssembledContext {
+        let mut text = String::from("Function calls executed:\n");
+        let function_budget = budget / FUNCTION_RESULTS_SHARE;
+        let mut function_tokens = 0;
+        for result in function_results {
+            let clipped = truncate_to_tokens(result, FUNCTION_RESULT_MAX_TOKENS);
+            let line = format!("- {}\n", clipped);
+            let tokens = count_tokens(&line);
+            if function_tokens + tokens > function_budget {
+                text.push_str("- (further tool output omitted)\n");
+                break;
+            }
+            function_tokens += tokens;
+            text.push_str(&line);
+        }
+        text.push_str("\nRetrieved blog posts:\n\n");
+
+        let mut used = count_tokens(&text);
+        let mut included_posts = Vec::new();
+        let mut included_scores = Vec::new();
+        let mut included_embeddings: Vec<Vec<f32>> = Vec::new();
+        let mut included_texts: Vec<String> = Vec::new();
+        let mut posts_dropped = 0;
+        let mut passages_used = 0;
+        let mut duplicates_skipped = 0;
+
+        for (post, score) in posts.into_iter().zip(scores) {
+            let (label, source) = match (&post.full_text, &post.summary, &post.description) {
+                (Some(full_text), _, _) if !full_text.trim().is_empty() => ("Excerpt", full_text.as_str()),
+                (_, Some(summary), _) if !summary.trim().is_empty() => ("Summary", summary.as_str()),
+                (_, _, Some(description)) if !description.trim().is_empty() => ("Description", description.as_str()),
+                _ => ("Summary", ""),
+            };
+
+            let mut passages: Vec<Passage> = split_passages(source)
+                .into_iter()
+                .take(mode.passages_considered())
+                .enumerate()
+                .map(|(index, text)| {
+                    let embedding = embed(&text);
+                    let score = match (&query_embedding, &embedding) {
+                        (Some(query), Some(passage)) => cosine_similarity(query, passage),
+                        // earlier passages first when they can't be scored
+                        _ => -(index as f32),
+                    };
+                    Passage {
+                        index,
+                        tokens: count_tokens(&text),
+                        text,
+                        score,
+                        embedding,
+                    }
+                })
+                .collect();
+            passages.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+
+            let number = included_posts.len() + 1;
+            let header = format!(
+                "[{}]\nTitle: {}\nCompany: {}\nPublished: {}\nLink: {}\n",
+                number, post.title, post.company, post.published_at, post.link
+            );
+            let header_tokens = count_tokens(&header) + 1;
+
+            let mut chosen: Vec<Passage> = Vec::new();
+            let mut post_tokens = header_tokens;
+            let mut skipped_here = 0;
+            for passage in passages {
+                if chosen.len() == mode.passages_per_post() {
+                    break;
+                }
+                let normalized = normalize(&passage.text);
+                let duplicate = included_texts.contains(&normalized)
+                    || chosen.iter().any(|c| normalize(&c.text) == normalized)
+                    || passage.embedding.as_ref().is_some_and(|embedding| {
+                        included_embeddings
+                            .iter()
+                            .chain(chosen.iter().filter_map(|c| c.embedding.as_ref()))
+                            .any(|other| cosine_similarity(embedding, other) >= DUPLICATE_SIMILARITY)
+                    });
+                if duplicate {
+                    skipped_here += 1;
+                    continue;
+                }
+                let passage_tokens = passage.tokens + count_tokens(label) + 3;
+                if used + post_tokens + passage_tokens > budget {
+                    continue;
+                }
+                post_tokens += passage_tokens;
+                chosen.push(passage);
+            }
+            duplicates_skipped += skipped_here;
+
+            // a post whose every passage repeats earlier content, or that can't fit, adds nothing
+            let nothing_new = chosen.is_empty() && !source.is_empty();
+            if nothing_new || used + post_tokens > budget {
+                posts_dropped += 1;
+                continue;
+            }
+
+            chosen.sort_by_key(|p| p.index);
+            text.push_str(&header);
+            for passage in &chosen {
+                text.push_str(&format!("{}: {}\n", label, passage.text));
+            }
+            text.push('\n');
+
+            used += post_tokens;
+            passages_used += chosen.len();
+            for passage in chosen {
+                included_texts.push(normalize(&passage.text));
+                included_embeddings.extend(passage.embedding);
+            }
+            included_posts.push(post);
+            included_scores.push(score);
+        }
+
+        let usage = ContextUsage {
+            tokens_used: count_tokens(&text),
+            token_budget: budget,
+            posts_used: included_posts.len(),
+            posts_dropped,
+            passages: passages_used,
+            duplicates_skipped,
+        };
+        debug!("Assembled context: {:?}", usage);
+
+        AssembledContext {
+            text,
+            posts: included_posts,
+            scores: included_scores,
+            usage,
+        }
+    }
+
+    /// Paragraphs of `text`, merged while small and split when longer than `PASSAGE_MAX_TOKENS`.
+    fn split_passages(text: &str) -> Vec<String> {
+        let mut passages = Vec::new();
+        let mut current = String::new();
+
+        for paragraph in text.split("\n\n").map(|p| p.split_whitespace().collect::<Vec<_>>().join(" ")) {
+            if paragraph.is_empty() {
+                continue;
+            }
+            let candidate = if current.is_empty() {
+                paragraph.clone()
+            } else {
+                format!("{} {}", current, paragraph)
+            };
+            if count_tokens(&candidate) <= PASSAGE_MAX_TOKENS {
+                current = candidate;
+                continue;
+            }
+            if !current.is_empty() {
+                passages.push(std::mem::take(&mut current));
+            }
+            if count_tokens(&paragraph) <= PASSAGE_MAX_TOKENS {
+                current = paragraph;
+            } else {
+                passages.extend(split_long(&paragraph));
+            }
+        }
+        if !current.is_empty() {
+            passages.push(current);
+        }
+        passages
+    }
+
+    /// Word windows of roughly `PASSAGE_MAX_TOKENS` tokens.
+    fn split_long(paragraph: &str) -> Vec<String> {
+        let mut chunks = Vec::new();
+        let mut current = String::new();
+        let mut current_tokens = 0;
+        for word in paragraph.split_whitespace() {
+            // counted per word (plus the space) so long texts aren't re-tokenized on every step
+            let word_tokens = count_tokens(word) + 1;
+            if !current.is_empty() && current_tokens + word_tokens > PASSAGE_MAX_TOKENS {
+                chunks.push(std::mem::take(&mut current));
+                current_tokens = 0;
+            }
+            if !current.is_empty() {
+                current.push(' ');
+            }
+            current.push_str(word);
+            current_tokens += word_tokens;
+        }
+        if !current.is_empty() {
+            chunks.push(current);
+        }
+        chunks
+    }
+
+    fn normalize(text: &str) -> String {
+        text.chars()
+            .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+            .collect::<String>()
+            .to_lowercase()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn post(id: i32, full_text: &str) -> Poast {
+            Poast {
+                id,
+                published_at: "2024-01-01".to_string(),
+                company: "Acme".to_string(),
+                title: format!("Post {}", id),
+                link: format!("https://example.com/{}", id),
+                description: None,
+                summary: None,
+                full_text: Some(full_text.to_string()),
+                links: None,
+                similarity: None,
+            }
+        }
+
+        fn words(prefix: &str, count: usize) -> String {
+            (0..count).map(|i| format!("{}{}", prefix, i)).collect::<Vec<_>>().join(" ")
+        }
+
+        fn assemble(posts: Vec<Poast>, function_results: &[String], budget: usize) -> AssembledContext {
+            let scores = vec![1.0; posts.len()];
+            assemble_with(None, |_| None, posts, scores, function_results, budget, ContextMode::Posts)
+        }
+
+        #[test]
+        fn test_split_passages_merges_short_paragraphs() {
+            let passages = split_passages("First  paragraph.\n\n\n\nSecond\nparagraph.\n\n   \n\nThird.");
+            assert_eq!(passages, vec!["First paragraph. Second paragraph. Third."]);
+            assert!(split_passages("").is_empty());
+            assert!(split_passages("\n\n  \n\n").is_empty());
+        }
+
+        #[test]
+        fn test_split_passages_keeps_large_paragraphs_apart() {
+            let first = words("alpha", 100);
+            let second = words("beta", 100);
+            let passages = split_passages(&format!("{}\n\n{}", first, second));
+            assert!(passages.len() >= 2);
+            assert!(passages.iter().all(|p| count_tokens(p) <= PASSAGE_MAX_TOKENS));
+            assert_eq!(passages.join(" "), format!("{} {}", first, second));
+        }
+
+        #[test]
+        fn test_split_long_windows_words() {
+            let paragraph = words("word", 600);
+            let chunks = split_long(&paragraph);
+            assert!(chunks.len() > 1);
+            assert!(chunks.iter().all(|c| !c.is_empty() && count_tokens(c) <= PASSAGE_MAX_TOKENS));
+            assert_eq!(chunks.join(" "), paragraph);
+
+            // a paragraph that is too long comes out of split_passages the same way
+            assert_eq!(split_passages(&paragraph), chunks);
+            assert!(split_long("").is_empty());
+        }
+
+        #[test]
+        fn test_truncate_to_tokens() {
+            assert_eq!(truncate_to_tokens("short text", 10), "short text");
+
+            let long = words("token", 200);
+            let truncated = truncate_to_tokens(&long, 20);
+            assert!(truncated.ends_with('…'));
+            assert!(count_tokens(&truncated) <= 20);
+            assert!(long.starts_with(truncated.trim_end_matches('…')));
+        }
+
+        #[test]
+        fn test_assemble_skips_duplicate_posts() {
+            let text = "The same announcement was syndicated to two blogs.";
+            let context = assemble(vec![post(1, text), post(2, &text.to_uppercase()), post(3, "Something new.")], &[], 6000);
+
+            let ids: Vec<i32> = context.posts.iter().map(|p| p.id).collect();
+            assert_eq!(ids, vec![1, 3]);
+            assert_eq!(context.usage.posts_dropped, 1);
+            assert_eq!(context.usage.duplicates_skipped, 1);
+            // surviving posts are renumbered so citations stay contiguous
+            assert!(context.text.contains("[2]\nTitle: Post 3"));
+        }
+
+        #[test]
+        fn test_assemble_skips_near_duplicates_by_embedding() {
+            // everything starting with the same word embeds to the same vector
+            let embed = |text: &str| {
+                let first = text.split_whitespace().next().unwrap_or_default();
+                Some(if first == "Rust" { vec![1.0, 0.0] } else { vec![0.0, 1.0] })
+            };
+            let posts = vec![post(1, "Rust 1.80 is out."), post(2, "Rust 1.80 has been released."), post(3, "Go 1.23 is out.")];
+
+            let context = assemble_with(None, embed, posts, vec![1.0; 3], &[], 6000, ContextMode::Posts);
+            let ids: Vec<i32> = context.posts.iter().map(|p| p.id).collect();
+            assert_eq!(ids, vec![1, 3]);
+            assert_eq!(context.usage.duplicates_skipped, 1);
+        }
+
+        #[test]
+        fn test_assemble_respects_budget() {
+            let posts: Vec<Poast> = (1..=10).map(|id| post(id, &words(&format!("p{}w", id), 150))).collect();
+            let budget = 600;
+            let context = assemble(posts, &[], budget);
+
+            assert!(!context.posts.is_empty());
+            assert!(context.usage.posts_dropped > 0);
+            assert_eq!(context.usage.posts_used + context.usage.posts_dropped, 10);
+            assert!(context.usage.tokens_used <= budget);
+        }
+
+        #[test]
+        fn test_assemble_caps_passages_per_post() {
+            let context = assemble(vec![post(1, &words("long", 2000))], &[], 6000);
+            assert_eq!(context.usage.passages, MAX_PASSAGES_PER_POST);
+            // without scores the opening passages are used, in reading order
+            assert!(context.text.contains("Excerpt: long0 long1"));
+        }
+
+        #[test]
+        fn test_assemble_single_post_fills_the_budget() {
+            let text = words("long", 3000);
+            let posts = assemble(vec![post(1, &text)], &[], 6000);
+            let single = assemble_with(None, |_| None, vec![post(1, &text)], vec![1.0], &[], 6000, ContextMode::SinglePost);
+
+            assert_eq!(posts.usage.passages, MAX_PASSAGES_PER_POST);
+            assert!(single.usage.passages > MAX_PASSAGES_CONSIDERED);
+            assert!(single.usage.tokens_used > 6000 * 3 / 4);
+            assert!(single.usage.tokens_used <= 6000);
+            assert_eq!(single.posts.len(), 1);
+        }
+
+        #[test]
+        fn test_assemble_limits_function_output() {
+            let results: Vec<String> = (0..20).map(|i| words(&format!("stat{}x", i), 300)).collect();
+            let context = assemble(Vec::new(), &results, 6000);
+
+            assert!(context.text.contains("(further tool output omitted)"));
+            let tool_section = context.text.split("Retrieved blog posts:").next().unwrap_or_default();
+            assert!(count_tokens(tool_section) <= 6000 / FUNCTION_RESULTS_SHARE + 20);
+        }
+    }
+}
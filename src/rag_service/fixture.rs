@@ -0,0 +1,192 @@
+/// In-memory stand-in for the `poasts` table, used by `rag_eval` so RAG runs are repeatable.
+pub mod fixture {
+    use anyhow::{Context, Result};
+    use futures_util::future::BoxFuture;
+    use serde::Deserialize;
+    use std::path::Path;
+
+    use crate::components::poasts::{Poast, PostFilter};
+    use crate::components::search::SearchType;
+    use crate::embedding_service::embeddings::embedding_text;
+    use crate::embeddings_service::embeddings_local::LocalEmbeddingService;
+    use crate::rag_service::source::source::{recent_since, top_buzzwords, CompanyStats, PostSource, SourceResult};
+    use crate::vector_search_service::vector_search::cosine_similarity;
+
+    #[derive(Debug, Clone, Deserialize)]
+    pub struct FixturePost {
+        #[serde(flatten)]
+        pub post: Poast,
+        #[serde(default)]
+        pub buzzwords: Vec<String>,
+    }
+
+    pub struct FixtureCorpus {
+        posts: Vec<FixturePost>,
+        embeddings: Vec<Vec<f32>>,
+    }
+
+    impl FixtureCorpus {
+        /// Read a JSON array of posts and embed them with the local MiniLM model.
+        pub fn load(path: &Path) -> Result<Self> {
+            let body = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read corpus {}", path.display()))?;
+            let posts: Vec<FixturePost> = serde_json::from_str(&body)
+                .with_context(|| format!("Failed to parse corpus {}", path.display()))?;
+
+            LocalEmbeddingService::init()?;
+            let service = LocalEmbeddingService::get_instance()?;
+            let embeddings = posts
+                .iter()
+                .map(|p| service.generate_embedding(&embedding_text(&p.post)))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(Self { posts, embeddings })
+        }
+
+        pub fn len(&self) -> usize {
+            self.posts.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.posts.is_empty()
+        }
+
+        /// Same filters `get_poasts` applies, newest first.
+        pub fn filter(&self, filter: &PostFilter) -> Vec<Poast> {
+            let mut posts: Vec<Poast> = self
+                .posts
+                .iter()
+                .filter(|p| Self::matches(p, filter))
+                .map(|p| p.post.clone())
+                .collect();
+            posts.sort_by(|a, b| b.published_at.cmp(&a.published_at));
+            posts.truncate(filter.page_size());
+            posts
+        }
+
+        /// Posts passing `filter` (its search term excluded), most similar to `query` first.
+        pub fn semantic(&self, query: &str, filter: &PostFilter) -> Result<Vec<Poast>> {
+            let query_vector = LocalEmbeddingService::get_instance()?.generate_embedding(query)?;
+            let unfiltered_term = PostFilter {
+                search_term: None,
+                ..filter.clone()
+            };
+
+            let mut scored: Vec<(f32, &FixturePost)> = self
+                .posts
+                .iter()
+                .zip(&self.embeddings)
+                .filter(|(p, _)| Self::matches(p, &unfiltered_term))
+                .map(|(p, embedding)| (cosine_similarity(&query_vector, embedding), p))
+                .collect();
+            scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+
+            let min_similarity = filter.min_similarity.unwrap_or(0);
+            Ok(scored
+                .into_iter()
+                .map(|(score, p)| Poast {
+                    similarity: Some((score * 100.0).round() as i32),
+                    ..p.post.clone()
+                })
+                .filter(|p| p.similarity.unwrap_or(0) >= min_similarity)
+                .take(filter.page_size())
+                .collect())
+        }
+
+        pub fn post(&self, id: i32) -> Option<Poast> {
+            self.posts.iter().find(|p| p.post.id == id).map(|p| p.post.clone())
+        }
+
+        /// Every post from `company` with its buzzwords, newest first.
+        pub fn company_posts(&self, company: &str) -> Vec<&FixturePost> {
+            let mut posts: Vec<&FixturePost> = self
+                .posts
+                .iter()
+                .filter(|p| p.post.company.eq_ignore_ascii_case(company))
+                .collect();
+            posts.sort_by(|a, b| b.post.published_at.cmp(&a.post.published_at));
+            posts
+        }
+
+        fn matches(post: &FixturePost, filter: &PostFilter) -> bool {
+            let companies = filter.company_set();
+            if !companies.is_empty() && !companies.iter().any(|c| c.eq_ignore_ascii_case(&post.post.company)) {
+                return false;
+            }
+            if let Some(term) = filter.search_term() {
+                let term = term.to_lowercase();
+                let in_summary = post.post.summary.as_deref().unwrap_or("").to_lowercase().contains(&term);
+                if !post.post.title.to_lowercase().contains(&term) && !in_summary {
+                    return false;
+                }
+            }
+            if let Some(after) = filter.published_after.as_deref().filter(|d| !d.trim().is_empty()) {
+                if post.post.published_at.as_str() < after.trim() {
+                    return false;
+                }
+            }
+            if let Some(before) = filter.published_before.as_deref().filter(|d| !d.trim().is_empty()) {
+                if post.post.published_at.as_str() >= before.trim() {
+                    return false;
+                }
+            }
+            if let Some(tag) = filter.tag.as_deref().filter(|t| !t.trim().is_empty()) {
+                if !post.buzzwords.iter().any(|b| b.eq_ignore_ascii_case(tag.trim())) {
+                    return false;
+                }
+            }
+            true
+        }
+    }
+
+    /// Every search type is the corpus' own MiniLM similarity.
+    impl PostSource for FixtureCorpus {
+        fn posts(&self, filter: PostFilter) -> BoxFuture<'_, SourceResult<Vec<Poast>>> {
+            Box::pin(async move { Ok(self.filter(&filter)) })
+        }
+
+        fn search(&self, filter: PostFilter) -> BoxFuture<'_, SourceResult<Vec<Poast>>> {
+            Box::pin(async move {
+                match filter.search_term() {
+                    Some(term) => Ok(self.semantic(term, &filter)?),
+                    None => Ok(self.filter(&filter)),
+                }
+            })
+        }
+
+        fn semantic(&self, query: String, _search_type: SearchType) -> BoxFuture<'_, SourceResult<Vec<Poast>>> {
+            Box::pin(async move { Ok(FixtureCorpus::semantic(self, &query, &PostFilter::default())?) })
+        }
+
+        fn post(&self, id: i32) -> BoxFuture<'_, SourceResult<Option<Poast>>> {
+            Box::pin(async move { Ok(FixtureCorpus::post(self, id)) })
+        }
+
+        fn company_stats<'a>(&'a self, company: &'a str) -> BoxFuture<'a, SourceResult<CompanyStats>> {
+            Box::pin(async move {
+                let recent_since = recent_since();
+                let posts = self.company_posts(company);
+                Ok(CompanyStats {
+                    company: company.to_string(),
+                    post_count: posts.len(),
+                    recent_count: posts.iter().filter(|p| p.post.published_at >= recent_since).count(),
+                    first_published: posts.last().map(|p| p.post.published_at.clone()),
+                    latest_published: posts.first().map(|p| p.post.published_at.clone()),
+                    top_buzzwords: top_buzzwords(posts.iter().flat_map(|p| p.buzzwords.iter().cloned())),
+                })
+            })
+        }
+
+        fn minilm_vectors<'a>(&'a self, links: &'a [String]) -> BoxFuture<'a, SourceResult<Vec<(String, Vec<f32>)>>> {
+            Box::pin(async move {
+                Ok(self
+                    .posts
+                    .iter()
+                    .zip(&self.embeddings)
+                    .filter(|(p, _)| links.contains(&p.post.link))
+                    .map(|(p, embedding)| (p.post.link.clone(), embedding.clone()))
+                    .collect())
+            })
+        }
+    }
+}
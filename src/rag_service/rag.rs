@@ -13,9 +13,10 @@ pub mod rag {
     use anyhow::Result;
     use anyhow::anyhow;
 
+    use crate::components::poasts::Poast;
     use crate::components::search::SearchType;
 
-    pub use crate::rag_service::rag::rag::enhanced_rag::enhanced_rag::EnhancedRagService;
+    pub use crate::rag_service::rag::rag::enhanced_rag::enhanced_rag::{citation_markers, EnhancedRagService};
 
     #[derive(Debug, Serialize, Deserialize, Clone)]
     pub struct RagMessage {
@@ -34,11 +35,40 @@ pub mod rag {
         pub relevance_score: f32,
     }
 
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct UnsupportedClaim {
+        pub sentence: String,
+        pub reason: String,
+    }
+
+    /// Result of checking an answer's sentences against the posts it was generated from.
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct AnswerVerification {
+        pub checked: usize,
+        pub unsupported: Vec<UnsupportedClaim>,
+    }
+
+    /// How much of the context token budget an answer's sources took.
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct ContextUsage {
+        pub tokens_used: usize,
+        pub token_budget: usize,
+        pub posts_used: usize,
+        /// Retrieved posts left out because they didn't fit or only repeated other content.
+        pub posts_dropped: usize,
+        pub passages: usize,
+        pub duplicates_skipped: usize,
+    }
+
     #[derive(Debug, Serialize, Deserialize, Clone)]
     pub struct RagResponse {
-        pub message_type: String, // "content", "citation", "error", "done", "status"
+        pub message_type: String, // "content", "citation", "error", "done", "status", "session", "verification", "context_usage"
         pub content: Option<String>,
         pub citations: Option<Vec<Citation>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub verification: Option<AnswerVerification>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub context_usage: Option<ContextUsage>,
     }
 
     pub struct RagService {
@@ -61,14 +91,27 @@ pub mod rag {
             }
         }
 
+        /// Answer `query` given the conversation so far. Returns the assistant message that was
+        /// streamed, or `None` if no answer was generated.
         pub async fn process_query(
             &self,
             query: String,
             search_type: SearchType,
+            history: Vec<RagMessage>,
             tx: mpsc::Sender<Result<Event, Infallible>>,
-        ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        ) -> Result<Option<RagMessage>, Box<dyn std::error::Error + Send + Sync>> {
             let enhanced_service = EnhancedRagService::new();
-            enhanced_service.process_query(query, search_type, tx).await
+            enhanced_service.process_query(query, search_type, &history, tx).await
+        }
+
+        /// Answer `query` from `post` alone, for the question box on a post's page.
+        pub async fn process_post_query(
+            &self,
+            post: Poast,
+            query: String,
+            tx: mpsc::Sender<Result<Event, Infallible>>,
+        ) -> Result<Option<RagMessage>, Box<dyn std::error::Error + Send + Sync>> {
+            EnhancedRagService::new().process_post_query(post, query, &[], tx).await
         }
     }
 
@@ -102,8 +145,10 @@ pub mod rag {
             use async_openai::{
                 config::OpenAIConfig,
                 types::{
+                    ResponseFormat,
                     ChatCompletionRequestMessage, ChatCompletionRequestSystemMessage,
-                    ChatCompletionRequestUserMessage, CreateChatCompletionRequest,
+                    ChatCompletionRequestUserMessage, ChatCompletionRequestAssistantMessage,
+                    ChatCompletionRequestToolMessage, CreateChatCompletionRequest,
                     ChatCompletionTool, ChatCompletionToolType, FunctionObject,
                     ChatCompletionMessageToolCall, ChatCompletionToolChoiceOption,
                 },
@@ -118,11 +163,40 @@ pub mod rag {
             use tokio::sync::mpsc;
             use anyhow::Result;
         
-            use crate::components::poasts::{semantic_search, get_poasts, PostFilter, Poast};
+            use crate::components::poasts::{PostFilter, Poast};
             use crate::rag_service::rag::rag::RagResponse;
             use crate::components::search::SearchType;
             use crate::rag_service::rag::rag::Citation;
-        
+            use crate::rag_service::rag::rag::RagMessage;
+            use crate::rag_service::rag::rag::{AnswerVerification, UnsupportedClaim};
+            use crate::embedding_service::embeddings::embedding_text;
+            use crate::embeddings_service::embeddings_local::{EmbeddingError, LocalEmbeddingService};
+            use crate::vector_search_service::vector_search::cosine_similarity;
+            use crate::rag_service::source::source::{CompanyStats, PostSource, SupabasePosts};
+            use crate::rag_service::context::context::{assemble_context, AssembledContext, ContextMode, CONTEXT_TOKEN_BUDGET};
+            use std::collections::{HashMap, HashSet};
+            use std::sync::Arc;
+
+            /// Prior turns sent to the model; older ones are dropped.
+            const MAX_HISTORY_MESSAGES: usize = 10;
+            /// Earlier answers are clipped to this many characters when replayed.
+            const MAX_HISTORY_MESSAGE_CHARS: usize = 2000;
+            /// Tool-calling rounds before the answer is generated with whatever was gathered.
+            const MAX_TOOL_ROUNDS: usize = 3;
+            /// Tools whose answer is their text output rather than a list of posts.
+            const TEXT_RESULT_TOOLS: [&str; 2] = ["get_company_stats", "compare_companies"];
+            /// A post's full text is clipped to this many characters for deep reading.
+            const FULL_TEXT_MAX_CHARS: usize = 12000;
+            /// Sentences shorter than this many words (headings, "Here's what I found:") aren't verified.
+            const MIN_VERIFIED_SENTENCE_WORDS: usize = 4;
+            /// Tool output (stats, full texts) given to the verifier is clipped to this many characters.
+            const VERIFIER_TOOL_OUTPUT_CHARS: usize = 6000;
+            /// Posts whose MiniLM similarity to the question is below this are left out of the context...
+            const MIN_RELEVANCE: f32 = 0.2;
+            /// ...except for the best few, so broad questions ("latest posts from X") still get an answer.
+            const MIN_KEPT_POSTS: usize = 3;
+            const MAX_CONTEXT_POSTS: usize = 20;
+
             #[derive(Debug, Serialize, Deserialize)]
             pub struct GetLatestPostsArgs {
                 pub company: Option<String>,
@@ -145,13 +219,52 @@ pub mod rag {
             #[derive(Debug, Serialize, Deserialize)]
             pub struct SemanticSearchArgs {
                 pub query: String,
-                pub search_type: Option<String>, // "openai" or "local"
+                pub search_type: Option<String>, // "hybrid", "openai" or "local"
                 pub limit: Option<u32>,
             }
-        
+
+            #[derive(Debug, Serialize, Deserialize)]
+            pub struct DateRangeArgs {
+                pub start_date: String, // YYYY-MM-DD, inclusive
+                pub end_date: String,   // YYYY-MM-DD, inclusive
+                pub company: Option<String>,
+                pub limit: Option<u32>,
+            }
+
+            #[derive(Debug, Serialize, Deserialize)]
+            pub struct BuzzwordArgs {
+                pub buzzword: String,
+                pub company: Option<String>,
+                pub limit: Option<u32>,
+            }
+
+            #[derive(Debug, Serialize, Deserialize)]
+            pub struct CompanyStatsArgs {
+                pub companies: Vec<String>,
+            }
+
+            #[derive(Debug, Serialize, Deserialize)]
+            pub struct PostFullTextArgs {
+                pub post_id: i32,
+            }
+
+            #[derive(Debug, Serialize, Deserialize)]
+            pub struct CompareCompaniesArgs {
+                pub company_a: String,
+                pub company_b: String,
+                pub topic: Option<String>,
+                pub limit: Option<u32>,
+            }
+
+            fn posts_limit(limit: Option<u32>) -> usize {
+                limit.unwrap_or(10).clamp(1, 20) as usize
+            }
+
             pub struct EnhancedRagService {
                 client: Client<OpenAIConfig>,
                 model: String,
+                /// Supabase, or a fixture corpus in `rag_eval`.
+                posts: Arc<dyn PostSource>,
             }
 
             impl Default for EnhancedRagService {
@@ -166,15 +279,31 @@ pub mod rag {
                     Self {
                         client,
                         model: "gpt-4o-mini".to_string(), // Use a model that supports function calling
+                        posts: Arc::new(SupabasePosts),
+                    }
+                }
+
+                /// Use another OpenAI-compatible endpoint and model, e.g. a local server.
+                pub fn with_client(client: Client<OpenAIConfig>, model: String) -> Self {
+                    Self {
+                        client,
+                        model,
+                        posts: Arc::new(SupabasePosts),
                     }
                 }
+
+                pub fn with_posts(mut self, posts: Arc<dyn PostSource>) -> Self {
+                    self.posts = posts;
+                    self
+                }
         
                 pub async fn process_query(
                     &self,
                     query: String,
                     search_type: SearchType,
+                    history: &[RagMessage],
                     tx: mpsc::Sender<Result<Event, Infallible>>,
-                ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+                ) -> Result<Option<RagMessage>, Box<dyn std::error::Error + Send + Sync>> {
                     info!("Processing enhanced RAG query: {}", query);
         
                     // Step 1: Send initial status
@@ -186,51 +315,113 @@ pub mod rag {
 
                     debug!("available tools: {:?}", tools);
         
-                    // Step 3: Let OpenAI decide which function(s) to call
-                    let function_calls = self.determine_functions(&query, tools.clone()).await?;
+                    // Step 3/4: Let OpenAI call functions, feeding results back until it stops
+                    // asking for more or the round limit is reached
+                    let mut messages = self.tool_selection_messages(&query, history);
+                    let mut all_posts = Vec::new();
+                    let mut function_results = Vec::new();
+                    // stats and comparisons can answer a question without retrieving any posts
+                    let mut has_text_results = false;
+                    // posts the model asked to read in full are kept whatever their score
+                    let mut pinned = HashSet::new();
 
-                    debug!("function calls tools: {:?}", tools);
-        
-                    if function_calls.is_empty() {
+                    for round in 0..MAX_TOOL_ROUNDS {
+                        let function_calls = self.determine_functions(messages.clone(), tools.clone()).await?;
+                        debug!("round {} function calls: {:?}", round + 1, function_calls);
+
+                        if function_calls.is_empty() {
+                            break;
+                        }
+
+                        messages.push(ChatCompletionRequestMessage::Assistant(ChatCompletionRequestAssistantMessage {
+                            tool_calls: Some(function_calls.clone()),
+                            ..Default::default()
+                        }));
+
+                        for tool_call in function_calls {
+                            self.send_status(&tx, &format!("Executing: {}", tool_call.function.name)).await?;
+
+                            // errors go back to the model so it can retry with other arguments
+                            let (posts, result_description) = match self.execute_function_call(&tool_call, search_type).await {
+                                Ok(result) => {
+                                    has_text_results |= TEXT_RESULT_TOOLS.contains(&tool_call.function.name.as_str());
+                                    result
+                                }
+                                Err(e) => {
+                                    error!("Function {} failed: {}", tool_call.function.name, e);
+                                    (Vec::new(), format!("{} failed: {}", tool_call.function.name, e))
+                                }
+                            };
+
+                            messages.push(ChatCompletionRequestMessage::Tool(ChatCompletionRequestToolMessage {
+                                content: Self::tool_result_content(&posts, &result_description).into(),
+                                tool_call_id: tool_call.id.clone(),
+                            }));
+                            if tool_call.function.name == "get_post_full_text" {
+                                pinned.extend(posts.iter().map(|post| post.id));
+                            }
+                            all_posts.extend(posts);
+                            function_results.push(result_description);
+                        }
+                    }
+
+                    if function_results.is_empty() {
                         // Fallback to semantic search if no functions are called
                         self.send_status(&tx, "Performing semantic search...").await?;
-                        return self.fallback_semantic_search(query, search_type, tx).await;
-                    }
-        
-                    // Step 4: Execute the function calls
-                    let mut all_posts = Vec::new();
-                    let mut function_results = Vec::new();
-        
-                    for tool_call in function_calls {
-                        self.send_status(&tx, &format!("Executing: {}", tool_call.function.name)).await?;
-                        
-                        let (posts, result_description) = self.execute_function_call(&tool_call, search_type).await?;
-                        all_posts.extend(posts);
-                        function_results.push(result_description);
+                        return self.fallback_semantic_search(query, search_type, history, tx).await;
                     }
-        
-                    // Step 5: Remove duplicates and limit results
-                    all_posts.sort_by(|a, b| b.published_at.cmp(&a.published_at));
-                    all_posts.dedup_by(|a, b| a.id == b.id);
-                    all_posts.truncate(20); // Limit to 20 total posts
-        
-                    if all_posts.is_empty() {
+
+                    if all_posts.is_empty() && !has_text_results {
                         self.send_error(&tx, "No posts found matching your criteria.").await?;
-                        return Ok(());
+                        return Ok(None);
                     }
+
+                    // Step 5: Score posts against the question, drop weak matches and limit results
+                    let (all_posts, scores) = if all_posts.is_empty() {
+                        (Vec::new(), Vec::new())
+                    } else {
+                        self.send_status(&tx, "Ranking sources...").await?;
+                        self.rerank(&query, all_posts, &pinned).await
+                    };
+
+                    // Step 6: Fit the best passages into the token budget
+                    let context = self.build_context(&query, all_posts, scores, &function_results, ContextMode::Posts, &tx).await?;
+
+                    // Step 7: Send citations for the posts that made it into the context
+                    let citations = self.create_citations(&context.posts, &context.scores);
+                    self.send_citations(&tx, citations.clone()).await?;
         
-                    // Step 6: Send citations
-                    let citations = self.create_citations(&all_posts);
-                    self.send_citations(&tx, citations).await?;
-        
-                    // Step 7: Generate contextual response
+                    // Step 8: Generate contextual response
                     self.send_status(&tx, "Generating response...").await?;
-                    let context = self.create_context(&all_posts, &function_results);
-                    self.generate_streaming_response(query, context, tx).await?;
-        
-                    Ok(())
+                    let answer = self.generate_streaming_response(query, context.text, history, &tx).await?;
+
+                    // Step 9: Check the answer against its sources
+                    self.finish_answer(answer, &context.posts, &function_results, citations, &tx).await.map(Some)
                 }
         
+                /// Answer `query` using only one post's text: no tools, no retrieval.
+                pub async fn process_post_query(
+                    &self,
+                    post: Poast,
+                    query: String,
+                    history: &[RagMessage],
+                    tx: mpsc::Sender<Result<Event, Infallible>>,
+                ) -> Result<Option<RagMessage>, Box<dyn std::error::Error + Send + Sync>> {
+                    info!("Processing RAG query about post {}: {}", post.id, query);
+
+                    self.send_status(&tx, "Reading the post...").await?;
+                    // the one post gets the whole budget, not the few passages a post gets among many
+                    let context = self.build_context(&query, vec![post], vec![1.0], &[], ContextMode::SinglePost, &tx).await?;
+
+                    let citations = self.create_citations(&context.posts, &context.scores);
+                    self.send_citations(&tx, citations.clone()).await?;
+
+                    self.send_status(&tx, "Generating response...").await?;
+                    let answer = self.generate_streaming_response(query, context.text, history, &tx).await?;
+
+                    self.finish_answer(answer, &context.posts, &[], citations, &tx).await.map(Some)
+                }
+
                 fn get_function_definitions(&self) -> Vec<ChatCompletionTool> {
                     vec![
                         ChatCompletionTool {
@@ -311,7 +502,7 @@ pub mod rag {
                             r#type: ChatCompletionToolType::Function,
                             function: FunctionObject {
                                 name: "semantic_search_posts".to_string(),
-                                description: Some("Perform semantic search across all posts to find content similar to the query. Use this for conceptual or thematic searches.".to_string()),
+                                description: Some("Search across all posts for content matching the query. Combines keyword and semantic ranking by default, so it works for exact product names and acronyms as well as conceptual or thematic searches.".to_string()),
                                 parameters: Some(json!({
                                     "type": "object",
                                     "properties": {
@@ -321,8 +512,8 @@ pub mod rag {
                                         },
                                         "search_type": {
                                             "type": "string",
-                                            "enum": ["openai", "local"],
-                                            "description": "Type of semantic search to use"
+                                            "enum": ["hybrid", "openai", "local"],
+                                            "description": "Ranking to use (default: hybrid). Use openai or local for purely semantic matching"
                                         },
                                         "limit": {
                                             "type": "integer",
@@ -336,19 +527,154 @@ pub mod rag {
                                 strict: None,
                             },
                         },
+                        ChatCompletionTool {
+                            r#type: ChatCompletionToolType::Function,
+                            function: FunctionObject {
+                                name: "get_posts_in_date_range".to_string(),
+                                description: Some("Get posts published between two dates, optionally from one company. Use this for questions like 'what did X publish in March 2024' or 'posts from last quarter'.".to_string()),
+                                parameters: Some(json!({
+                                    "type": "object",
+                                    "properties": {
+                                        "start_date": {
+                                            "type": "string",
+                                            "description": "First day of the range, YYYY-MM-DD (inclusive)"
+                                        },
+                                        "end_date": {
+                                            "type": "string",
+                                            "description": "Last day of the range, YYYY-MM-DD (inclusive)"
+                                        },
+                                        "company": {
+                                            "type": "string",
+                                            "description": "Filter posts by this company name (optional)"
+                                        },
+                                        "limit": {
+                                            "type": "integer",
+                                            "description": "Number of posts to return (default: 10, max: 20)",
+                                            "minimum": 1,
+                                            "maximum": 20
+                                        }
+                                    },
+                                    "required": ["start_date", "end_date"]
+                                })),
+                                strict: None,
+                            },
+                        },
+                        ChatCompletionTool {
+                            r#type: ChatCompletionToolType::Function,
+                            function: FunctionObject {
+                                name: "find_posts_by_buzzword".to_string(),
+                                description: Some("Get posts tagged with a buzzword/topic tag such as 'kubernetes' or 'observability', optionally from one company.".to_string()),
+                                parameters: Some(json!({
+                                    "type": "object",
+                                    "properties": {
+                                        "buzzword": {
+                                            "type": "string",
+                                            "description": "The tag to look up"
+                                        },
+                                        "company": {
+                                            "type": "string",
+                                            "description": "Filter posts by this company name (optional)"
+                                        },
+                                        "limit": {
+                                            "type": "integer",
+                                            "description": "Number of posts to return (default: 10, max: 20)",
+                                            "minimum": 1,
+                                            "maximum": 20
+                                        }
+                                    },
+                                    "required": ["buzzword"]
+                                })),
+                                strict: None,
+                            },
+                        },
+                        ChatCompletionTool {
+                            r#type: ChatCompletionToolType::Function,
+                            function: FunctionObject {
+                                name: "get_company_stats".to_string(),
+                                description: Some("Get post counts, recent activity, first/latest post dates and most common topics for one or more companies. Use this for 'how much does X write', 'how active is X' or 'what does X usually write about'.".to_string()),
+                                parameters: Some(json!({
+                                    "type": "object",
+                                    "properties": {
+                                        "companies": {
+                                            "type": "array",
+                                            "items": { "type": "string" },
+                                            "description": "Company names (max 5)",
+                                            "maxItems": 5
+                                        }
+                                    },
+                                    "required": ["companies"]
+                                })),
+                                strict: None,
+                            },
+                        },
+                        ChatCompletionTool {
+                            r#type: ChatCompletionToolType::Function,
+                            function: FunctionObject {
+                                name: "get_post_full_text".to_string(),
+                                description: Some("Get the full text of one post by its id, for questions that need details beyond the summary. Post ids appear in the results of the other functions.".to_string()),
+                                parameters: Some(json!({
+                                    "type": "object",
+                                    "properties": {
+                                        "post_id": {
+                                            "type": "integer",
+                                            "description": "The id of the post"
+                                        }
+                                    },
+                                    "required": ["post_id"]
+                                })),
+                                strict: None,
+                            },
+                        },
+                        ChatCompletionTool {
+                            r#type: ChatCompletionToolType::Function,
+                            function: FunctionObject {
+                                name: "compare_companies".to_string(),
+                                description: Some("Compare two companies: their posting stats plus their most relevant posts on an optional topic (or their latest posts without one).".to_string()),
+                                parameters: Some(json!({
+                                    "type": "object",
+                                    "properties": {
+                                        "company_a": {
+                                            "type": "string",
+                                            "description": "The first company name"
+                                        },
+                                        "company_b": {
+                                            "type": "string",
+                                            "description": "The second company name"
+                                        },
+                                        "topic": {
+                                            "type": "string",
+                                            "description": "Optional topic to compare their posts on"
+                                        },
+                                        "limit": {
+                                            "type": "integer",
+                                            "description": "Posts per company (default: 5, max: 10)",
+                                            "minimum": 1,
+                                            "maximum": 10
+                                        }
+                                    },
+                                    "required": ["company_a", "company_b"]
+                                })),
+                                strict: None,
+                            },
+                        },
                     ]
                 }
-        
-                async fn determine_functions(
-                    &self,
-                    query: &str,
-                    tools: Vec<ChatCompletionTool>,
-                ) -> Result<Vec<ChatCompletionMessageToolCall>, Box<dyn std::error::Error + Send + Sync>> {
+
+                fn tool_selection_messages(&self, query: &str, history: &[RagMessage]) -> Vec<ChatCompletionRequestMessage> {
                     let system_message = ChatCompletionRequestSystemMessage {
-                        content: "You are a helpful assistant that helps users blog posts. \
-                        Analyze the user's query and determine which function(s) to call to best answer their question. \
-                        You can call multiple functions if needed (e.g., to compare latest posts from different companies). \
-                        Always try to call at least one function to retrieve posts.".into(),
+                        content: format!(
+                            "You are a helpful assistant that helps users blog posts. \
+                            Analyze the user's query and determine which function(s) to call to best answer their question. \
+                            You can call multiple functions if needed (e.g., to compare latest posts from different companies). \
+                            Always try to call at least one function to retrieve posts. \
+                            You will see each function's results and can call more functions in up to {} rounds, \
+                            for example to read the full text of a post you found. Stop calling functions once you have \
+                            enough to answer. Today is {}. \
+                            The latest message may be a follow-up: use the earlier conversation to resolve references \
+                            like 'they', 'that company' or 'their earlier posts' into concrete arguments.",
+                            MAX_TOOL_ROUNDS,
+                            chrono::Utc::now().format("%Y-%m-%d")
+                        ).into(),
                         name: None,
                     };
         
@@ -357,12 +683,32 @@ pub mod rag {
                         name: None,
                     };
         
+                    let mut messages = vec![ChatCompletionRequestMessage::System(system_message)];
+                    messages.extend(Self::history_messages(history));
+                    messages.push(ChatCompletionRequestMessage::User(user_message));
+                    messages
+                }
+
+                /// What the model sees for one function call: the description plus the posts with ids.
+                fn tool_result_content(posts: &[Poast], description: &str) -> String {
+                    let mut content = description.to_string();
+                    for post in posts {
+                        content.push_str(&format!(
+                            "\n- [id {}] {} - {} ({})",
+                            post.id, post.company, post.title, post.published_at
+                        ));
+                    }
+                    content
+                }
+        
+                async fn determine_functions(
+                    &self,
+                    messages: Vec<ChatCompletionRequestMessage>,
+                    tools: Vec<ChatCompletionTool>,
+                ) -> Result<Vec<ChatCompletionMessageToolCall>, Box<dyn std::error::Error + Send + Sync>> {
                     let request = CreateChatCompletionRequest {
                         model: self.model.clone(),
-                        messages: vec![
-                            ChatCompletionRequestMessage::System(system_message),
-                            ChatCompletionRequestMessage::User(user_message),
-                        ],
+                        messages,
                         tools: Some(tools),
                         tool_choice: Some(ChatCompletionToolChoiceOption::Auto),
                         temperature: Some(0.1),
@@ -415,11 +761,12 @@ pub mod rag {
                         }
                         "semantic_search_posts" => {
                             let args: SemanticSearchArgs = serde_json::from_str(&tool_call.function.arguments)?;
-                            let search_type = match args.search_type.as_deref().unwrap_or("openai") {
+                            let search_type = match args.search_type.as_deref().unwrap_or("hybrid") {
                                 "local" => SearchType::LocalSemantic,
-                                _ => SearchType::OpenAISemantic,
+                                "openai" => SearchType::OpenAISemantic,
+                                _ => SearchType::Hybrid,
                             };
-                            let mut posts = semantic_search(args.query.clone(), search_type).await
+                            let mut posts = self.semantic_posts(args.query.clone(), search_type).await
                                 .map_err(|e| format!("Semantic search failed: {}", e))?;
                             if let Some(limit) = args.limit {
                                 posts.truncate(limit as usize);
@@ -427,6 +774,66 @@ pub mod rag {
                             let description = format!("Found {} posts semantically similar to '{}'", posts.len(), args.query);
                             Ok((posts, description))
                         }
+                        "get_posts_in_date_range" => {
+                            let args: DateRangeArgs = serde_json::from_str(&tool_call.function.arguments)?;
+                            let posts = self.get_posts_in_date_range(&args).await?;
+                            let company = args.company.map(|c| format!(" from {}", c)).unwrap_or_default();
+                            let description = format!(
+                                "Found {} posts{} published between {} and {}",
+                                posts.len(), company, args.start_date, args.end_date
+                            );
+                            Ok((posts, description))
+                        }
+                        "find_posts_by_buzzword" => {
+                            let args: BuzzwordArgs = serde_json::from_str(&tool_call.function.arguments)?;
+                            let filter = PostFilter {
+                                company: args.company.clone(),
+                                tag: Some(args.buzzword.clone()),
+                                limit: Some(posts_limit(args.limit)),
+                                ..Default::default()
+                            };
+                            let posts = self.fetch_posts(filter).await
+                                .map_err(|e| format!("Failed to get posts by buzzword: {}", e))?;
+                            let company = args.company.map(|c| format!(" from {}", c)).unwrap_or_default();
+                            let description = format!("Found {} posts{} tagged '{}'", posts.len(), company, args.buzzword);
+                            Ok((posts, description))
+                        }
+                        "get_company_stats" => {
+                            let args: CompanyStatsArgs = serde_json::from_str(&tool_call.function.arguments)?;
+                            let mut lines = Vec::new();
+                            for company in args.companies.iter().take(5) {
+                                lines.push(self.company_stats(company).await?.describe());
+                            }
+                            Ok((Vec::new(), format!("Company stats:\n{}", lines.join("\n"))))
+                        }
+                        "get_post_full_text" => {
+                            let args: PostFullTextArgs = serde_json::from_str(&tool_call.function.arguments)?;
+                            let post = self.get_post_with_full_text(args.post_id).await?;
+                            let full_text = post.full_text.as_deref().unwrap_or("(no full text stored for this post)");
+                            let clipped: String = full_text.chars().take(FULL_TEXT_MAX_CHARS).collect();
+                            let description = format!(
+                                "Full text of post {} ({} - {}):\n{}",
+                                post.id, post.company, post.title, clipped
+                            );
+                            Ok((vec![post], description))
+                        }
+                        "compare_companies" => {
+                            let args: CompareCompaniesArgs = serde_json::from_str(&tool_call.function.arguments)?;
+                            let limit = args.limit.unwrap_or(5).clamp(1, 10) as usize;
+                            let mut posts = Vec::new();
+                            let mut lines = Vec::new();
+                            for company in [&args.company_a, &args.company_b] {
+                                let company_posts = self.company_posts_on_topic(company, args.topic.as_deref(), limit).await?;
+                                lines.push(format!(
+                                    "{} ({} posts retrieved{})",
+                                    self.company_stats(company).await?.describe(),
+                                    company_posts.len(),
+                                    args.topic.as_ref().map(|t| format!(" about '{}'", t)).unwrap_or_default()
+                                ));
+                                posts.extend(company_posts);
+                            }
+                            Ok((posts, format!("Comparison of {} and {}:\n{}", args.company_a, args.company_b, lines.join("\n"))))
+                        }
                         _ => Err(format!("Unknown function: {}", tool_call.function.name).into()),
                     }
                 }
@@ -439,9 +846,10 @@ pub mod rag {
                     let filter = PostFilter {
                         search_term: None,
                         company,
+                        ..Default::default()
                     };
                     
-                    let mut posts = get_poasts(Some(filter)).await
+                    let mut posts = self.fetch_posts(filter).await
                         .map_err(|e| format!("Failed to get latest posts: {}", e))?;
                     
                     // Sort by published_at descending (newest first)
@@ -464,9 +872,10 @@ pub mod rag {
                     let filter = PostFilter {
                         search_term: None,
                         company,
+                        ..Default::default()
                     };
                     
-                    let mut posts = get_poasts(Some(filter)).await
+                    let mut posts = self.fetch_posts(filter).await
                         .map_err(|e| format!("Failed to get earliest posts: {}", e))?;
                     
                     // Sort by published_at ascending (oldest first)
@@ -490,9 +899,10 @@ pub mod rag {
                     let filter = PostFilter {
                         search_term: search_term.map(|s| s.to_string()),
                         company: Some(company.to_string()),
+                        ..Default::default()
                     };
                     
-                    let mut posts = get_poasts(Some(filter)).await
+                    let mut posts = self.fetch_posts(filter).await
                         .map_err(|e| format!("Failed to search posts by company: {}", e))?;
                     
                     // Sort by published_at descending (newest first)
@@ -507,68 +917,407 @@ pub mod rag {
                     Ok(posts)
                 }
         
-                fn create_citations(&self, posts: &[Poast]) -> Vec<Citation> {
-                    posts
+                async fn get_posts_in_date_range(
+                    &self,
+                    args: &DateRangeArgs,
+                ) -> Result<Vec<Poast>, Box<dyn std::error::Error + Send + Sync>> {
+                    let start = chrono::NaiveDate::parse_from_str(args.start_date.trim(), "%Y-%m-%d")
+                        .map_err(|_| format!("Invalid start_date '{}', expected YYYY-MM-DD", args.start_date))?;
+                    let end = chrono::NaiveDate::parse_from_str(args.end_date.trim(), "%Y-%m-%d")
+                        .map_err(|_| format!("Invalid end_date '{}', expected YYYY-MM-DD", args.end_date))?;
+
+                    // published_before is exclusive, so go to the start of the day after end_date
+                    let filter = PostFilter {
+                        company: args.company.clone(),
+                        published_after: Some(start.to_string()),
+                        published_before: end.succ_opt().map(|d| d.to_string()),
+                        limit: Some(posts_limit(args.limit)),
+                        ..Default::default()
+                    };
+
+                    self.fetch_posts(filter).await
+                        .map_err(|e| format!("Failed to get posts in date range: {}", e).into())
+                }
+
+                /// A company's most relevant posts on `topic`, or its latest posts without one.
+                async fn company_posts_on_topic(
+                    &self,
+                    company: &str,
+                    topic: Option<&str>,
+                    limit: usize,
+                ) -> Result<Vec<Poast>, Box<dyn std::error::Error + Send + Sync>> {
+                    let filter = PostFilter {
+                        search_term: topic.map(|t| t.to_string()),
+                        company: Some(company.to_string()),
+                        limit: Some(limit),
+                        ..Default::default()
+                    };
+
+                    let posts = match topic {
+                        Some(_) => self.posts.search(filter).await,
+                        None => self.posts.posts(filter).await,
+                    }
+                    .map_err(|e| format!("Failed to get posts for {}: {}", company, e))?;
+
+                    Ok(posts)
+                }
+
+                async fn get_post_with_full_text(
+                    &self,
+                    post_id: i32,
+                ) -> Result<Poast, Box<dyn std::error::Error + Send + Sync>> {
+                    self.posts
+                        .post(post_id)
+                        .await?
+                        .ok_or_else(|| format!("No post with id {}", post_id).into())
+                }
+
+                async fn company_stats(
+                    &self,
+                    company: &str,
+                ) -> Result<CompanyStats, Box<dyn std::error::Error + Send + Sync>> {
+                    self.posts.company_stats(company).await
+                }
+
+                async fn fetch_posts(&self, filter: PostFilter) -> Result<Vec<Poast>, Box<dyn std::error::Error + Send + Sync>> {
+                    self.posts.posts(filter).await
+                }
+
+                async fn semantic_posts(&self, query: String, search_type: SearchType) -> Result<Vec<Poast>, Box<dyn std::error::Error + Send + Sync>> {
+                    self.posts.semantic(query, search_type).await
+                }
+
+                /// Dedupe `posts`, score each against `query` with the local MiniLM model and return
+                /// them best first with their scores, without the low-relevance tail. Posts are
+                /// scored by their stored MiniLM vectors; only posts without one are embedded here.
+                /// If the model isn't available the original order is kept and nothing is dropped.
+                async fn rerank(
+                    &self,
+                    query: &str,
+                    mut posts: Vec<Poast>,
+                    pinned: &HashSet<i32>,
+                ) -> (Vec<Poast>, Vec<f32>) {
+                    let mut seen = HashSet::new();
+                    posts.retain(|post| seen.insert(post.id));
+
+                    let links: Vec<String> = posts.iter().map(|post| post.link.clone()).collect();
+                    let mut stored: HashMap<String, Vec<f32>> = match self.posts.minilm_vectors(&links).await {
+                        Ok(vectors) => vectors.into_iter().collect(),
+                        Err(e) => {
+                            error!("Couldn't load stored MiniLM vectors, embedding every post: {}", e);
+                            HashMap::new()
+                        }
+                    };
+                    // a stored vector, or the text to embed when there is none
+                    let candidates: Vec<Result<Vec<f32>, String>> = posts
                         .iter()
+                        .map(|post| stored.remove(&post.link).ok_or_else(|| embedding_text(post)))
+                        .collect();
+                    debug!(
+                        "Reranking {} posts, {} without a stored vector",
+                        candidates.len(),
+                        candidates.iter().filter(|c| c.is_err()).count()
+                    );
+
+                    let query_text = query.to_string();
+                    let scored = tokio::task::spawn_blocking(move || {
+                        LocalEmbeddingService::init()?;
+                        let service = LocalEmbeddingService::get_instance()?;
+                        let query_vector = service.generate_embedding(&query_text)?;
+                        candidates
+                            .into_iter()
+                            .map(|candidate| {
+                                let vector = match candidate {
+                                    Ok(vector) => vector,
+                                    Err(text) => service.generate_embedding(&text)?,
+                                };
+                                Ok(cosine_similarity(&query_vector, &vector).clamp(0.0, 1.0))
+                            })
+                            .collect::<Result<Vec<f32>, EmbeddingError>>()
+                    })
+                    .await;
+
+                    let scores = match scored {
+                        Ok(Ok(scores)) => scores,
+                        Ok(Err(e)) => {
+                            error!("Reranking failed, keeping retrieval order: {}", e);
+                            posts.truncate(MAX_CONTEXT_POSTS);
+                            let scores = vec![0.0; posts.len()];
+                            return (posts, scores);
+                        }
+                        Err(e) => {
+                            error!("Reranking task failed, keeping retrieval order: {}", e);
+                            posts.truncate(MAX_CONTEXT_POSTS);
+                            let scores = vec![0.0; posts.len()];
+                            return (posts, scores);
+                        }
+                    };
+
+                    let mut ranked: Vec<(Poast, f32)> = posts.into_iter().zip(scores).collect();
+                    ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+                    let before = ranked.len();
+                    let ranked: Vec<(Poast, f32)> = ranked
+                        .into_iter()
                         .enumerate()
-                        .map(|(i, post)| Citation {
+                        .filter(|(i, (post, score))| {
+                            *i < MIN_KEPT_POSTS || *score >= MIN_RELEVANCE || pinned.contains(&post.id)
+                        })
+                        .map(|(_, scored)| scored)
+                        .take(MAX_CONTEXT_POSTS)
+                        .collect();
+                    debug!("Reranked {} posts, kept {}", before, ranked.len());
+
+                    ranked.into_iter().unzip()
+                }
+
+                fn create_citations(&self, posts: &[Poast], scores: &[f32]) -> Vec<Citation> {
+                    posts
+                        .iter()
+                        .zip(scores)
+                        .map(|(post, &relevance_score)| Citation {
                             title: post.title.clone(),
                             company: post.company.clone(),
                             link: post.link.clone(),
                             published_at: post.published_at.clone(),
-                            relevance_score: 1.0 - (i as f32 * 0.05), // Slight decrease in relevance
+                            relevance_score,
                         })
                         .collect()
                 }
         
-                fn create_context(&self, posts: &[Poast], function_results: &[String]) -> String {
-                    let mut context = String::new();
-                    
-                    context.push_str("Function calls executed:\n");
-                    for result in function_results {
-                        context.push_str(&format!("- {}\n", result));
-                    }
-                    context.push('\n');
-        
-                    context.push_str("Retrieved blog posts:\n\n");
-        
-                    for (i, post) in posts.iter().enumerate() {
-                        context.push_str(&format!("Post {}:\n", i + 1));
-                        context.push_str(&format!("Title: {}\n", post.title));
-                        context.push_str(&format!("Company: {}\n", post.company));
-                        context.push_str(&format!("Published: {}\n", post.published_at));
-                        
-                        if let Some(summary) = &post.summary {
-                            context.push_str(&format!("Summary: {}\n", summary));
-                        }
-                        
-                        context.push_str(&format!("Link: {}\n\n", post.link));
-                    }
-        
-                    context
+                /// Fit the ranked posts and tool output into the token budget and tell the client
+                /// how much of it was used.
+                async fn build_context(
+                    &self,
+                    query: &str,
+                    posts: Vec<Poast>,
+                    scores: Vec<f32>,
+                    function_results: &[String],
+                    mode: ContextMode,
+                    tx: &mpsc::Sender<Result<Event, Infallible>>,
+                ) -> Result<AssembledContext, Box<dyn std::error::Error + Send + Sync>> {
+                    let query = query.to_string();
+                    let function_results = function_results.to_vec();
+                    // tokenizing and passage embeddings are CPU bound
+                    let context = tokio::task::spawn_blocking(move || {
+                        assemble_context(&query, posts, scores, &function_results, CONTEXT_TOKEN_BUDGET, mode)
+                    })
+                    .await?;
+
+                    let response = RagResponse {
+                        message_type: "context_usage".to_string(),
+                        content: None,
+                        citations: None,
+                        verification: None,
+                        context_usage: Some(context.usage.clone()),
+                    };
+                    self.send_response(tx, response).await?;
+
+                    Ok(context)
                 }
-        
+
                 async fn fallback_semantic_search(
                     &self,
                     query: String,
                     search_type: SearchType,
+                    history: &[RagMessage],
                     tx: mpsc::Sender<Result<Event, Infallible>>,
-                ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-                    let posts = semantic_search(query.clone(), search_type).await
+                ) -> Result<Option<RagMessage>, Box<dyn std::error::Error + Send + Sync>> {
+                    let posts = self.semantic_posts(query.clone(), search_type).await
                         .map_err(|e| format!("Semantic search failed: {}", e))?;
                     
                     if posts.is_empty() {
                         self.send_error(&tx, "No relevant posts found for your query.").await?;
-                        return Ok(());
+                        return Ok(None);
                     }
-        
-                    let citations = self.create_citations(&posts);
-                    self.send_citations(&tx, citations).await?;
-        
-                    let context = self.create_context(&posts, &[]);
-                    self.generate_streaming_response(query, context, tx).await?;
-        
-                    Ok(())
+
+                    let (posts, scores) = self.rerank(&query, posts, &HashSet::new()).await;
+                    let context = self.build_context(&query, posts, scores, &[], ContextMode::Posts, &tx).await?;
+                    let citations = self.create_citations(&context.posts, &context.scores);
+                    self.send_citations(&tx, citations.clone()).await?;
+
+                    let answer = self.generate_streaming_response(query, context.text, history, &tx).await?;
+
+                    self.finish_answer(answer, &context.posts, &[], citations, &tx).await.map(Some)
+                }
+
+                /// Verify the streamed answer, send the result, then signal completion.
+                async fn finish_answer(
+                    &self,
+                    answer: String,
+                    posts: &[Poast],
+                    function_results: &[String],
+                    citations: Vec<Citation>,
+                    tx: &mpsc::Sender<Result<Event, Infallible>>,
+                ) -> Result<RagMessage, Box<dyn std::error::Error + Send + Sync>> {
+                    if !answer.trim().is_empty() {
+                        self.send_status(tx, "Checking the answer against its sources...").await?;
+                        // a failed check shouldn't cost the user the answer
+                        match self.verify_answer(&answer, posts, function_results).await {
+                            Ok(verification) => {
+                                let response = RagResponse {
+                                    message_type: "verification".to_string(),
+                                    content: None,
+                                    citations: None,
+                                    verification: Some(verification),
+                                    context_usage: None,
+                                };
+                                self.send_response(tx, response).await?;
+                            }
+                            Err(e) => error!("Answer verification failed: {}", e),
+                        }
+                    }
+
+                    let response = RagResponse {
+                        message_type: "done".to_string(),
+                        content: None,
+                        citations: None,
+                        verification: None,
+                        context_usage: None,
+                    };
+                    self.send_response(tx, response).await?;
+
+                    Ok(Self::assistant_message(answer, citations))
+                }
+
+                /// Flag answer sentences that aren't backed by the posts they cite (or by any
+                /// retrieved post or tool output if they cite none).
+                async fn verify_answer(
+                    &self,
+                    answer: &str,
+                    posts: &[Poast],
+                    function_results: &[String],
+                ) -> Result<AnswerVerification, Box<dyn std::error::Error + Send + Sync>> {
+                    #[derive(Deserialize)]
+                    struct VerifierItem {
+                        sentence: usize,
+                        reason: String,
+                    }
+
+                    #[derive(Deserialize)]
+                    struct VerifierReply {
+                        #[serde(default)]
+                        unsupported: Vec<VerifierItem>,
+                    }
+
+                    let sentences = answer_sentences(answer);
+                    let mut unsupported = Vec::new();
+                    let mut to_check = Vec::new();
+
+                    for sentence in &sentences {
+                        let markers = citation_markers(sentence);
+                        if cites_missing_source(&markers, posts.len()) {
+                            unsupported.push(UnsupportedClaim {
+                                sentence: sentence.clone(),
+                                reason: "Cites a source that wasn't retrieved".to_string(),
+                            });
+                        } else {
+                            to_check.push((sentence, markers));
+                        }
+                    }
+
+                    if !to_check.is_empty() {
+                        let mut sources = String::new();
+                        for (i, post) in posts.iter().enumerate() {
+                            sources.push_str(&format!(
+                                "[{}] {} - {} ({}): {}\n",
+                                i + 1,
+                                post.company,
+                                post.title,
+                                post.published_at,
+                                post.summary.as_deref().or(post.description.as_deref()).unwrap_or("(no summary)")
+                            ));
+                        }
+                        let tool_output: String = function_results.join("\n").chars().take(VERIFIER_TOOL_OUTPUT_CHARS).collect();
+
+                        let mut numbered = String::new();
+                        for (i, (sentence, markers)) in to_check.iter().enumerate() {
+                            let cites = if markers.is_empty() {
+                                "cites nothing".to_string()
+                            } else {
+                                format!("cites {:?}", markers)
+                            };
+                            numbered.push_str(&format!("S{} ({}): {}\n", i + 1, cites, sentence));
+                        }
+
+                        let request = CreateChatCompletionRequest {
+                            model: self.model.clone(),
+                            messages: vec![
+                                ChatCompletionRequestMessage::System(ChatCompletionRequestSystemMessage {
+                                    content: "You check answers about blog posts against their sources. \
+                                    A sentence is supported if the sources it cites (or, when it cites nothing, any source \
+                                    or tool output) state or directly imply it. Greetings, transitions and offers to help \
+                                    need no support. Respond with JSON: \
+                                    {\"unsupported\": [{\"sentence\": <sentence number>, \"reason\": \"<short reason>\"}]}".into(),
+                                    name: None,
+                                }),
+                                ChatCompletionRequestMessage::User(ChatCompletionRequestUserMessage {
+                                    content: format!(
+                                        "Sources:\n{}\nTool output:\n{}\n\nSentences:\n{}",
+                                        sources, tool_output, numbered
+                                    ).into(),
+                                    name: None,
+                                }),
+                            ],
+                            response_format: Some(ResponseFormat::JsonObject),
+                            temperature: Some(0.0),
+                            ..Default::default()
+                        };
+
+                        let response = self.client.chat().create(request).await?;
+                        let content = response.choices
+                            .into_iter()
+                            .next()
+                            .and_then(|choice| choice.message.content)
+                            .unwrap_or_default();
+                        let reply: VerifierReply = serde_json::from_str(&content)?;
+
+                        for item in reply.unsupported {
+                            if let Some((sentence, _)) = item.sentence.checked_sub(1).and_then(|i| to_check.get(i)) {
+                                unsupported.push(UnsupportedClaim {
+                                    sentence: sentence.to_string(),
+                                    reason: item.reason,
+                                });
+                            }
+                        }
+                    }
+
+                    Ok(AnswerVerification {
+                        checked: sentences.len(),
+                        unsupported,
+                    })
+                }
+
+                fn assistant_message(content: String, citations: Vec<Citation>) -> RagMessage {
+                    RagMessage {
+                        role: "assistant".to_string(),
+                        content,
+                        citations: (!citations.is_empty()).then_some(citations),
+                        timestamp: chrono::Utc::now().to_rfc3339(),
+                    }
+                }
+
+                /// The most recent prior turns as chat messages, oldest first.
+                fn history_messages(history: &[RagMessage]) -> Vec<ChatCompletionRequestMessage> {
+                    let start = history.len().saturating_sub(MAX_HISTORY_MESSAGES);
+                    history[start..]
+                        .iter()
+                        .filter(|message| !message.content.trim().is_empty())
+                        .map(|message| {
+                            let content: String = message.content.chars().take(MAX_HISTORY_MESSAGE_CHARS).collect();
+                            if message.role == "assistant" {
+                                ChatCompletionRequestMessage::Assistant(ChatCompletionRequestAssistantMessage {
+                                    content: Some(content.into()),
+                                    ..Default::default()
+                                })
+                            } else {
+                                ChatCompletionRequestMessage::User(ChatCompletionRequestUserMessage {
+                                    content: content.into(),
+                                    name: None,
+                                })
+                            }
+                        })
+                        .collect()
                 }
         
                 // Helper methods for sending different types of responses
@@ -581,6 +1330,8 @@ pub mod rag {
                         message_type: "status".to_string(),
                         content: Some(status.to_string()),
                         citations: None,
+                        verification: None,
+                        context_usage: None,
                     };
                     self.send_response(tx, response).await
                 }
@@ -594,6 +1345,8 @@ pub mod rag {
                         message_type: "citations".to_string(),
                         content: None,
                         citations: Some(citations),
+                        verification: None,
+                        context_usage: None,
                     };
                     self.send_response(tx, response).await
                 }
@@ -607,6 +1360,8 @@ pub mod rag {
                         message_type: "error".to_string(),
                         content: Some(error.to_string()),
                         citations: None,
+                        verification: None,
+                        context_usage: None,
                     };
                     self.send_response(tx, response).await
                 }
@@ -622,21 +1377,27 @@ pub mod rag {
                     Ok(())
                 }
         
+                /// Stream the answer to the client and return the full text.
                 async fn generate_streaming_response(
                     &self,
                     query: String,
                     context: String,
-                    tx: mpsc::Sender<Result<Event, Infallible>>,
-                ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+                    history: &[RagMessage],
+                    tx: &mpsc::Sender<Result<Event, Infallible>>,
+                ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
                     let system_message = ChatCompletionRequestSystemMessage {
                         content: format!(
                             "You are a helpful assistant that answers questions about blog posts. \
                             Use the provided context to answer the user's question. The context includes information about which \
                             functions were called and what posts were retrieved. \
                             
-                            Always reference specific posts when relevant by mentioning the company name and post title. \
                             Be concise but informative. Format your response in markdown.\n\n\
-                            When referencing posts, use this format: **[Company Name - Post Title]**\n\n\
+                            Cite your sources inline: after each statement that comes from a post, add its number in \
+                            square brackets, e.g. [1] or [2][5], using the numbers the posts have in the context. \
+                            Only cite posts that actually support the statement, and don't state facts that \
+                            aren't in the context.\n\n\
+                            Earlier turns of the conversation are included for reference; answer the latest question \
+                            using the context below.\n\n\
                             Context:\n{}",
                             context
                         ).into(),
@@ -648,12 +1409,13 @@ pub mod rag {
                         name: None,
                     };
         
+                    let mut messages = vec![ChatCompletionRequestMessage::System(system_message)];
+                    messages.extend(Self::history_messages(history));
+                    messages.push(ChatCompletionRequestMessage::User(user_message));
+
                     let request = CreateChatCompletionRequest {
                         model: self.model.clone(),
-                        messages: vec![
-                            ChatCompletionRequestMessage::System(system_message),
-                            ChatCompletionRequestMessage::User(user_message),
-                        ],
+                        messages,
                         stream: Some(true),
                         max_completion_tokens: Some(1000),
                         temperature: Some(0.7),
@@ -661,38 +1423,168 @@ pub mod rag {
                     };
         
                     let mut stream = self.client.chat().create_stream(request).await?;
-        
+                    let mut answer = String::new();
+
                     while let Some(result) = stream.next().await {
                         match result {
                             Ok(response) => {
                                 for choice in response.choices {
                                     if let Some(delta) = choice.delta.content {
+                                        answer.push_str(&delta);
                                         let response = RagResponse {
                                             message_type: "content".to_string(),
                                             content: Some(delta),
                                             citations: None,
+                                            verification: None,
+                                            context_usage: None,
                                         };
-                                        self.send_response(&tx, response).await?;
+                                        self.send_response(tx, response).await?;
                                     }
                                 }
                             }
                             Err(e) => {
                                 error!("Error in streaming response: {}", e);
-                                self.send_error(&tx, &format!("Error generating response: {}", e)).await?;
+                                self.send_error(tx, &format!("Error generating response: {}", e)).await?;
                                 break;
                             }
                         }
                     }
-        
-                    // Send completion signal
-                    let response = RagResponse {
-                        message_type: "done".to_string(),
-                        content: None,
-                        citations: None,
-                    };
-                    self.send_response(&tx, response).await?;
-        
-                    Ok(())
+
+                    Ok(answer)
+                }
+            }
+
+            /// Answer sentences worth verifying, with any citation markers that follow their
+            /// final punctuation kept attached.
+            fn answer_sentences(answer: &str) -> Vec<String> {
+                let mut sentences = Vec::new();
+
+                for line in answer.lines() {
+                    let line = line.trim().trim_start_matches(['-', '*', '#', '>']).trim();
+                    let mut start = 0;
+                    let mut search = 0;
+
+                    while let Some(offset) = line[search..].find(['.', '!', '?']) {
+                        // swallow markers written after the full stop: "... done.[2]" or "... done. [2][3]"
+                        let mut end = search + offset + 1;
+                        while let Some(len) = leading_marker_len(&line[end..]) {
+                            end += len;
+                        }
+                        // "1.5" or "e.g.x" don't end a sentence
+                        if line[end..].chars().next().is_none_or(char::is_whitespace) {
+                            sentences.push(line[start..end].trim().to_string());
+                            start = end;
+                        }
+                        search = end;
+                    }
+                    sentences.push(line[start..].trim().to_string());
+                }
+
+                sentences
+                    .into_iter()
+                    .filter(|s| s.split_whitespace().count() >= MIN_VERIFIED_SENTENCE_WORDS)
+                    .collect()
+            }
+
+            /// Byte length of a citation marker at the start of `text`, including any whitespace
+            /// before it.
+            fn leading_marker_len(text: &str) -> Option<usize> {
+                let trimmed = text.trim_start();
+                let inner = trimmed.strip_prefix('[')?;
+                let close = inner.find(']')?;
+                marker_numbers(&inner[..close])?;
+                Some(text.len() - trimmed.len() + close + 2)
+            }
+
+            /// The numbers in a marker's body ("1" or "1, 3"), or None if it isn't a citation.
+            fn marker_numbers(body: &str) -> Option<Vec<usize>> {
+                if !body.chars().all(|c| c.is_ascii_digit() || c == ',' || c == ' ') {
+                    return None;
+                }
+                let numbers: Vec<usize> = body.split(',').filter_map(|n| n.trim().parse().ok()).collect();
+                (!numbers.is_empty()).then_some(numbers)
+            }
+
+            /// True if any marker points outside the `sources` numbered from 1.
+            fn cites_missing_source(markers: &[usize], sources: usize) -> bool {
+                markers.iter().any(|&n| n == 0 || n > sources)
+            }
+
+            /// Numbers in `[n]` / `[n, m]` markers, in order of appearance.
+            pub fn citation_markers(sentence: &str) -> Vec<usize> {
+                let mut markers = Vec::new();
+                let mut rest = sentence;
+
+                while let Some(open) = rest.find('[') {
+                    rest = &rest[open + 1..];
+                    let Some(close) = rest.find(']') else { break };
+                    // "[see [2]]": only the innermost bracket can be a marker
+                    let body = rest[..close].rsplit('[').next().unwrap_or_default();
+                    markers.extend(marker_numbers(body).into_iter().flatten());
+                    rest = &rest[close + 1..];
+                }
+
+                markers
+            }
+
+            #[cfg(test)]
+            mod tests {
+                use super::*;
+
+                #[test]
+                fn test_citation_markers() {
+                    assert_eq!(citation_markers("Rust is fast [1]."), vec![1]);
+                    assert_eq!(citation_markers("Both agree [1][3] and [2, 4]."), vec![1, 3, 2, 4]);
+                    assert_eq!(citation_markers("See [12]"), vec![12]);
+                    assert_eq!(citation_markers("Nested [see [2]] works"), vec![2]);
+                }
+
+                #[test]
+                fn test_citation_markers_malformed() {
+                    for sentence in ["[]", "[ ]", "[a]", "[1-3]", "[-1]", "unclosed [1", "[1.5]", "[,]", "no markers"] {
+                        assert!(citation_markers(sentence).is_empty(), "{}", sentence);
+                    }
+                    assert_eq!(citation_markers("[1,,2]"), vec![1, 2]);
+                    assert_eq!(citation_markers("[1] then unclosed [2"), vec![1]);
+                }
+
+                #[test]
+                fn test_cites_missing_source() {
+                    assert!(!cites_missing_source(&[], 0));
+                    assert!(!cites_missing_source(&[1, 3], 3));
+                    assert!(cites_missing_source(&[0], 3));
+                    assert!(cites_missing_source(&[2, 4], 3));
+                    assert!(cites_missing_source(&[1], 0));
+                }
+
+                #[test]
+                fn test_answer_sentences_keeps_markers() {
+                    let sentences = answer_sentences(
+                        "Rust ships a new borrow checker.[1] Go added generics last year. [2][3] Both are fast languages [1, 2].",
+                    );
+                    assert_eq!(
+                        sentences,
+                        vec![
+                            "Rust ships a new borrow checker.[1]",
+                            "Go added generics last year. [2][3]",
+                            "Both are fast languages [1, 2].",
+                        ]
+                    );
+                }
+
+                #[test]
+                fn test_answer_sentences_splitting() {
+                    let sentences = answer_sentences(
+                        "- Version 1.5 shipped with faster builds!\n## Notes\nShort one. Is this really the last line?",
+                    );
+                    assert_eq!(
+                        sentences,
+                        vec!["Version 1.5 shipped with faster builds!", "Is this really the last line?"]
+                    );
+
+                    // a bracket that isn't a marker doesn't get attached
+                    let sentences = answer_sentences("They wrote about caching today. [Link] to the post is below here.");
+                    assert_eq!(sentences, vec!["They wrote about caching today.", "[Link] to the post is below here."]);
                 }
             }
         }
@@ -0,0 +1,226 @@
+/// Where the RAG service reads posts from: Supabase in the app, a fixture corpus in `rag_eval`.
+pub mod source {
+    use futures_util::future::BoxFuture;
+    use serde::Deserialize;
+
+    use crate::components::poasts::{get_poasts, search_poasts, semantic_search, Poast, PostFilter};
+    use crate::components::search::SearchType;
+    use crate::embedding_service::embeddings::EmbeddingColumn;
+    use crate::vector_search_service::vector_search::stored_vectors;
+
+    pub type SourceResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// "Recent" window for company stats.
+    pub const RECENT_DAYS: i64 = 90;
+
+    #[derive(Debug)]
+    pub struct CompanyStats {
+        pub company: String,
+        pub post_count: usize,
+        pub recent_count: usize,
+        pub first_published: Option<String>,
+        pub latest_published: Option<String>,
+        pub top_buzzwords: Vec<(String, usize)>,
+    }
+
+    impl CompanyStats {
+        pub fn describe(&self) -> String {
+            let mut description = format!(
+                "{}: {} posts in total, {} in the last {} days",
+                self.company, self.post_count, self.recent_count, RECENT_DAYS
+            );
+            if let (Some(first), Some(latest)) = (&self.first_published, &self.latest_published) {
+                description.push_str(&format!(", first post {}, latest post {}", first, latest));
+            }
+            if !self.top_buzzwords.is_empty() {
+                let buzzwords: Vec<String> = self.top_buzzwords
+                    .iter()
+                    .map(|(word, count)| format!("{} ({})", word, count))
+                    .collect();
+                description.push_str(&format!(". Most common topics: {}", buzzwords.join(", ")));
+            }
+            description
+        }
+    }
+
+    /// Start of the `RECENT_DAYS` window as a `published_at` date.
+    pub fn recent_since() -> String {
+        (chrono::Utc::now() - chrono::Duration::days(RECENT_DAYS))
+            .format("%Y-%m-%d")
+            .to_string()
+    }
+
+    /// Most frequent buzzwords (case-insensitive), most first.
+    pub fn top_buzzwords(words: impl Iterator<Item = String>) -> Vec<(String, usize)> {
+        let mut counts: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+        for word in words {
+            *counts.entry(word.to_lowercase()).or_insert(0) += 1;
+        }
+        let mut top: Vec<(String, usize)> = counts.into_iter().collect();
+        top.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        top.truncate(8);
+        top
+    }
+
+    /// Post lookups the RAG tools need. Boxed futures so the service can hold `Arc<dyn PostSource>`.
+    pub trait PostSource: Send + Sync {
+        /// Posts passing `filter`, newest first, as `get_poasts` returns them.
+        fn posts(&self, filter: PostFilter) -> BoxFuture<'_, SourceResult<Vec<Poast>>>;
+
+        /// Posts passing `filter`, most relevant to its search term first.
+        fn search(&self, filter: PostFilter) -> BoxFuture<'_, SourceResult<Vec<Poast>>>;
+
+        /// Posts most similar to `query`, unfiltered.
+        fn semantic(&self, query: String, search_type: SearchType) -> BoxFuture<'_, SourceResult<Vec<Poast>>>;
+
+        /// One post with its full text.
+        fn post(&self, id: i32) -> BoxFuture<'_, SourceResult<Option<Poast>>>;
+
+        fn company_stats<'a>(&'a self, company: &'a str) -> BoxFuture<'a, SourceResult<CompanyStats>>;
+
+        /// Stored MiniLM vectors for whichever of `links` have one.
+        fn minilm_vectors<'a>(&'a self, links: &'a [String]) -> BoxFuture<'a, SourceResult<Vec<(String, Vec<f32>)>>>;
+    }
+
+    /// The `poasts` table, through the same server functions the UI uses.
+    pub struct SupabasePosts;
+
+    impl SupabasePosts {
+        /// Exact row count from PostgREST's Content-Range header.
+        async fn count_posts(company: &str, since: Option<&str>) -> SourceResult<usize> {
+            let mut request = crate::supabase::get_client()
+                .from("poasts")
+                .select("id")
+                .eq("company", company)
+                .exact_count()
+                .limit(1);
+            if let Some(since) = since {
+                request = request.gte("published_at", since);
+            }
+
+            let response = request.execute().await?;
+            let count = response
+                .headers()
+                .get("content-range")
+                .and_then(|range| range.to_str().ok())
+                .and_then(|range| range.rsplit('/').next())
+                .and_then(|total| total.parse().ok())
+                .unwrap_or(0);
+
+            Ok(count)
+        }
+
+        async fn fetch_company_stats(company: &str) -> SourceResult<CompanyStats> {
+            #[derive(Deserialize)]
+            struct DateRow {
+                published_at: String,
+            }
+
+            #[derive(Deserialize)]
+            struct BuzzwordRow {
+                buzzwords: Option<Vec<String>>,
+            }
+
+            let supabase = crate::supabase::get_client();
+            let recent_since = recent_since();
+
+            let post_count = Self::count_posts(company, None).await?;
+            let recent_count = Self::count_posts(company, Some(&recent_since)).await?;
+
+            let mut edge_dates = Vec::new();
+            for ascending in [true, false] {
+                let body = supabase
+                    .from("poasts")
+                    .select("published_at")
+                    .eq("company", company)
+                    .order(if ascending { "published_at.asc" } else { "published_at.desc" })
+                    .limit(1)
+                    .execute()
+                    .await?
+                    .text()
+                    .await?;
+                let rows: Vec<DateRow> = serde_json::from_str(&body)?;
+                edge_dates.push(rows.into_iter().next().map(|r| r.published_at));
+            }
+
+            // topics from the most recent posts
+            let body = supabase
+                .from("poasts")
+                .select("buzzwords")
+                .eq("company", company)
+                .order("published_at.desc")
+                .limit(200)
+                .execute()
+                .await?
+                .text()
+                .await?;
+            let rows: Vec<BuzzwordRow> = serde_json::from_str(&body)?;
+            let top_buzzwords = top_buzzwords(rows.into_iter().flat_map(|r| r.buzzwords.unwrap_or_default()));
+
+            let latest_published = edge_dates.pop().flatten();
+            let first_published = edge_dates.pop().flatten();
+
+            Ok(CompanyStats {
+                company: company.to_string(),
+                post_count,
+                recent_count,
+                first_published,
+                latest_published,
+                top_buzzwords,
+            })
+        }
+    }
+
+    impl PostSource for SupabasePosts {
+        fn posts(&self, filter: PostFilter) -> BoxFuture<'_, SourceResult<Vec<Poast>>> {
+            Box::pin(async move { Ok(get_poasts(Some(filter)).await?) })
+        }
+
+        fn search(&self, filter: PostFilter) -> BoxFuture<'_, SourceResult<Vec<Poast>>> {
+            Box::pin(async move { Ok(search_poasts(filter, SearchType::Hybrid).await?.posts) })
+        }
+
+        fn semantic(&self, query: String, search_type: SearchType) -> BoxFuture<'_, SourceResult<Vec<Poast>>> {
+            Box::pin(async move { Ok(semantic_search(query, search_type).await?) })
+        }
+
+        fn post(&self, id: i32) -> BoxFuture<'_, SourceResult<Option<Poast>>> {
+            Box::pin(async move {
+                let body = crate::supabase::get_client()
+                    .from("poasts")
+                    .select("id, published_at, company, title, link, description, summary, full_text")
+                    .eq("id", id.to_string())
+                    .limit(1)
+                    .execute()
+                    .await?
+                    .text()
+                    .await?;
+
+                let mut posts: Vec<Poast> = serde_json::from_str(&body)?;
+                Ok(posts.pop())
+            })
+        }
+
+        fn company_stats<'a>(&'a self, company: &'a str) -> BoxFuture<'a, SourceResult<CompanyStats>> {
+            Box::pin(Self::fetch_company_stats(company))
+        }
+
+        fn minilm_vectors<'a>(&'a self, links: &'a [String]) -> BoxFuture<'a, SourceResult<Vec<(String, Vec<f32>)>>> {
+            Box::pin(async move { Ok(stored_vectors(EmbeddingColumn::MiniLm, links).await?) })
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_top_buzzwords_case_insensitive() {
+            let words = ["Rust", "rust", "Kafka", "RUST", "kafka", "Go"].into_iter().map(String::from);
+            assert_eq!(
+                top_buzzwords(words),
+                vec![("rust".to_string(), 3), ("kafka".to_string(), 2), ("go".to_string(), 1)]
+            );
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use dashmap::DashMap;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Longest question accepted on the public ask page.
+pub const ASK_MAX_QUESTION_CHARS: usize = 500;
+/// Questions per user per UTC day.
+pub const ASK_DAILY_QUOTA: i32 = 30;
+const ASK_PER_USER_PER_MINUTE: usize = 4;
+const ASK_PER_IP_PER_MINUTE: usize = 10;
+/// Public answers generated at once across all users.
+const ASK_MAX_CONCURRENT: usize = 8;
+/// Requests per API key per minute on `/api/v1`.
+pub const API_REQUESTS_PER_MINUTE: usize = 120;
+/// Keys tracked before a limiter sweeps out idle ones.
+const SWEEP_THRESHOLD: usize = 10_000;
+
+/// Sliding-window request counter keyed by user or client IP.
+pub struct RateLimiter {
+    limit: usize,
+    window: Duration,
+    hits: DashMap<String, VecDeque<Instant>>,
+}
+
+impl RateLimiter {
+    pub fn new(limit: usize, window: Duration) -> Self {
+        Self {
+            limit,
+            window,
+            hits: DashMap::new(),
+        }
+    }
+
+    /// Record a hit for `key`, or return how long to wait if it is over the limit.
+    pub fn check(&self, key: &str) -> Result<(), Duration> {
+        self.check_at(key, Instant::now(), true)
+    }
+
+    /// Like `check`, but without recording a hit.
+    pub fn peek(&self, key: &str) -> Result<(), Duration> {
+        self.check_at(key, Instant::now(), false)
+    }
+
+    fn check_at(&self, key: &str, now: Instant, record: bool) -> Result<(), Duration> {
+        if self.hits.len() > SWEEP_THRESHOLD {
+            self.hits.retain(|_, hits| hits.back().is_some_and(|t| now.duration_since(*t) < self.window));
+        }
+
+        let mut hits = self.hits.entry(key.to_string()).or_default();
+        while hits.front().is_some_and(|t| now.duration_since(*t) >= self.window) {
+            hits.pop_front();
+        }
+
+        if hits.len() >= self.limit {
+            let oldest = hits.front().copied().unwrap_or(now);
+            return Err(self.window.saturating_sub(now.duration_since(oldest)));
+        }
+
+        if record {
+            hits.push_back(now);
+        }
+        Ok(())
+    }
+}
+
+/// Held while a public answer is being generated; dropping it frees the user's slot.
+pub struct AskPermit {
+    user_id: i32,
+    in_flight: Arc<DashMap<i32, ()>>,
+    _global: OwnedSemaphorePermit,
+}
+
+impl Drop for AskPermit {
+    fn drop(&mut self) {
+        self.in_flight.remove(&self.user_id);
+    }
+}
+
+#[derive(Debug)]
+pub enum AskRejection {
+    TooLong,
+    Empty,
+    RateLimited(Duration),
+    AlreadyAsking,
+    Busy,
+}
+
+impl std::fmt::Display for AskRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AskRejection::TooLong => write!(f, "Questions are limited to {} characters", ASK_MAX_QUESTION_CHARS),
+            AskRejection::Empty => write!(f, "Please enter a question"),
+            AskRejection::RateLimited(wait) => write!(f, "Too many questions, try again in {}s", wait.as_secs().max(1)),
+            AskRejection::AlreadyAsking => write!(f, "Wait for your current answer to finish"),
+            AskRejection::Busy => write!(f, "Lots of people are asking right now, try again shortly"),
+        }
+    }
+}
+
+/// Limits for the public ask endpoint, shared through `AppState`.
+#[derive(Clone)]
+pub struct AskLimits {
+    per_user: Arc<RateLimiter>,
+    per_ip: Arc<RateLimiter>,
+    in_flight: Arc<DashMap<i32, ()>>,
+    global: Arc<Semaphore>,
+}
+
+impl Default for AskLimits {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AskLimits {
+    pub fn new() -> Self {
+        Self::with_limits(ASK_PER_USER_PER_MINUTE, ASK_PER_IP_PER_MINUTE, ASK_MAX_CONCURRENT)
+    }
+
+    fn with_limits(per_user_per_minute: usize, per_ip_per_minute: usize, max_concurrent: usize) -> Self {
+        Self {
+            per_user: Arc::new(RateLimiter::new(per_user_per_minute, Duration::from_secs(60))),
+            per_ip: Arc::new(RateLimiter::new(per_ip_per_minute, Duration::from_secs(60))),
+            in_flight: Arc::new(DashMap::new()),
+            global: Arc::new(Semaphore::new(max_concurrent)),
+        }
+    }
+
+    /// Everything short of the daily quota, which lives in the database. Only admitted
+    /// questions count against the per-minute limits.
+    pub fn admit(&self, user_id: i32, ip: &str, question: &str) -> Result<AskPermit, AskRejection> {
+        let length = question.chars().count();
+        if length == 0 {
+            return Err(AskRejection::Empty);
+        }
+        if length > ASK_MAX_QUESTION_CHARS {
+            return Err(AskRejection::TooLong);
+        }
+
+        let user_key = user_id.to_string();
+        self.per_ip.peek(ip).map_err(AskRejection::RateLimited)?;
+        self.per_user.peek(&user_key).map_err(AskRejection::RateLimited)?;
+
+        let global = self.global.clone().try_acquire_owned().map_err(|_| AskRejection::Busy)?;
+        if self.in_flight.insert(user_id, ()).is_some() {
+            return Err(AskRejection::AlreadyAsking);
+        }
+        let permit = AskPermit {
+            user_id,
+            in_flight: self.in_flight.clone(),
+            _global: global,
+        };
+
+        // re-checked while recording; another user on the same IP may have got in since the peek
+        self.per_ip.check(ip).map_err(AskRejection::RateLimited)?;
+        self.per_user.check(&user_key).map_err(AskRejection::RateLimited)?;
+        Ok(permit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter_window() {
+        let limiter = RateLimiter::new(2, Duration::from_secs(60));
+        let start = Instant::now();
+
+        assert!(limiter.check_at("a", start, true).is_ok());
+        assert!(limiter.check_at("a", start + Duration::from_secs(10), true).is_ok());
+        let wait = limiter.check_at("a", start + Duration::from_secs(20), true).unwrap_err();
+        assert_eq!(wait, Duration::from_secs(40));
+
+        // other keys have their own window
+        assert!(limiter.check_at("b", start + Duration::from_secs(20), true).is_ok());
+
+        // the first hit ages out after the window
+        assert!(limiter.check_at("a", start + Duration::from_secs(60), true).is_ok());
+        assert!(limiter.check_at("a", start + Duration::from_secs(61), true).is_err());
+    }
+
+    #[test]
+    fn test_rate_limiter_peek_does_not_record() {
+        let limiter = RateLimiter::new(1, Duration::from_secs(60));
+        assert!(limiter.peek("a").is_ok());
+        assert!(limiter.peek("a").is_ok());
+        assert!(limiter.check("a").is_ok());
+        assert!(limiter.peek("a").is_err());
+    }
+
+    #[test]
+    fn test_ask_permit_limits_concurrency() {
+        let limits = AskLimits::with_limits(10, 10, 1);
+
+        let permit = limits.admit(1, "10.0.0.1", "first?").unwrap();
+        assert!(matches!(limits.admit(1, "10.0.0.1", "again?"), Err(AskRejection::Busy)));
+        assert!(matches!(limits.admit(2, "10.0.0.2", "other?"), Err(AskRejection::Busy)));
+
+        drop(permit);
+        assert!(limits.admit(2, "10.0.0.2", "other?").is_ok());
+    }
+
+    #[test]
+    fn test_ask_rejects_a_second_question_from_the_same_user() {
+        let limits = AskLimits::with_limits(10, 10, 4);
+
+        let _permit = limits.admit(1, "10.0.0.1", "first?").unwrap();
+        assert!(matches!(limits.admit(1, "10.0.0.1", "second?"), Err(AskRejection::AlreadyAsking)));
+        assert!(limits.admit(2, "10.0.0.1", "someone else?").is_ok());
+    }
+
+    #[test]
+    fn test_ask_rejections_do_not_count_against_the_rate_limit() {
+        let limits = AskLimits::with_limits(2, 10, 1);
+
+        let permit = limits.admit(1, "10.0.0.1", "first?").unwrap();
+        for _ in 0..5 {
+            assert!(matches!(limits.admit(1, "10.0.0.1", "again?"), Err(AskRejection::Busy)));
+        }
+        assert!(matches!(limits.admit(1, "10.0.0.1", ""), Err(AskRejection::Empty)));
+        drop(permit);
+
+        // only the admitted question was counted, so one more fits in the minute
+        drop(limits.admit(1, "10.0.0.1", "second?").unwrap());
+        assert!(matches!(limits.admit(1, "10.0.0.1", "third?"), Err(AskRejection::RateLimited(_))));
+    }
+}
@@ -0,0 +1,246 @@
+#[cfg(feature = "ssr")]
+pub mod recommendations {
+    use chrono::{NaiveDate, Utc};
+    use log::{debug, info};
+    use serde::Deserialize;
+    use std::collections::{HashMap, HashSet};
+    use std::hash::{Hash, Hasher};
+    use thiserror::Error;
+
+    use crate::components::poasts::{fetch_post_page, ranked_posts, Poast, PostFilter, PostScope};
+    use crate::embedding_service::embeddings::EmbeddingColumn;
+    use crate::vector_search_service::vector_search::{
+        cosine_similarity, nearest_links, stored_vectors, VectorSearchError,
+    };
+
+    /// Engaged posts averaged into the interest vector, most recent engagement first.
+    const MAX_ENGAGED_POSTS: usize = 100;
+    const BOOKMARK_WEIGHT: f32 = 2.0;
+    const READ_WEIGHT: f32 = 1.0;
+    /// Nearest neighbours of the interest vector considered for the feed.
+    const SIMILAR_CANDIDATES: usize = 150;
+    /// Newest posts considered whatever their similarity, so fresh posts can still surface.
+    const RECENT_CANDIDATES: usize = 100;
+    const FOLLOWED_CANDIDATES: usize = 50;
+    const SIMILARITY_WEIGHT: f32 = 0.65;
+    const RECENCY_WEIGHT: f32 = 0.35;
+    /// Added to the score of posts from followed companies.
+    const FOLLOW_BOOST: f32 = 0.15;
+    /// Age in days at which a post's recency score halves.
+    const RECENCY_HALF_LIFE_DAYS: f32 = 14.0;
+    /// Ranked posts kept per user; "load more" pages through these.
+    pub const MAX_RANKED_POSTS: usize = 200;
+
+    #[derive(Error, Debug)]
+    pub enum RecommendationError {
+        #[error("Post query failed: {0}")]
+        Posts(String),
+
+        #[error("Vector search failed: {0}")]
+        Vectors(#[from] VectorSearchError),
+
+        #[error("JSON error: {0}")]
+        Json(#[from] serde_json::Error),
+    }
+
+    /// What a user has engaged with. Post ids are most recent first.
+    #[derive(Debug, Clone, Default, Hash)]
+    pub struct Engagement {
+        pub bookmarked: Vec<i32>,
+        pub read: Vec<i32>,
+        pub followed_companies: Vec<String>,
+    }
+
+    impl Engagement {
+        /// True for new users, who get the chronological listing instead.
+        pub fn is_empty(&self) -> bool {
+            self.bookmarked.is_empty() && self.read.is_empty() && self.followed_companies.is_empty()
+        }
+
+        /// Changes whenever the engagement does, for cache keys.
+        pub fn fingerprint(&self) -> u64 {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            self.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        /// Weight per engaged post, bookmarks counting more than reads.
+        fn weights(&self) -> Vec<(i32, f32)> {
+            let mut weights: Vec<(i32, f32)> = Vec::new();
+            let engaged = self
+                .bookmarked
+                .iter()
+                .map(|id| (*id, BOOKMARK_WEIGHT))
+                .chain(self.read.iter().map(|id| (*id, READ_WEIGHT)));
+            for (id, weight) in engaged {
+                match weights.iter_mut().find(|(seen, _)| *seen == id) {
+                    Some((_, total)) => *total += weight,
+                    None if weights.len() < MAX_ENGAGED_POSTS => weights.push((id, weight)),
+                    None => {}
+                }
+            }
+            weights
+        }
+    }
+
+    /// Weighted mean of the engaged posts' stored vectors, normalised, in the first space that
+    /// has any of them.
+    async fn interest_vector(
+        weights: &[(i32, f32)],
+    ) -> Result<Option<(EmbeddingColumn, Vec<f32>)>, RecommendationError> {
+        #[derive(Deserialize)]
+        struct PostLink {
+            id: i32,
+            link: String,
+        }
+
+        if weights.is_empty() {
+            return Ok(None);
+        }
+
+        let ids: Vec<String> = weights.iter().map(|(id, _)| id.to_string()).collect();
+        let body = crate::supabase::get_client()
+            .from("poasts")
+            .select("id, link")
+            .in_("id", &ids)
+            .execute()
+            .await
+            .map_err(|e| RecommendationError::Posts(e.to_string()))?
+            .text()
+            .await
+            .map_err(|e| RecommendationError::Posts(e.to_string()))?;
+        let links: Vec<PostLink> = serde_json::from_str(&body)?;
+
+        let weight_by_id: HashMap<i32, f32> = weights.iter().copied().collect();
+        let weight_by_link: HashMap<String, f32> = links
+            .into_iter()
+            .filter_map(|post| weight_by_id.get(&post.id).map(|weight| (post.link, *weight)))
+            .collect();
+        let links: Vec<String> = weight_by_link.keys().cloned().collect();
+
+        for column in [EmbeddingColumn::OpenAI, EmbeddingColumn::MiniLm] {
+            let vectors = stored_vectors(column, &links).await?;
+            let Some(dim) = vectors.first().map(|(_, vector)| vector.len()) else {
+                continue;
+            };
+
+            let mut sum = vec![0.0f32; dim];
+            for (link, vector) in vectors.iter().filter(|(_, vector)| vector.len() == dim) {
+                let weight = weight_by_link.get(link).copied().unwrap_or(READ_WEIGHT);
+                for (total, value) in sum.iter_mut().zip(vector) {
+                    *total += weight * value;
+                }
+            }
+
+            let norm = sum.iter().map(|v| v * v).sum::<f32>().sqrt();
+            if norm > 0.0 {
+                sum.iter_mut().for_each(|v| *v /= norm);
+                debug!("Interest vector from {} posts in {:?}", vectors.len(), column);
+                return Ok(Some((column, sum)));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// 1.0 for posts published today, halving every `RECENCY_HALF_LIFE_DAYS`.
+    fn recency(published_at: &str, today: NaiveDate) -> f32 {
+        published_at
+            .get(..10)
+            .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+            .map(|date| {
+                let age = (today - date).num_days().max(0) as f32;
+                0.5f32.powf(age / RECENCY_HALF_LIFE_DAYS)
+            })
+            .unwrap_or(0.0)
+    }
+
+    fn posts_error(e: impl std::fmt::Display) -> RecommendationError {
+        RecommendationError::Posts(e.to_string())
+    }
+
+    /// The user's "for you" feed: unread posts scored by similarity to their interest vector
+    /// blended with recency, plus a boost for followed companies. Best first.
+    pub async fn rank_for_user(engagement: Engagement) -> Result<Vec<Poast>, RecommendationError> {
+        let interest = interest_vector(&engagement.weights()).await?;
+
+        let mut candidates: HashMap<i32, Poast> = HashMap::new();
+
+        // read posts are dropped here rather than excluded in the queries, whose URLs would
+        // otherwise grow with the user's whole reading history
+        if let Some((column, vector)) = &interest {
+            // the engaged posts sit nearest the interest vector, so look past them
+            let depth = SIMILAR_CANDIDATES + MAX_ENGAGED_POSTS;
+            let nearest = nearest_links(*column, vector, depth).await?;
+            let similar = ranked_posts(nearest, &PostFilter::default(), &PostScope::default(), 0, depth)
+                .await
+                .map_err(posts_error)?;
+            candidates.extend(similar.posts.into_iter().map(|post| (post.id, post)));
+        }
+
+        let recent = fetch_post_page(&PostFilter {
+            limit: Some(RECENT_CANDIDATES),
+            ..Default::default()
+        }, &PostScope::default())
+        .await
+        .map_err(posts_error)?;
+        let followed = if engagement.followed_companies.is_empty() {
+            Vec::new()
+        } else {
+            fetch_post_page(&PostFilter {
+                companies: engagement.followed_companies.clone(),
+                limit: Some(FOLLOWED_CANDIDATES),
+                ..Default::default()
+            }, &PostScope::default())
+            .await
+            .map_err(posts_error)?
+            .posts
+        };
+        for post in recent.posts.into_iter().chain(followed) {
+            candidates.entry(post.id).or_insert(post);
+        }
+
+        let read: HashSet<i32> = engagement.read.iter().copied().collect();
+        candidates.retain(|id, _| !read.contains(id));
+
+        // posts found by recency or follow haven't been scored against the interest vector yet
+        if let Some((column, vector)) = &interest {
+            let unscored: Vec<String> = candidates
+                .values()
+                .filter(|post| post.similarity.is_none())
+                .map(|post| post.link.clone())
+                .collect();
+            let vectors: HashMap<String, Vec<f32>> = stored_vectors(*column, &unscored).await?.into_iter().collect();
+            for post in candidates.values_mut().filter(|post| post.similarity.is_none()) {
+                if let Some(post_vector) = vectors.get(&post.link) {
+                    post.similarity = Some((cosine_similarity(vector, post_vector) * 100.0).round() as i32);
+                }
+            }
+        }
+
+        let followed: HashSet<&str> = engagement.followed_companies.iter().map(String::as_str).collect();
+        let today = Utc::now().date_naive();
+        let mut scored: Vec<(f32, Poast)> = candidates
+            .into_values()
+            .map(|post| {
+                let similarity = post.similarity.unwrap_or(0).max(0) as f32 / 100.0;
+                let mut score = SIMILARITY_WEIGHT * similarity + RECENCY_WEIGHT * recency(&post.published_at, today);
+                if followed.contains(post.company.as_str()) {
+                    score += FOLLOW_BOOST;
+                }
+                (score, post)
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| b.1.published_at.cmp(&a.1.published_at)));
+        scored.truncate(MAX_RANKED_POSTS);
+
+        info!(
+            "Ranked {} posts from {} bookmarks, {} reads and {} followed companies",
+            scored.len(),
+            engagement.bookmarked.len(),
+            engagement.read.len(),
+            engagement.followed_companies.len()
+        );
+        Ok(scored.into_iter().map(|(_, post)| post).collect())
+    }
+}
@@ -20,7 +20,9 @@ use async_openai::{
     Client,
 };
 
+use crate::database::db::DbPool;
 use crate::server_fn::{invalidate_poasts_cache, RssProgressUpdate};
+use crate::webhook_service::webhooks::{notify_saved_searches, IngestedPost};
 
 const STANDARD_ENTRY_LIMIT: usize = 10;
 const EXTENDED_ENTRY_LIMIT: usize = 20;
@@ -33,13 +35,29 @@ pub struct FeedResult {
     pub skipped_posts: i32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// A row of the `links` table: one company and its feed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FeedLink {
-    company: String,
-    link: String,
-    last_processed: Option<DateTime<Utc>>,
+    pub company: String,
+    pub link: String,
+    pub last_processed: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub logo_url: Option<String>,
+    /// Disabled feeds are skipped by ingestion but keep their posts.
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+    /// Entries to process per run, replacing the standard/extended limits.
+    #[serde(default)]
+    pub entry_limit: Option<i32>,
 }
 
+fn enabled_by_default() -> bool {
+    true
+}
+
+/// Columns selected into `FeedLink`.
+pub const FEED_LINK_COLUMNS: &str = "company,link,last_processed,logo_url,enabled,entry_limit";
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PostInsights {
     pub summary: String,
@@ -58,6 +76,51 @@ pub struct BlogPost {
     buzzwords: Option<Vec<String>>,
 }
 
+/// Posts inserted but not yet made visible or checked against saved searches. Flushed after
+/// each feed and, if the run stops early on a cancel or an error, when dropped, so inserted
+/// posts always get their company's cached pages invalidated and their notifications sent.
+struct PendingNotifications {
+    posts: Vec<IngestedPost>,
+    pool: DbPool,
+}
+
+impl PendingNotifications {
+    fn new(pool: DbPool) -> Self {
+        Self { posts: Vec::new(), pool }
+    }
+
+    /// Webhooks retry for a while, so they run in the background rather than holding the
+    /// progress stream open.
+    fn flush(&mut self) {
+        if self.posts.is_empty() {
+            return;
+        }
+        let posts = std::mem::take(&mut self.posts);
+        let pool = self.pool.clone();
+        let mut companies: Vec<String> = posts.iter().map(|p| p.company.clone()).collect();
+        companies.sort();
+        companies.dedup();
+        tokio::spawn(async move {
+            // invalidate first so notified links already show up in cached pages
+            for company in companies {
+                if let Err(e) = invalidate_poasts_cache(Some(company.clone())).await {
+                    log::error!("Failed to invalidate poasts cache for {}: {}", company, e);
+                }
+            }
+            match notify_saved_searches(posts, pool).await {
+                Ok(delivered) => log::info!("Delivered {} saved search notifications", delivered),
+                Err(e) => log::error!("Saved search notifications failed: {}", e),
+            }
+        });
+    }
+}
+
+impl Drop for PendingNotifications {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
     pub enum InsightError {
         #[error("OpenAI error: {0}")]
@@ -70,23 +133,26 @@ pub struct BlogPost {
 pub async fn process_feeds_with_progress(
     progress_sender: tokio::sync::mpsc::Sender<Result<Event, Infallible>>,
     cancel_token: CancellationToken,
+    pool: DbPool,
 ) -> Result<(), Box<dyn Error + Send + Sync>> {
     let supabase = crate::supabase::get_client();
     let openai = Client::with_config(OpenAIConfig::default());
     
     let response = supabase
         .from("links")
-        .select("company,link,last_processed")
+        .select(FEED_LINK_COLUMNS)
+        .eq("enabled", "true")
         .execute()
         .await?;
     
     let feed_links: Vec<FeedLink> = serde_json::from_str(&response.text().await?)?;
+    let mut pending = PendingNotifications::new(pool);
     
-    for link_info in feed_links {
+    'feeds: for link_info in feed_links {
         // check for cancellation before processing each feed
         if cancel_token.is_cancelled() {
             log::info!("RSS processing cancelled");
-            return Ok(());
+            break;
         }
 
         let mut company_progress = RssProgressUpdate {
@@ -141,13 +207,17 @@ pub async fn process_feeds_with_progress(
         let mut entries = feed.entries;
         entries.sort_by(|a, b| b.published.cmp(&a.published)); 
 
-        // determine how many entries to process based on last_processed timestamp
-        let entries_limit = match &link_info.last_processed {
-            None => {
+        // a configured limit wins, otherwise it depends on the last_processed timestamp
+        let entries_limit = match (link_info.entry_limit, &link_info.last_processed) {
+            (Some(limit), _) => {
+                log::info!("Using configured entry limit for {}: {}", link_info.company, limit);
+                limit.max(1) as usize
+            }
+            (None, None) => {
                 log::info!("First time processing {}, fetching all entries", link_info.company);
                 entries.len()  // for new companies, process all entries
             }
-            Some(last_processed) => {
+            (None, Some(last_processed)) => {
                 let time_since_last_process = Utc::now() - *last_processed;
                 if time_since_last_process > EXTENDED_PROCESSING_THRESHOLD {
                     log::info!(
@@ -175,7 +245,7 @@ pub async fn process_feeds_with_progress(
             // check for cancellation before processing each entry
             if cancel_token.is_cancelled() {
                 log::info!("RSS processing cancelled while processing entries");
-                return Ok(());
+                break 'feeds;
             }
 
             let title = entry.title
@@ -232,6 +302,15 @@ pub async fn process_feeds_with_progress(
                 .execute()
                 .await?;
 
+            pending.posts.push(IngestedPost {
+                title: post.title,
+                company: post.company,
+                link: post.link,
+                published_at: post.published_at,
+                summary: post.summary,
+                buzzwords: post.buzzwords.unwrap_or_default(),
+            });
+
             company_progress.new_posts += 1;
             progress_sender.send(company_progress.clone().into_event()).await?;
         }
@@ -245,17 +324,22 @@ pub async fn process_feeds_with_progress(
             .execute()
             .await?;
         
+        // make this company's new posts visible immediately
+        pending.flush();
+
         // Send final company status
         company_progress.status = "completed".to_string();
         company_progress.current_post = None;
         progress_sender.send(company_progress.into_event()).await?;
     }
 
-    // invalidate poasts cache to see new results immediately
-    invalidate_poasts_cache().await.map_err(|e| {
-        Box::new(std::io::Error::other(e.to_string())) as Box<dyn Error + Send + Sync>
-    })?;
-    
+    // posts from a feed interrupted by cancellation
+    pending.flush();
+
+    if cancel_token.is_cancelled() {
+        return Ok(());
+    }
+
     progress_sender
         .send(Ok(Event::default().data("[DONE]")))
         .await?;
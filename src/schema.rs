@@ -1,5 +1,21 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    api_keys (id) {
+        id -> Int4,
+        user_id -> Int4,
+        #[max_length = 100]
+        name -> Varchar,
+        #[max_length = 16]
+        prefix -> Varchar,
+        #[max_length = 64]
+        key_hash -> Varchar,
+        last_used_at -> Nullable<Timestamp>,
+        revoked_at -> Nullable<Timestamp>,
+        created_at -> Nullable<Timestamp>,
+    }
+}
+
 diesel::table! {
     canvas_rooms (id) {
         id -> Uuid,
@@ -19,6 +35,58 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    chat_messages (id) {
+        id -> Uuid,
+        session_id -> Uuid,
+        #[max_length = 20]
+        role -> Varchar,
+        content -> Text,
+        citations -> Nullable<Jsonb>,
+        created_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    chat_sessions (id) {
+        id -> Uuid,
+        user_id -> Int4,
+        #[max_length = 255]
+        title -> Varchar,
+        created_at -> Nullable<Timestamp>,
+        updated_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    digests (id) {
+        id -> Int4,
+        #[max_length = 10]
+        period -> Varchar,
+        #[max_length = 10]
+        grouped_by -> Varchar,
+        starts_on -> Date,
+        ends_on -> Date,
+        #[max_length = 255]
+        title -> Varchar,
+        markdown -> Text,
+        html -> Text,
+        post_count -> Int4,
+        sources -> Jsonb,
+        created_at -> Nullable<Timestamp>,
+        updated_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    followed_companies (user_id, company) {
+        user_id -> Int4,
+        #[max_length = 255]
+        company -> Varchar,
+        created_at -> Nullable<Timestamp>,
+    }
+}
+
 diesel::table! {
     game_sessions (id) {
         id -> Uuid,
@@ -49,6 +117,53 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    post_bookmarks (id) {
+        id -> Int4,
+        user_id -> Int4,
+        post_id -> Int4,
+        created_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    post_reads (id) {
+        id -> Int4,
+        user_id -> Int4,
+        post_id -> Int4,
+        created_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    rag_usage (user_id, day) {
+        user_id -> Int4,
+        day -> Date,
+        questions -> Int4,
+    }
+}
+
+diesel::table! {
+    reading_list_items (id) {
+        id -> Int4,
+        reading_list_id -> Int4,
+        post_id -> Int4,
+        created_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    reading_lists (id) {
+        id -> Int4,
+        user_id -> Int4,
+        #[max_length = 100]
+        name -> Varchar,
+        description -> Nullable<Text>,
+        created_at -> Nullable<Timestamp>,
+        updated_at -> Nullable<Timestamp>,
+    }
+}
+
 diesel::table! {
     room_players (id) {
         id -> Uuid,
@@ -79,6 +194,26 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    saved_searches (id) {
+        id -> Int4,
+        user_id -> Int4,
+        #[max_length = 100]
+        name -> Varchar,
+        #[max_length = 10]
+        kind -> Varchar,
+        query -> Text,
+        min_similarity -> Int4,
+        webhook_url -> Text,
+        #[max_length = 64]
+        webhook_secret -> Varchar,
+        enabled -> Bool,
+        last_matched_at -> Nullable<Timestamp>,
+        created_at -> Nullable<Timestamp>,
+        updated_at -> Nullable<Timestamp>,
+    }
+}
+
 diesel::table! {
     team_players (team_id, user_id) {
         team_id -> Uuid,
@@ -120,25 +255,64 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    webhook_deliveries (id) {
+        id -> Int4,
+        saved_search_id -> Int4,
+        post_link -> Text,
+        #[max_length = 10]
+        status -> Varchar,
+        attempts -> Int4,
+        response_status -> Nullable<Int4>,
+        error -> Nullable<Text>,
+        payload -> Jsonb,
+        created_at -> Nullable<Timestamp>,
+        updated_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::joinable!(api_keys -> users (user_id));
 diesel::joinable!(canvas_rooms -> users (created_by));
+diesel::joinable!(chat_messages -> chat_sessions (session_id));
+diesel::joinable!(chat_sessions -> users (user_id));
+diesel::joinable!(followed_companies -> users (user_id));
 diesel::joinable!(game_sessions -> canvas_rooms (room_id));
 diesel::joinable!(game_teams -> game_sessions (session_id));
+diesel::joinable!(post_bookmarks -> users (user_id));
+diesel::joinable!(post_reads -> users (user_id));
+diesel::joinable!(rag_usage -> users (user_id));
+diesel::joinable!(reading_list_items -> reading_lists (reading_list_id));
+diesel::joinable!(reading_lists -> users (user_id));
 diesel::joinable!(room_players -> canvas_rooms (room_id));
 diesel::joinable!(room_players -> users (user_id));
 diesel::joinable!(saved_canvases -> canvas_rooms (room_id));
 diesel::joinable!(saved_canvases -> game_sessions (session_id));
 diesel::joinable!(saved_canvases -> users (created_by));
+diesel::joinable!(saved_searches -> users (user_id));
 diesel::joinable!(team_players -> game_teams (team_id));
 diesel::joinable!(team_players -> users (user_id));
 diesel::joinable!(user_game_stats -> users (user_id));
+diesel::joinable!(webhook_deliveries -> saved_searches (saved_search_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    api_keys,
     canvas_rooms,
+    chat_messages,
+    chat_sessions,
+    digests,
+    followed_companies,
     game_sessions,
     game_teams,
+    post_bookmarks,
+    post_reads,
+    rag_usage,
+    reading_list_items,
+    reading_lists,
     room_players,
     saved_canvases,
+    saved_searches,
     team_players,
     user_game_stats,
     users,
+    webhook_deliveries,
 );
@@ -1,7 +1,311 @@
 use once_cell::sync::Lazy;
+use std::collections::HashMap;
 use std::sync::Mutex;
 use std::time::{Duration, Instant};
-use crate::components::poasts::Poast;
+use crate::components::poasts::{Poast, PostPage};
 
-pub static POASTS_CACHE: Lazy<Mutex<(Option<Vec<Poast>>, Instant)>> = Lazy::new(|| Mutex::new((None, Instant::now())));
-pub const CACHE_DURATION: Duration = Duration::from_secs(3600);
+/// `get_poasts` lists, keyed by the serialized filter.
+pub static POASTS_CACHE: Lazy<KeyedCache<Vec<Poast>>> =
+    Lazy::new(|| KeyedCache::new(256, Duration::from_secs(3600), Duration::from_secs(3600)));
+
+/// `search_poasts` pages, keyed by the serialized (search type, filter).
+pub static SEARCH_RESULTS_CACHE: Lazy<KeyedCache<PostPage>> =
+    Lazy::new(|| KeyedCache::new(256, Duration::from_secs(600), Duration::from_secs(600)));
+
+/// Ranked "for you" feeds, keyed by user and a fingerprint of what they've engaged with.
+pub static FOR_YOU_CACHE: Lazy<KeyedCache<Vec<Poast>>> =
+    Lazy::new(|| KeyedCache::new(256, Duration::from_secs(600), Duration::from_secs(600)));
+
+pub static COMPANIES_CACHE: Lazy<KeyedCache<Vec<String>>> =
+    Lazy::new(|| KeyedCache::new(1, Duration::from_secs(3600), Duration::from_secs(24 * 3600)));
+
+/// (embedding model, normalized query) -> query vector. Never served stale.
+pub static QUERY_EMBEDDING_CACHE: Lazy<KeyedCache<Vec<f32>>> =
+    Lazy::new(|| KeyedCache::new(512, Duration::from_secs(24 * 3600), Duration::ZERO));
+
+pub const ALL_POSTS_TAG: &str = "posts";
+/// Entries that aren't narrowed to specific companies, so any company's new posts can change them.
+pub const ANY_COMPANY_TAG: &str = "company:*";
+
+pub fn company_tag(company: &str) -> String {
+    format!("company:{}", company)
+}
+
+/// Tags for a cached post list: one per company it is limited to, or `ANY_COMPANY_TAG`.
+pub fn post_list_tags(companies: &[String]) -> Vec<String> {
+    let mut tags = vec![ALL_POSTS_TAG.to_string()];
+    if companies.is_empty() {
+        tags.push(ANY_COMPANY_TAG.to_string());
+    } else {
+        tags.extend(companies.iter().map(|c| company_tag(c)));
+    }
+    tags
+}
+
+pub enum Lookup<V> {
+    Fresh(V),
+    /// Past its TTL but inside the stale window. `refresh` is true for exactly one caller,
+    /// which should fetch a new value while everyone keeps getting this one.
+    Stale { value: V, refresh: bool },
+    Miss,
+}
+
+struct Entry<V> {
+    value: V,
+    inserted: Instant,
+    last_used: u64,
+    tags: Vec<String>,
+    refreshing: bool,
+}
+
+struct Inner<V> {
+    tick: u64,
+    entries: HashMap<String, Entry<V>>,
+    /// Bumped by every invalidation, so fetches can tell whether one happened while they ran.
+    generation: u64,
+    cleared_at: u64,
+    invalidated_at: HashMap<String, u64>,
+}
+
+impl<V> Inner<V> {
+    fn invalidated_since(&self, generation: u64, tags: &[String]) -> bool {
+        self.cleared_at > generation
+            || tags.iter().any(|t| self.invalidated_at.get(t).is_some_and(|&at| at > generation))
+    }
+}
+
+/// Size-bounded keyed cache with a freshness TTL, a stale-while-revalidate window after it,
+/// LRU eviction and tag-based invalidation.
+pub struct KeyedCache<V> {
+    capacity: usize,
+    ttl: Duration,
+    stale_for: Duration,
+    inner: Mutex<Inner<V>>,
+}
+
+impl<V: Clone> KeyedCache<V> {
+    pub fn new(capacity: usize, ttl: Duration, stale_for: Duration) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            ttl,
+            stale_for,
+            inner: Mutex::new(Inner {
+                tick: 0,
+                entries: HashMap::new(),
+                generation: 0,
+                cleared_at: 0,
+                invalidated_at: HashMap::new(),
+            }),
+        }
+    }
+
+    pub fn lookup(&self, key: &str) -> Lookup<V> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.tick += 1;
+        let tick = inner.tick;
+
+        let Some(entry) = inner.entries.get_mut(key) else {
+            return Lookup::Miss;
+        };
+
+        let age = entry.inserted.elapsed();
+        if age < self.ttl {
+            entry.last_used = tick;
+            return Lookup::Fresh(entry.value.clone());
+        }
+
+        if age < self.ttl + self.stale_for {
+            entry.last_used = tick;
+            let refresh = !entry.refreshing;
+            entry.refreshing = true;
+            return Lookup::Stale { value: entry.value.clone(), refresh };
+        }
+
+        inner.entries.remove(key);
+        Lookup::Miss
+    }
+
+    /// The cached value if it is still fresh.
+    pub fn get(&self, key: &str) -> Option<V> {
+        match self.lookup(key) {
+            Lookup::Fresh(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Take before starting a fetch and pass to `insert_since` with its result.
+    pub fn generation(&self) -> u64 {
+        self.inner.lock().unwrap().generation
+    }
+
+    pub fn insert(&self, key: String, value: V, tags: Vec<String>) {
+        let mut inner = self.inner.lock().unwrap();
+        self.insert_locked(&mut inner, key, value, tags);
+    }
+
+    /// Insert a value fetched after `generation`, unless `clear` or an invalidation of one of
+    /// its tags has happened since, in which case it may predate that change and is dropped.
+    /// Returns whether it was inserted.
+    pub fn insert_since(&self, generation: u64, key: String, value: V, tags: Vec<String>) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.invalidated_since(generation, &tags) {
+            return false;
+        }
+        self.insert_locked(&mut inner, key, value, tags);
+        true
+    }
+
+    fn insert_locked(&self, inner: &mut Inner<V>, key: String, value: V, tags: Vec<String>) {
+        inner.tick += 1;
+        let tick = inner.tick;
+
+        if !inner.entries.contains_key(&key) && inner.entries.len() >= self.capacity {
+            let max_age = self.ttl + self.stale_for;
+            inner.entries.retain(|_, entry| entry.inserted.elapsed() < max_age);
+
+            if inner.entries.len() >= self.capacity {
+                let oldest = inner
+                    .entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.last_used)
+                    .map(|(k, _)| k.clone());
+                if let Some(oldest) = oldest {
+                    inner.entries.remove(&oldest);
+                }
+            }
+        }
+
+        inner.entries.insert(key, Entry {
+            value,
+            inserted: Instant::now(),
+            last_used: tick,
+            tags,
+            refreshing: false,
+        });
+    }
+
+    /// Let another caller retry the refresh after a failed one.
+    pub fn refresh_failed(&self, key: &str) {
+        if let Some(entry) = self.inner.lock().unwrap().entries.get_mut(key) {
+            entry.refreshing = false;
+        }
+    }
+
+    /// Drop every entry carrying any of `tags`. Returns how many were removed.
+    pub fn invalidate_tags(&self, tags: &[String]) -> usize {
+        let mut inner = self.inner.lock().unwrap();
+        inner.generation += 1;
+        let generation = inner.generation;
+        for tag in tags {
+            inner.invalidated_at.insert(tag.clone(), generation);
+        }
+
+        let before = inner.entries.len();
+        inner.entries.retain(|_, entry| !entry.tags.iter().any(|t| tags.contains(t)));
+        before - inner.entries.len()
+    }
+
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.generation += 1;
+        inner.cleared_at = inner.generation;
+        inner.entries.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl<V: Clone + Send + 'static> KeyedCache<V> {
+    /// Return the cached value for `key`, fetching on a miss. Stale hits are returned immediately
+    /// while one background task refreshes the entry. A fetch that overlaps an invalidation of
+    /// its tags is returned to its caller but not cached.
+    pub async fn get_or_fetch<F, Fut, E>(
+        &'static self,
+        key: String,
+        tags: Vec<String>,
+        fetch: F,
+    ) -> Result<V, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<V, E>> + Send + 'static,
+        E: std::fmt::Display,
+    {
+        match self.lookup(&key) {
+            Lookup::Fresh(value) => Ok(value),
+            Lookup::Stale { value, refresh } => {
+                if refresh {
+                    let generation = self.generation();
+                    let future = fetch();
+                    tokio::spawn(async move {
+                        match future.await {
+                            Ok(fresh) => {
+                                if !self.insert_since(generation, key, fresh, tags) {
+                                    log::debug!("Dropped a background refresh that raced an invalidation");
+                                }
+                            }
+                            Err(e) => {
+                                log::warn!("Background cache refresh failed: {}", e);
+                                self.refresh_failed(&key);
+                            }
+                        }
+                    });
+                }
+                Ok(value)
+            }
+            Lookup::Miss => {
+                let generation = self.generation();
+                let value = fetch().await?;
+                self.insert_since(generation, key, value.clone(), tags);
+                Ok(value)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache() -> KeyedCache<i32> {
+        KeyedCache::new(8, Duration::from_secs(60), Duration::from_secs(60))
+    }
+
+    #[test]
+    fn test_insert_since_drops_results_that_raced_an_invalidation() {
+        let cache = cache();
+        let tags = post_list_tags(&["Acme".to_string()]);
+
+        let started = cache.generation();
+        cache.invalidate_tags(&[company_tag("Acme")]);
+        assert!(!cache.insert_since(started, "acme".to_string(), 1, tags.clone()));
+        assert!(cache.get("acme").is_none());
+
+        // a fetch started after the invalidation is kept
+        assert!(cache.insert_since(cache.generation(), "acme".to_string(), 2, tags));
+        assert_eq!(cache.get("acme"), Some(2));
+    }
+
+    #[test]
+    fn test_insert_since_ignores_unrelated_tags() {
+        let cache = cache();
+        let started = cache.generation();
+        cache.invalidate_tags(&[company_tag("Other")]);
+        assert!(cache.insert_since(started, "acme".to_string(), 1, post_list_tags(&["Acme".to_string()])));
+    }
+
+    #[test]
+    fn test_insert_since_drops_results_that_raced_a_clear() {
+        let cache = cache();
+        let started = cache.generation();
+        cache.clear();
+        assert!(!cache.insert_since(started, "key".to_string(), 1, Vec::new()));
+        assert!(cache.is_empty());
+    }
+}
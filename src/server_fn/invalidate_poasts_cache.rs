@@ -1,18 +1,38 @@
 use leptos::prelude::*;
 use server_fn::codec::PostUrl;
 
+/// Drop cached post lists and search results. With `company`, only entries that could include
+/// that company's posts are dropped; without it everything goes, including query embeddings.
 #[server(
     name = InvalidatePoastsCache,
     prefix = "/api",
     endpoint = "invalidate_poasts_cache",
     input = PostUrl
 )]
-pub async fn invalidate_poasts_cache() -> Result<(), ServerFnError> {
-    use crate::server_fn::cache::POASTS_CACHE;
+pub async fn invalidate_poasts_cache(company: Option<String>) -> Result<(), ServerFnError> {
+    use crate::server_fn::cache::{
+        company_tag, ANY_COMPANY_TAG, COMPANIES_CACHE, FOR_YOU_CACHE, POASTS_CACHE,
+        QUERY_EMBEDDING_CACHE, SEARCH_RESULTS_CACHE,
+    };
 
-    let mut cache = POASTS_CACHE.lock().unwrap();
-    *cache = (None, std::time::Instant::now());
+    match company {
+        Some(company) => {
+            let tags = [company_tag(&company), ANY_COMPANY_TAG.to_string()];
+            let removed = POASTS_CACHE.invalidate_tags(&tags)
+                + SEARCH_RESULTS_CACHE.invalidate_tags(&tags)
+                + FOR_YOU_CACHE.invalidate_tags(&tags);
+            log::info!("Poasts cache invalidated for {} ({} entries)", company, removed);
+        }
+        None => {
+            POASTS_CACHE.clear();
+            SEARCH_RESULTS_CACHE.clear();
+            FOR_YOU_CACHE.clear();
+            COMPANIES_CACHE.clear();
+            // pipelines call this after re-embedding, possibly with a new model
+            QUERY_EMBEDDING_CACHE.clear();
+            log::info!("Poasts cache invalidated");
+        }
+    }
 
-    log::info!("Poasts cache invalidated");
     Ok(())
 }
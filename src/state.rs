@@ -12,6 +12,7 @@ cfg_if! {
         use crate::database::db::DbPool;
         use crate::auth::oauth::OAuthState;
         use crate::handlers::CanvasRoomManager;
+        use crate::rate_limit::{AskLimits, RateLimiter, API_REQUESTS_PER_MINUTE};
 
         #[derive(FromRef, Clone)]
         pub struct AppState {
@@ -22,7 +23,8 @@ cfg_if! {
             pub drawing_tx: broadcast::Sender<String>,
             pub user_count: Arc<Mutex<usize>>,
             pub canvas_manager: Option<CanvasRoomManager>,
-
+            pub ask_limits: AskLimits,
+            pub api_limiter: Arc<RateLimiter>,
         }
 
         impl AppState {
@@ -36,6 +38,8 @@ cfg_if! {
                     drawing_tx,
                     user_count: Arc::new(Mutex::new(0)),
                     canvas_manager: Some(CanvasRoomManager::new()),
+                    ask_limits: AskLimits::new(),
+                    api_limiter: Arc::new(RateLimiter::new(API_REQUESTS_PER_MINUTE, std::time::Duration::from_secs(60))),
                 }
             }
         }
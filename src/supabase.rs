@@ -17,3 +17,22 @@ static CLIENT: Lazy<Postgrest> = Lazy::new(|| {
 pub fn get_client() -> &'static Postgrest {
     &CLIENT
 }
+
+/// A value quoted for use inside an `or=(…)`/`and=(…)` filter, so commas, parentheses and
+/// quotes in user input stay part of the value instead of ending the condition.
+pub fn quoted_filter_value(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quoted_filter_value() {
+        assert_eq!(quoted_filter_value("%rust%"), "\"%rust%\"");
+        assert_eq!(quoted_filter_value("a,b(c)"), "\"a,b(c)\"");
+        assert_eq!(quoted_filter_value("say \"hi\""), "\"say \\\"hi\\\"\"");
+        assert_eq!(quoted_filter_value("back\\slash"), "\"back\\\\slash\"");
+    }
+}
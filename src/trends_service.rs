@@ -0,0 +1,518 @@
+#[cfg(feature = "ssr")]
+pub mod trends {
+    use async_openai::{
+        config::OpenAIConfig,
+        types::{
+            ChatCompletionRequestSystemMessage, ChatCompletionRequestUserMessage,
+            CreateChatCompletionRequest, ResponseFormat,
+        },
+        Client,
+    };
+    use log::{debug, error, info, warn};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use serde::Deserialize;
+    use serde_json::json;
+    use std::collections::HashMap;
+    use thiserror::Error;
+    use tokio_util::sync::CancellationToken;
+
+    use crate::embedding_service::embeddings::EmbeddingColumn;
+    use crate::vector_search_service::vector_search::{fetch_vectors, VectorSearchError};
+
+    // will need to run this in supabase
+    const _MIGRATION_SQL: &str = r#"
+CREATE TABLE topic_clusters (
+    id INTEGER PRIMARY KEY,
+    label TEXT NOT NULL,
+    buzzwords TEXT[] NOT NULL DEFAULT '{}',
+    size INTEGER NOT NULL,
+    computed_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE TABLE topic_cluster_months (
+    cluster_id INTEGER NOT NULL REFERENCES topic_clusters(id) ON DELETE CASCADE,
+    month TEXT NOT NULL,
+    post_count INTEGER NOT NULL,
+    companies JSONB NOT NULL DEFAULT '{}',
+    PRIMARY KEY (cluster_id, month)
+);
+
+CREATE TABLE topic_cluster_posts (
+    cluster_id INTEGER NOT NULL REFERENCES topic_clusters(id) ON DELETE CASCADE,
+    link TEXT NOT NULL REFERENCES poasts(link) ON DELETE CASCADE,
+    PRIMARY KEY (cluster_id, link)
+);
+
+-- swaps in a new clustering run in one transaction, so readers never see a half-written run
+CREATE OR REPLACE FUNCTION replace_topic_clusters(clusters jsonb, months jsonb, posts jsonb)
+RETURNS void
+LANGUAGE plpgsql AS $$
+BEGIN
+    DELETE FROM topic_clusters WHERE id >= 0;
+
+    INSERT INTO topic_clusters (id, label, buzzwords, size)
+    SELECT c.id, c.label, c.buzzwords, c.size
+    FROM jsonb_to_recordset(clusters) AS c(id int, label text, buzzwords text[], size int);
+
+    INSERT INTO topic_cluster_months (cluster_id, month, post_count, companies)
+    SELECT m.cluster_id, m.month, m.post_count, m.companies
+    FROM jsonb_to_recordset(months) AS m(cluster_id int, month text, post_count int, companies jsonb);
+
+    -- posts deleted while clustering ran are skipped rather than failing the swap
+    INSERT INTO topic_cluster_posts (cluster_id, link)
+    SELECT p.cluster_id, p.link
+    FROM jsonb_to_recordset(posts) AS p(cluster_id int, link text)
+    JOIN poasts ON poasts.link = p.link;
+END;
+$$;
+"#;
+
+    const LABEL_SAMPLE_TITLES: usize = 8;
+    const TOP_BUZZWORDS: usize = 10;
+
+    #[derive(Error, Debug)]
+    pub enum TrendsError {
+        #[error("Supabase error: {0}")]
+        Supabase(String),
+
+        #[error("JSON error: {0}")]
+        Json(#[from] serde_json::Error),
+
+        #[error("Vector search error: {0}")]
+        VectorSearch(#[from] VectorSearchError),
+
+        #[error("Clustering cancelled")]
+        Cancelled,
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct ClusteringOptions {
+        /// Number of clusters; defaults to sqrt(n / 2) clamped to 8..=40.
+        pub k: Option<usize>,
+        pub max_iterations: usize,
+        pub seed: u64,
+    }
+
+    impl Default for ClusteringOptions {
+        fn default() -> Self {
+            Self {
+                k: None,
+                max_iterations: 50,
+                seed: 42,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct ClusteringSummary {
+        pub posts: usize,
+        pub clusters: usize,
+    }
+
+    #[derive(Debug, Clone, Deserialize)]
+    struct PostMeta {
+        link: String,
+        title: String,
+        company: String,
+        published_at: String,
+        buzzwords: Option<Vec<String>>,
+    }
+
+    #[derive(Deserialize)]
+    struct ClusterLabel {
+        label: String,
+    }
+
+    struct Cluster {
+        members: Vec<usize>,
+        buzzwords: Vec<String>,
+        label: String,
+    }
+
+    fn normalize(vector: &mut [f32]) {
+        let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            vector.iter_mut().for_each(|x| *x /= norm);
+        }
+    }
+
+    fn dot(a: &[f32], b: &[f32]) -> f32 {
+        a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
+    }
+
+    /// Spherical k-means with k-means++ seeding. Vectors must already be unit length.
+    /// Returns the cluster index of every vector.
+    pub fn kmeans(vectors: &[Vec<f32>], k: usize, max_iterations: usize, rng: &mut StdRng) -> Vec<usize> {
+        if vectors.is_empty() || k == 0 {
+            return Vec::new();
+        }
+        let k = k.min(vectors.len());
+
+        let mut centroids: Vec<Vec<f32>> = vec![vectors[rng.gen_range(0..vectors.len())].clone()];
+        let mut nearest: Vec<f32> = vectors.iter().map(|v| 1.0 - dot(v, &centroids[0])).collect();
+
+        while centroids.len() < k {
+            let weights: Vec<f32> = nearest.iter().map(|d| d.max(0.0).powi(2)).collect();
+            let total: f32 = weights.iter().sum();
+            let next = if total <= 0.0 {
+                rng.gen_range(0..vectors.len())
+            } else {
+                let mut target = rng.gen::<f32>() * total;
+                weights
+                    .iter()
+                    .position(|w| {
+                        target -= w;
+                        target <= 0.0
+                    })
+                    .unwrap_or(vectors.len() - 1)
+            };
+
+            centroids.push(vectors[next].clone());
+            let newest = centroids.last().unwrap();
+            for (d, v) in nearest.iter_mut().zip(vectors) {
+                *d = d.min(1.0 - dot(v, newest));
+            }
+        }
+
+        let mut assignments = vec![usize::MAX; vectors.len()];
+        for iteration in 0..max_iterations {
+            let mut changed = 0;
+            for (i, v) in vectors.iter().enumerate() {
+                let best = centroids
+                    .iter()
+                    .enumerate()
+                    .map(|(c, centroid)| (c, dot(v, centroid)))
+                    .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+                    .map(|(c, _)| c)
+                    .unwrap_or(0);
+                if assignments[i] != best {
+                    assignments[i] = best;
+                    changed += 1;
+                }
+            }
+
+            debug!("k-means iteration {}: {} reassignments", iteration + 1, changed);
+            if changed == 0 {
+                break;
+            }
+
+            let dim = vectors[0].len();
+            let mut sums = vec![vec![0.0f32; dim]; k];
+            let mut counts = vec![0usize; k];
+            for (v, &c) in vectors.iter().zip(&assignments) {
+                counts[c] += 1;
+                sums[c].iter_mut().zip(v).for_each(|(s, x)| *s += x);
+            }
+
+            for c in 0..k {
+                if counts[c] == 0 {
+                    // reseed an empty cluster with the point furthest from its centroid
+                    let furthest = vectors
+                        .iter()
+                        .zip(&assignments)
+                        .enumerate()
+                        .min_by(|(_, (a, &ca)), (_, (b, &cb))| {
+                            dot(a, &centroids[ca])
+                                .partial_cmp(&dot(b, &centroids[cb]))
+                                .unwrap_or(std::cmp::Ordering::Equal)
+                        })
+                        .map(|(i, _)| i)
+                        .unwrap_or(0);
+                    centroids[c] = vectors[furthest].clone();
+                } else {
+                    normalize(&mut sums[c]);
+                    centroids[c] = std::mem::take(&mut sums[c]);
+                }
+            }
+        }
+
+        assignments
+    }
+
+    async fn fetch_post_metadata() -> Result<HashMap<String, PostMeta>, TrendsError> {
+        let supabase = crate::supabase::get_client();
+        let page_size = 1000;
+        let mut posts = HashMap::new();
+        let mut current_page = 0;
+
+        loop {
+            let start = current_page * page_size;
+            let response = supabase
+                .from("poasts")
+                .select("link, title, company, published_at, buzzwords")
+                .range(start, start + page_size - 1)
+                .execute()
+                .await
+                .map_err(|e| TrendsError::Supabase(e.to_string()))?;
+
+            let body = response.text()
+                .await
+                .map_err(|e| TrendsError::Supabase(e.to_string()))?;
+            let page: Vec<PostMeta> = serde_json::from_str(&body)?;
+
+            if page.is_empty() {
+                break;
+            }
+            posts.extend(page.into_iter().map(|p| (p.link.clone(), p)));
+            current_page += 1;
+        }
+
+        Ok(posts)
+    }
+
+    fn top_buzzwords(members: &[&PostMeta]) -> Vec<String> {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for post in members {
+            for word in post.buzzwords.iter().flatten() {
+                *counts.entry(word.trim().to_lowercase()).or_insert(0) += 1;
+            }
+        }
+
+        let mut counts: Vec<(String, usize)> = counts.into_iter().filter(|(w, _)| !w.is_empty()).collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        counts.into_iter().take(TOP_BUZZWORDS).map(|(w, _)| w).collect()
+    }
+
+    async fn label_cluster(client: &Client<OpenAIConfig>, buzzwords: &[String], titles: &[&str]) -> String {
+        let fallback = buzzwords.iter().take(3).cloned().collect::<Vec<_>>().join(", ");
+
+        let request = CreateChatCompletionRequest {
+            model: "gpt-4o-mini".to_string(),
+            messages: vec![
+                ChatCompletionRequestSystemMessage {
+                    content: "You name topics for a tech blog aggregator.".into(),
+                    name: None,
+                }
+                .into(),
+                ChatCompletionRequestUserMessage {
+                    content: format!(
+                        "These engineering blog posts were grouped together. Give the group a short topic label \
+                        (2-5 words). Respond only in JSON with one field 'label'.\n\n\
+                        Most common buzzwords: {}\n\nExample titles:\n- {}",
+                        buzzwords.join(", "),
+                        titles.join("\n- ")
+                    )
+                    .into(),
+                    name: None,
+                }
+                .into(),
+            ],
+            response_format: Some(ResponseFormat::JsonObject),
+            max_completion_tokens: Some(60),
+            ..Default::default()
+        };
+
+        match client.chat().create(request).await {
+            Ok(response) => {
+                let content = response
+                    .choices
+                    .first()
+                    .and_then(|choice| choice.message.content.clone())
+                    .unwrap_or_default();
+                match serde_json::from_str::<ClusterLabel>(&content) {
+                    Ok(parsed) if !parsed.label.trim().is_empty() => parsed.label.trim().to_string(),
+                    _ => {
+                        warn!("Unusable cluster label response: {}", content);
+                        fallback
+                    }
+                }
+            }
+            Err(e) => {
+                error!("Failed to label cluster: {}", e);
+                fallback
+            }
+        }
+    }
+
+    /// Cluster every post's MiniLM vector, label each cluster from its buzzwords, and atomically
+    /// replace the stored topics with per-month sizes and company counts.
+    pub async fn run_topic_clustering(
+        options: ClusteringOptions,
+        cancel_token: CancellationToken,
+    ) -> Result<ClusteringSummary, TrendsError> {
+        let minilm = fetch_vectors(EmbeddingColumn::MiniLm).await?;
+        let metadata = fetch_post_metadata().await?;
+
+        let (links, mut vectors): (Vec<String>, Vec<Vec<f32>>) = minilm
+            .into_iter()
+            .filter(|(link, _)| metadata.contains_key(link))
+            .unzip();
+        vectors.iter_mut().for_each(|v| normalize(v));
+
+        if vectors.is_empty() {
+            info!("No MiniLM vectors to cluster");
+            return Ok(ClusteringSummary { posts: 0, clusters: 0 });
+        }
+
+        let k = options
+            .k
+            .unwrap_or_else(|| ((vectors.len() as f64 / 2.0).sqrt() as usize).clamp(8, 40));
+        info!("Clustering {} posts into {} topics", vectors.len(), k);
+
+        let mut rng = StdRng::seed_from_u64(options.seed);
+        let max_iterations = options.max_iterations;
+        let assignments = tokio::task::spawn_blocking(move || kmeans(&vectors, k, max_iterations, &mut rng))
+        .await
+        .map_err(|e| TrendsError::Supabase(format!("clustering task failed: {}", e)))?;
+
+        if cancel_token.is_cancelled() {
+            return Err(TrendsError::Cancelled);
+        }
+
+        let mut member_lists: Vec<Vec<usize>> = vec![Vec::new(); k];
+        for (i, &c) in assignments.iter().enumerate() {
+            member_lists[c].push(i);
+        }
+
+        let openai = Client::with_config(OpenAIConfig::default());
+        let mut clusters = Vec::new();
+        for members in member_lists.into_iter().filter(|m| !m.is_empty()) {
+            if cancel_token.is_cancelled() {
+                return Err(TrendsError::Cancelled);
+            }
+
+            let posts: Vec<&PostMeta> = members.iter().map(|&i| &metadata[&links[i]]).collect();
+            let buzzwords = top_buzzwords(&posts);
+            let mut recent = posts.clone();
+            recent.sort_by(|a, b| b.published_at.cmp(&a.published_at));
+            let titles: Vec<&str> = recent.iter().take(LABEL_SAMPLE_TITLES).map(|p| p.title.as_str()).collect();
+
+            let label = label_cluster(&openai, &buzzwords, &titles).await;
+            info!("Cluster of {} posts labelled '{}'", members.len(), label);
+            clusters.push(Cluster { members, buzzwords, label });
+        }
+
+        // largest topics get the lowest ids
+        clusters.sort_by(|a, b| b.members.len().cmp(&a.members.len()));
+        store_clusters(&clusters, &links, &metadata).await?;
+
+        Ok(ClusteringSummary {
+            posts: links.len(),
+            clusters: clusters.len(),
+        })
+    }
+
+    async fn store_clusters(
+        clusters: &[Cluster],
+        links: &[String],
+        metadata: &HashMap<String, PostMeta>,
+    ) -> Result<(), TrendsError> {
+        let supabase = crate::supabase::get_client();
+
+        let mut cluster_rows = Vec::new();
+        let mut month_rows = Vec::new();
+        let mut post_rows = Vec::new();
+
+        for (id, cluster) in clusters.iter().enumerate() {
+            cluster_rows.push(json!({
+                "id": id,
+                "label": cluster.label,
+                "buzzwords": cluster.buzzwords,
+                "size": cluster.members.len(),
+            }));
+
+            let mut months: HashMap<String, (usize, HashMap<String, usize>)> = HashMap::new();
+            for &i in &cluster.members {
+                let post = &metadata[&links[i]];
+                let month: String = post.published_at.chars().take(7).collect();
+                let entry = months.entry(month).or_default();
+                entry.0 += 1;
+                *entry.1.entry(post.company.clone()).or_insert(0) += 1;
+                post_rows.push(json!({ "cluster_id": id, "link": post.link }));
+            }
+
+            for (month, (count, companies)) in months {
+                month_rows.push(json!({
+                    "cluster_id": id,
+                    "month": month,
+                    "post_count": count,
+                    "companies": companies,
+                }));
+            }
+        }
+
+        // one RPC so the old run is only replaced once the new one is fully written
+        let response = supabase
+            .rpc(
+                "replace_topic_clusters",
+                json!({
+                    "clusters": cluster_rows,
+                    "months": month_rows,
+                    "posts": post_rows,
+                })
+                .to_string(),
+            )
+            .execute()
+            .await
+            .map_err(|e| TrendsError::Supabase(e.to_string()))?;
+
+        if !response.status().is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(TrendsError::Supabase(format!("replace_topic_clusters failed: {}", body)));
+        }
+
+        info!("Stored {} topic clusters", clusters.len());
+        Ok(())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn unit(v: &[f32]) -> Vec<f32> {
+            let mut v = v.to_vec();
+            normalize(&mut v);
+            v
+        }
+
+        #[test]
+        fn test_kmeans_separates_groups() {
+            let vectors: Vec<Vec<f32>> = [
+                [1.0, 0.1, 0.0],
+                [0.9, 0.0, 0.1],
+                [1.0, 0.05, 0.05],
+                [0.0, 1.0, 0.1],
+                [0.1, 0.9, 0.0],
+                [0.0, 0.0, 1.0],
+                [0.1, 0.0, 0.9],
+            ]
+            .iter()
+            .map(|v| unit(v))
+            .collect();
+
+            let assignments = kmeans(&vectors, 3, 50, &mut StdRng::seed_from_u64(42));
+            assert_eq!(assignments.len(), vectors.len());
+            assert!(assignments[0] == assignments[1] && assignments[1] == assignments[2]);
+            assert_eq!(assignments[3], assignments[4]);
+            assert_eq!(assignments[5], assignments[6]);
+            assert_ne!(assignments[0], assignments[3]);
+            assert_ne!(assignments[0], assignments[5]);
+            assert_ne!(assignments[3], assignments[5]);
+        }
+
+        #[test]
+        fn test_kmeans_is_deterministic_per_seed() {
+            let vectors: Vec<Vec<f32>> = (0..40)
+                .map(|i| unit(&[(i as f32).sin(), (i as f32 * 0.7).cos(), (i % 5) as f32 * 0.2]))
+                .collect();
+
+            let first = kmeans(&vectors, 4, 50, &mut StdRng::seed_from_u64(7));
+            let second = kmeans(&vectors, 4, 50, &mut StdRng::seed_from_u64(7));
+            assert_eq!(first, second);
+            assert!(first.iter().all(|&c| c < 4));
+        }
+
+        #[test]
+        fn test_kmeans_edge_cases() {
+            let mut rng = StdRng::seed_from_u64(1);
+            assert!(kmeans(&[], 3, 10, &mut rng).is_empty());
+            assert!(kmeans(&[unit(&[1.0, 0.0])], 0, 10, &mut rng).is_empty());
+
+            // k is capped at the number of vectors
+            let vectors = vec![unit(&[1.0, 0.0]), unit(&[0.0, 1.0])];
+            let assignments = kmeans(&vectors, 5, 10, &mut rng);
+            assert_eq!(assignments.len(), 2);
+            assert_ne!(assignments[0], assignments[1]);
+        }
+    }
+}
@@ -0,0 +1,826 @@
+#[cfg(feature = "ssr")]
+pub mod hnsw;
+
+#[cfg(feature = "ssr")]
+pub mod vector_search {
+    use log::{debug, error, info, warn};
+    use once_cell::sync::Lazy;
+    use serde::Deserialize;
+    use serde_json::json;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use thiserror::Error;
+    use tokio::sync::{Mutex, RwLock};
+
+    use crate::components::poasts::PostEmbedding;
+    use crate::embedding_service::embeddings::{embedding_version, EmbeddingColumn};
+    use crate::supabase::quoted_filter_value;
+    use crate::vector_search_service::hnsw::Hnsw;
+
+    // will need to run this in supabase (pgvector must be enabled for the RPC path)
+    const _MIGRATION_SQL: &str = r#"
+CREATE EXTENSION IF NOT EXISTS vector;
+
+CREATE OR REPLACE FUNCTION match_post_embeddings_openai(query_embedding vector(1536), match_count int)
+RETURNS TABLE (link text, similarity float)
+LANGUAGE sql STABLE AS $$
+    SELECT pe.link, 1 - (pe.embedding <=> query_embedding) AS similarity
+    FROM post_embeddings pe
+    WHERE pe.embedding IS NOT NULL
+    ORDER BY pe.embedding <=> query_embedding
+    LIMIT match_count;
+$$;
+
+CREATE OR REPLACE FUNCTION match_post_embeddings_minilm(query_embedding vector(384), match_count int)
+RETURNS TABLE (link text, similarity float)
+LANGUAGE sql STABLE AS $$
+    SELECT pe.link, 1 - (pe.minilm <=> query_embedding) AS similarity
+    FROM post_embeddings pe
+    WHERE pe.minilm IS NOT NULL
+    ORDER BY pe.minilm <=> query_embedding
+    LIMIT match_count;
+$$;
+
+CREATE INDEX IF NOT EXISTS post_embeddings_minilm_idx ON post_embeddings USING hnsw (minilm vector_cosine_ops);
+
+ALTER TABLE poasts ADD COLUMN IF NOT EXISTS search_tsv tsvector GENERATED ALWAYS AS (
+    setweight(to_tsvector('english', coalesce(title, '')), 'A') ||
+    setweight(to_tsvector('english', coalesce(summary, '')), 'B') ||
+    setweight(to_tsvector('english', coalesce(full_text, '')), 'C')
+) STORED;
+
+CREATE INDEX IF NOT EXISTS poasts_search_tsv_idx ON poasts USING gin (search_tsv);
+
+CREATE OR REPLACE FUNCTION search_posts_lexical(query text, match_count int)
+RETURNS TABLE (link text, rank real)
+LANGUAGE sql STABLE AS $$
+    SELECT p.link, ts_rank_cd(p.search_tsv, websearch_to_tsquery('english', query), 32) AS rank
+    FROM poasts p
+    WHERE p.search_tsv @@ websearch_to_tsquery('english', query)
+    ORDER BY rank DESC
+    LIMIT match_count;
+$$;
+"#;
+
+    /// Flipped off once Postgres tells us the match functions (or the extension) don't exist,
+    /// so later queries go straight to the in-process ANN index.
+    static PGVECTOR_AVAILABLE: AtomicBool = AtomicBool::new(true);
+
+    /// Same idea for the full-text ranking function; without it lexical matching uses `ilike`.
+    static LEXICAL_RPC_AVAILABLE: AtomicBool = AtomicBool::new(true);
+
+    /// Searches clone the `Arc` and release the lock; writers copy on write if a search or a
+    /// save still holds the old indexes.
+    static ANN_INDEXES: Lazy<RwLock<Option<Arc<AnnIndexes>>>> = Lazy::new(|| RwLock::new(None));
+
+    /// Serializes loading and reconciling, which do their slow work outside `ANN_INDEXES`.
+    static ANN_MAINTENANCE: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+    const ANN_INDEX_DIR: &str = "data/ann_index";
+    const OPENAI_DIMENSIONS: usize = 1536;
+    const MINILM_DIMENSIONS: usize = 384;
+    const HNSW_M: usize = 16;
+    const HNSW_EF_CONSTRUCTION: usize = 200;
+    const HNSW_EF_SEARCH: usize = 64;
+
+    /// Standard RRF damping constant; keeps a single list's top hit from dominating the fusion.
+    pub const RRF_K: f32 = 60.0;
+    const HYBRID_CANDIDATES: usize = 50;
+
+    #[derive(Error, Debug)]
+    pub enum VectorSearchError {
+        #[error("Supabase error: {0}")]
+        Supabase(String),
+
+        #[error("JSON error: {0}")]
+        Json(#[from] serde_json::Error),
+
+        #[error("Index error: {0}")]
+        Index(String),
+    }
+
+    #[derive(Debug, Clone, Deserialize)]
+    pub struct ScoredLink {
+        pub link: String,
+        pub similarity: f32,
+    }
+
+    #[derive(Debug, Clone, Deserialize)]
+    struct LexicalMatch {
+        link: String,
+    }
+
+    fn rpc_name(column: EmbeddingColumn) -> &'static str {
+        match column {
+            EmbeddingColumn::OpenAI => "match_post_embeddings_openai",
+            EmbeddingColumn::MiniLm => "match_post_embeddings_minilm",
+        }
+    }
+
+    /// Nearest posts to `query` in the given vector space, best first.
+    pub async fn nearest_links(
+        column: EmbeddingColumn,
+        query: &[f32],
+        limit: usize,
+    ) -> Result<Vec<ScoredLink>, VectorSearchError> {
+        if PGVECTOR_AVAILABLE.load(Ordering::Relaxed) {
+            match pgvector_nearest(column, query, limit).await {
+                Ok(Some(results)) => return Ok(results),
+                Ok(None) => {
+                    warn!("pgvector match functions unavailable, using in-process index from now on");
+                    PGVECTOR_AVAILABLE.store(false, Ordering::Relaxed);
+                }
+                Err(e) => {
+                    warn!("pgvector search failed, falling back to in-process index: {}", e);
+                }
+            }
+        }
+
+        ann_nearest(column, query, limit).await
+    }
+
+    /// Returns `Ok(None)` when the database lacks the extension or the match functions.
+    async fn pgvector_nearest(
+        column: EmbeddingColumn,
+        query: &[f32],
+        limit: usize,
+    ) -> Result<Option<Vec<ScoredLink>>, VectorSearchError> {
+        let supabase = crate::supabase::get_client();
+
+        let response = supabase
+            .rpc(rpc_name(column), json!({
+                "query_embedding": query,
+                "match_count": limit,
+            }).to_string())
+            .execute()
+            .await
+            .map_err(|e| VectorSearchError::Supabase(e.to_string()))?;
+
+        let status = response.status();
+        let body = response.text()
+            .await
+            .map_err(|e| VectorSearchError::Supabase(e.to_string()))?;
+
+        if !status.is_success() {
+            if is_missing_function(status.as_u16(), &body) {
+                return Ok(None);
+            }
+            return Err(VectorSearchError::Supabase(format!("{}: {}", status, body)));
+        }
+
+        let results: Vec<ScoredLink> = serde_json::from_str(&body)?;
+        debug!("pgvector returned {} matches from {}", results.len(), rpc_name(column));
+        Ok(Some(results))
+    }
+
+    // PGRST202: function not found, 42883: undefined function/operator, 42704: undefined type
+    fn is_missing_function(status: u16, body: &str) -> bool {
+        status == 404 || body.contains("PGRST202") || body.contains("42883") || body.contains("42704")
+    }
+
+    /// Full-text matches for `query`, best first. Uses `search_posts_lexical` (ts_rank_cd over
+    /// title, summary and full_text) and falls back to `ilike` when the function isn't installed.
+    pub async fn lexical_links(query: &str, limit: usize) -> Result<Vec<String>, VectorSearchError> {
+        if LEXICAL_RPC_AVAILABLE.load(Ordering::Relaxed) {
+            let supabase = crate::supabase::get_client();
+            let response = supabase
+                .rpc("search_posts_lexical", json!({
+                    "query": query,
+                    "match_count": limit,
+                }).to_string())
+                .execute()
+                .await
+                .map_err(|e| VectorSearchError::Supabase(e.to_string()))?;
+
+            let status = response.status();
+            let body = response.text()
+                .await
+                .map_err(|e| VectorSearchError::Supabase(e.to_string()))?;
+
+            if status.is_success() {
+                let matches: Vec<LexicalMatch> = serde_json::from_str(&body)?;
+                debug!("Full-text search returned {} matches", matches.len());
+                return Ok(matches.into_iter().map(|m| m.link).collect());
+            }
+
+            if is_missing_function(status.as_u16(), &body) {
+                warn!("search_posts_lexical unavailable, using ilike matching from now on");
+                LEXICAL_RPC_AVAILABLE.store(false, Ordering::Relaxed);
+            } else {
+                warn!("Full-text search failed, falling back to ilike: {}: {}", status, body);
+            }
+        }
+
+        ilike_links(query, limit).await
+    }
+
+    /// Posts whose title, summary or body contain any query term. Title hits rank above
+    /// summary-only hits, then by how many terms matched.
+    async fn ilike_links(query: &str, limit: usize) -> Result<Vec<String>, VectorSearchError> {
+        let terms: Vec<String> = query
+            .split_whitespace()
+            .map(|t| t.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+            .filter(|t| t.len() > 1)
+            .collect();
+
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let conditions = terms
+            .iter()
+            .flat_map(|t| {
+                let pattern = quoted_filter_value(&format!("%{}%", t));
+                [
+                    format!("title.ilike.{}", pattern),
+                    format!("summary.ilike.{}", pattern),
+                    format!("full_text.ilike.{}", pattern),
+                ]
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let supabase = crate::supabase::get_client();
+        let response = supabase
+            .from("poasts")
+            .select("link, title, summary")
+            .or(conditions)
+            .order("published_at.desc")
+            .limit(limit * 4)
+            .execute()
+            .await
+            .map_err(|e| VectorSearchError::Supabase(e.to_string()))?;
+
+        let body = response.text()
+            .await
+            .map_err(|e| VectorSearchError::Supabase(e.to_string()))?;
+        let rows: Vec<serde_json::Value> = serde_json::from_str(&body)?;
+
+        let mut scored: Vec<(String, usize)> = rows
+            .into_iter()
+            .filter_map(|row| {
+                let link = row.get("link")?.as_str()?.to_string();
+                let title = row.get("title").and_then(|v| v.as_str()).unwrap_or("").to_lowercase();
+                let summary = row.get("summary").and_then(|v| v.as_str()).unwrap_or("").to_lowercase();
+                let score = terms
+                    .iter()
+                    .map(|t| if title.contains(t.as_str()) { 3 } else if summary.contains(t.as_str()) { 1 } else { 0 })
+                    .sum();
+                Some((link, score))
+            })
+            .collect();
+
+        // stable sort keeps newest-first among equal scores
+        scored.sort_by(|a, b| b.1.cmp(&a.1));
+        scored.truncate(limit);
+        Ok(scored.into_iter().map(|(link, _)| link).collect())
+    }
+
+    /// Reciprocal rank fusion: each list contributes `1 / (k + rank)` for every link it contains.
+    pub fn reciprocal_rank_fusion(rankings: &[Vec<String>], k: f32) -> Vec<(String, f32)> {
+        let mut scores: std::collections::HashMap<&str, f32> = std::collections::HashMap::new();
+
+        for ranking in rankings {
+            for (rank, link) in ranking.iter().enumerate() {
+                *scores.entry(link.as_str()).or_insert(0.0) += 1.0 / (k + rank as f32 + 1.0);
+            }
+        }
+
+        let mut fused: Vec<(String, f32)> = scores.into_iter().map(|(link, score)| (link.to_string(), score)).collect();
+        fused.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal).then_with(|| a.0.cmp(&b.0)));
+        fused
+    }
+
+    /// Full-text and OpenAI-vector rankings fused with RRF. Results keep the fused order, but
+    /// `similarity` is each post's cosine similarity to the query so it reads the same as in
+    /// semantic search; posts found only by keywords are scored from their stored vectors.
+    pub async fn hybrid_links(
+        query: &str,
+        query_embedding: &[f32],
+        limit: usize,
+    ) -> Result<Vec<ScoredLink>, VectorSearchError> {
+        let depth = limit.max(HYBRID_CANDIDATES);
+        let (lexical, semantic) = tokio::join!(
+            lexical_links(query, depth),
+            nearest_links(EmbeddingColumn::OpenAI, query_embedding, depth),
+        );
+
+        let lexical = lexical.unwrap_or_else(|e| {
+            warn!("Lexical search failed, hybrid results are semantic only: {}", e);
+            Vec::new()
+        });
+        let semantic = semantic?;
+        let mut similarities: std::collections::HashMap<String, f32> =
+            semantic.iter().map(|r| (r.link.clone(), r.similarity)).collect();
+
+        let rankings = [lexical, semantic.into_iter().map(|r| r.link).collect()];
+        let fused: Vec<String> = reciprocal_rank_fusion(&rankings, RRF_K)
+            .into_iter()
+            .take(limit)
+            .map(|(link, _)| link)
+            .collect();
+
+        let lexical_only: Vec<String> = fused.iter().filter(|link| !similarities.contains_key(*link)).cloned().collect();
+        if !lexical_only.is_empty() {
+            match stored_vectors(EmbeddingColumn::OpenAI, &lexical_only).await {
+                Ok(vectors) => similarities.extend(
+                    vectors.into_iter().map(|(link, vector)| (link, cosine_similarity(query_embedding, &vector))),
+                ),
+                Err(e) => warn!("Could not score keyword-only hybrid results: {}", e),
+            }
+        }
+
+        Ok(fused
+            .into_iter()
+            .map(|link| {
+                let similarity = similarities.get(&link).copied().unwrap_or(0.0);
+                ScoredLink { link, similarity }
+            })
+            .collect())
+    }
+
+    pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+        let dot_product: f32 = a.iter().zip(b.iter()).map(|(x, y)| x * y).sum();
+        let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+        let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+        dot_product / (norm_a * norm_b)
+    }
+
+    /// One HNSW graph per vector space, used when Postgres can't do the nearest-neighbor
+    /// search itself. Persisted under `ANN_INDEX_DIR` so restarts don't rebuild from scratch.
+    /// Each graph keeps the `embedding_version` of every vector it holds, so a post re-embedded
+    /// by another process is picked up on the next reconcile.
+    #[derive(Clone)]
+    pub struct AnnIndexes {
+        openai: Hnsw,
+        minilm: Hnsw,
+        openai_versions: HashMap<String, String>,
+        minilm_versions: HashMap<String, String>,
+    }
+
+    impl AnnIndexes {
+        fn empty() -> Self {
+            Self {
+                openai: Hnsw::new(OPENAI_DIMENSIONS, HNSW_M, HNSW_EF_CONSTRUCTION),
+                minilm: Hnsw::new(MINILM_DIMENSIONS, HNSW_M, HNSW_EF_CONSTRUCTION),
+                openai_versions: HashMap::new(),
+                minilm_versions: HashMap::new(),
+            }
+        }
+
+        fn versions(&self, column: EmbeddingColumn) -> &HashMap<String, String> {
+            match column {
+                EmbeddingColumn::OpenAI => &self.openai_versions,
+                EmbeddingColumn::MiniLm => &self.minilm_versions,
+            }
+        }
+
+        fn versions_mut(&mut self, column: EmbeddingColumn) -> &mut HashMap<String, String> {
+            match column {
+                EmbeddingColumn::OpenAI => &mut self.openai_versions,
+                EmbeddingColumn::MiniLm => &mut self.minilm_versions,
+            }
+        }
+
+        /// Insert or replace a vector, recording the version it was built from. Returns false
+        /// when the vector has the wrong dimension.
+        fn insert(&mut self, column: EmbeddingColumn, link: String, vector: &[f32], version: Option<String>) -> bool {
+            if !self.index_mut(column).insert(link.clone(), vector) {
+                return false;
+            }
+            match version {
+                Some(version) => self.versions_mut(column).insert(link, version),
+                None => self.versions_mut(column).remove(&link),
+            };
+            true
+        }
+
+        fn remove(&mut self, column: EmbeddingColumn, link: &str) {
+            self.index_mut(column).remove(link);
+            self.versions_mut(column).remove(link);
+        }
+
+        fn index(&self, column: EmbeddingColumn) -> &Hnsw {
+            match column {
+                EmbeddingColumn::OpenAI => &self.openai,
+                EmbeddingColumn::MiniLm => &self.minilm,
+            }
+        }
+
+        fn index_mut(&mut self, column: EmbeddingColumn) -> &mut Hnsw {
+            match column {
+                EmbeddingColumn::OpenAI => &mut self.openai,
+                EmbeddingColumn::MiniLm => &mut self.minilm,
+            }
+        }
+
+        pub fn nearest(&self, column: EmbeddingColumn, query: &[f32], limit: usize) -> Vec<ScoredLink> {
+            self.index(column)
+                .search(query, limit, HNSW_EF_SEARCH.max(limit))
+                .into_iter()
+                .map(|(link, similarity)| ScoredLink { link, similarity })
+                .collect()
+        }
+
+        /// A missing or unreadable versions file only costs a refetch: reconciling treats every
+        /// vector without a recorded version as replaced.
+        fn load_from_disk() -> std::io::Result<Self> {
+            let read_versions = |column| -> HashMap<String, String> {
+                std::fs::read(versions_path(column))
+                    .ok()
+                    .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+                    .unwrap_or_default()
+            };
+
+            Ok(Self {
+                openai: Hnsw::load(&index_path(EmbeddingColumn::OpenAI), OPENAI_DIMENSIONS)?,
+                minilm: Hnsw::load(&index_path(EmbeddingColumn::MiniLm), MINILM_DIMENSIONS)?,
+                openai_versions: read_versions(EmbeddingColumn::OpenAI),
+                minilm_versions: read_versions(EmbeddingColumn::MiniLm),
+            })
+        }
+
+        fn save_to_disk(&self) -> std::io::Result<()> {
+            for column in [EmbeddingColumn::OpenAI, EmbeddingColumn::MiniLm] {
+                self.index(column).save(&index_path(column))?;
+                let versions = serde_json::to_vec(self.versions(column)).map_err(std::io::Error::other)?;
+                std::fs::write(versions_path(column), versions)?;
+            }
+            Ok(())
+        }
+    }
+
+    fn index_dir() -> PathBuf {
+        PathBuf::from(std::env::var("ANN_INDEX_DIR").unwrap_or_else(|_| ANN_INDEX_DIR.to_string()))
+    }
+
+    fn index_path(column: EmbeddingColumn) -> PathBuf {
+        let file = match column {
+            EmbeddingColumn::OpenAI => "openai.hnsw",
+            EmbeddingColumn::MiniLm => "minilm.hnsw",
+        };
+        index_dir().join(file)
+    }
+
+    fn versions_path(column: EmbeddingColumn) -> PathBuf {
+        let file = match column {
+            EmbeddingColumn::OpenAI => "openai.versions.json",
+            EmbeddingColumn::MiniLm => "minilm.versions.json",
+        };
+        index_dir().join(file)
+    }
+
+    /// Load the ANN indexes at startup: from disk when a saved copy exists, reconciled with
+    /// Supabase, otherwise built from every vector in Supabase and saved for next time. Searches
+    /// keep working (through pgvector, or finding nothing) while this runs.
+    pub async fn init_ann_indexes() -> Result<(), VectorSearchError> {
+        let _maintenance = ANN_MAINTENANCE.lock().await;
+        if ANN_INDEXES.read().await.is_some() {
+            return Ok(());
+        }
+
+        let loaded = tokio::task::spawn_blocking(AnnIndexes::load_from_disk).await;
+        let indexes = match loaded {
+            Ok(Ok(mut indexes)) => {
+                info!(
+                    "Loaded ANN indexes from disk: {} OpenAI vectors, {} MiniLM vectors",
+                    indexes.openai.len(),
+                    indexes.minilm.len()
+                );
+                // the saved copy misses posts deleted, embedded or re-embedded since it was written
+                let changes = index_changes(&indexes).await?;
+                changes.apply(&mut indexes);
+                indexes
+            }
+            Ok(Err(e)) => {
+                info!("No usable ANN index on disk ({}), building from Supabase", e);
+                build_from_supabase().await?
+            }
+            Err(e) => {
+                error!("ANN index load task failed: {}", e);
+                build_from_supabase().await?
+            }
+        };
+
+        *ANN_INDEXES.write().await = Some(Arc::new(indexes));
+        Ok(())
+    }
+
+    async fn build_from_supabase() -> Result<AnnIndexes, VectorSearchError> {
+        // versions first: a post re-embedded while the vectors download then looks outdated
+        // and is refetched on the next reconcile, rather than the other way round
+        let openai_versions = stored_versions(EmbeddingColumn::OpenAI).await?;
+        let minilm_versions = stored_versions(EmbeddingColumn::MiniLm).await?;
+        let (openai, minilm) = fetch_all_vectors().await?;
+        let indexes = tokio::task::spawn_blocking(move || {
+            let mut indexes = AnnIndexes::empty();
+            for (link, vector) in openai {
+                let version = openai_versions.get(&link).cloned();
+                indexes.insert(EmbeddingColumn::OpenAI, link, &vector, version);
+            }
+            for (link, vector) in minilm {
+                let version = minilm_versions.get(&link).cloned();
+                indexes.insert(EmbeddingColumn::MiniLm, link, &vector, version);
+            }
+            if let Err(e) = indexes.save_to_disk() {
+                error!("Failed to save ANN indexes: {}", e);
+            }
+            indexes
+        })
+        .await
+        .map_err(|e| VectorSearchError::Index(e.to_string()))?;
+
+        info!(
+            "Built ANN indexes: {} OpenAI vectors, {} MiniLM vectors",
+            indexes.openai.len(),
+            indexes.minilm.len()
+        );
+        Ok(indexes)
+    }
+
+    /// `embedding_version` of every tracked vector in `column`.
+    async fn stored_versions(column: EmbeddingColumn) -> Result<HashMap<String, String>, VectorSearchError> {
+        use crate::embedding_service::embeddings::fetch_stored_embeddings;
+
+        let stored = fetch_stored_embeddings(column)
+            .await
+            .map_err(|e| VectorSearchError::Supabase(e.to_string()))?;
+        Ok(stored
+            .into_iter()
+            .filter_map(|(link, meta)| Some((link, meta.version()?)))
+            .collect())
+    }
+
+    /// Differences between the indexes and the vectors stored in Supabase. `added` covers both
+    /// new posts and posts whose vector was replaced, with the version to record.
+    #[derive(Default)]
+    struct IndexChanges {
+        removed: Vec<(EmbeddingColumn, String)>,
+        added: Vec<(EmbeddingColumn, String, Vec<f32>, Option<String>)>,
+    }
+
+    impl IndexChanges {
+        fn is_empty(&self) -> bool {
+            self.removed.is_empty() && self.added.is_empty()
+        }
+
+        fn apply(self, indexes: &mut AnnIndexes) {
+            if self.is_empty() {
+                return;
+            }
+            info!("Reconciling ANN indexes: {} removed, {} added", self.removed.len(), self.added.len());
+            for (column, link) in self.removed {
+                indexes.remove(column, &link);
+            }
+            for (column, link, vector, version) in self.added {
+                indexes.insert(column, link, &vector, version);
+            }
+        }
+    }
+
+    async fn index_changes(indexes: &AnnIndexes) -> Result<IndexChanges, VectorSearchError> {
+        use crate::embedding_service::embeddings::fetch_stored_embeddings;
+
+        let mut changes = IndexChanges::default();
+        for column in [EmbeddingColumn::OpenAI, EmbeddingColumn::MiniLm] {
+            let stored = fetch_stored_embeddings(column)
+                .await
+                .map_err(|e| VectorSearchError::Supabase(e.to_string()))?;
+            let index = indexes.index(column);
+            let versions = indexes.versions(column);
+
+            changes.removed.extend(
+                index.keys().filter(|link| !stored.contains_key(*link)).map(|link| (column, link.to_string())),
+            );
+
+            let outdated: Vec<String> = stored
+                .iter()
+                .filter(|(link, meta)| !index.contains(link) || versions.get(*link) != meta.version().as_ref())
+                .map(|(link, _)| link.clone())
+                .collect();
+            changes.added.extend(stored_vectors(column, &outdated).await?.into_iter().map(|(link, vector)| {
+                let version = stored.get(&link).and_then(|meta| meta.version());
+                (column, link, vector, version)
+            }));
+        }
+        Ok(changes)
+    }
+
+    /// Bring loaded indexes in line with Supabase after posts were deleted, embedded or
+    /// re-embedded elsewhere. The comparison runs without holding the index lock.
+    pub async fn reconcile_ann_indexes() -> Result<(), VectorSearchError> {
+        let _maintenance = ANN_MAINTENANCE.lock().await;
+        let Some(snapshot) = ANN_INDEXES.read().await.clone() else {
+            return Ok(());
+        };
+
+        let changes = index_changes(&snapshot).await?;
+        drop(snapshot);
+        if let Some(indexes) = ANN_INDEXES.write().await.as_mut() {
+            changes.apply(Arc::make_mut(indexes));
+        }
+        Ok(())
+    }
+
+    async fn ann_nearest(
+        column: EmbeddingColumn,
+        query: &[f32],
+        limit: usize,
+    ) -> Result<Vec<ScoredLink>, VectorSearchError> {
+        if ANN_INDEXES.read().await.is_none() {
+            init_ann_indexes().await?;
+        }
+
+        let indexes = ANN_INDEXES.read().await.clone();
+        Ok(indexes
+            .map(|indexes| indexes.nearest(column, query, limit))
+            .unwrap_or_default())
+    }
+
+    /// The stored vector for one post, if it has been embedded in that space.
+    pub async fn stored_vector(column: EmbeddingColumn, link: &str) -> Result<Option<Vec<f32>>, VectorSearchError> {
+        let supabase = crate::supabase::get_client();
+        let response = supabase
+            .from("post_embeddings")
+            .select(format!("link,{}", column.vector_column()))
+            .eq("link", link)
+            .execute()
+            .await
+            .map_err(|e| VectorSearchError::Supabase(e.to_string()))?;
+
+        let body = response.text()
+            .await
+            .map_err(|e| VectorSearchError::Supabase(e.to_string()))?;
+        let rows: Vec<PostEmbedding> = serde_json::from_str(&body)?;
+
+        Ok(rows.into_iter().next().and_then(|row| {
+            let vector = match column {
+                EmbeddingColumn::OpenAI => row.embedding,
+                EmbeddingColumn::MiniLm => row.minilm,
+            };
+            (!vector.is_empty()).then_some(vector)
+        }))
+    }
+
+    /// Stored vectors for several posts at once; posts not embedded in that space are left out.
+    pub async fn stored_vectors(
+        column: EmbeddingColumn,
+        links: &[String],
+    ) -> Result<Vec<(String, Vec<f32>)>, VectorSearchError> {
+        let supabase = crate::supabase::get_client();
+        let mut vectors = Vec::with_capacity(links.len());
+
+        for chunk in links.chunks(50) {
+            let response = supabase
+                .from("post_embeddings")
+                .select(format!("link,{}", column.vector_column()))
+                .in_("link", chunk)
+                .execute()
+                .await
+                .map_err(|e| VectorSearchError::Supabase(e.to_string()))?;
+
+            let body = response.text()
+                .await
+                .map_err(|e| VectorSearchError::Supabase(e.to_string()))?;
+            let rows: Vec<PostEmbedding> = serde_json::from_str(&body)?;
+
+            vectors.extend(rows.into_iter().filter_map(|row| {
+                let vector = match column {
+                    EmbeddingColumn::OpenAI => row.embedding,
+                    EmbeddingColumn::MiniLm => row.minilm,
+                };
+                (!vector.is_empty()).then_some((row.link, vector))
+            }));
+        }
+
+        Ok(vectors)
+    }
+
+    /// Insert or replace one post's vector, built from text with content hash `hash`. A no-op
+    /// until the indexes have been loaded, since loading picks up everything already stored in
+    /// Supabase.
+    pub async fn upsert_embedding(column: EmbeddingColumn, link: &str, vector: &[f32], hash: &str) {
+        if let Some(indexes) = ANN_INDEXES.write().await.as_mut() {
+            let indexes = Arc::make_mut(indexes);
+            let version = embedding_version(column.current_model(), hash);
+            if !indexes.insert(column, link.to_string(), vector, Some(version)) {
+                warn!("Skipped ANN insert for {}: expected {} dimensions, got {}",
+                    link, indexes.index(column).dim(), vector.len());
+            }
+        }
+    }
+
+    /// Write the current indexes to disk. Called by the embedding jobs once they finish a run.
+    pub async fn persist_ann_indexes() {
+        let Some(snapshot) = ANN_INDEXES.read().await.clone() else {
+            return;
+        };
+
+        match tokio::task::spawn_blocking(move || snapshot.save_to_disk()).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => error!("Failed to save ANN indexes: {}", e),
+            Err(e) => error!("ANN index save task failed: {}", e),
+        }
+    }
+
+    pub async fn fetch_all_vectors() -> Result<(Vec<(String, Vec<f32>)>, Vec<(String, Vec<f32>)>), VectorSearchError> {
+        info!("Loading all stored vectors from Supabase");
+        let openai = fetch_vectors(EmbeddingColumn::OpenAI).await?;
+        let minilm = fetch_vectors(EmbeddingColumn::MiniLm).await?;
+        info!("Fetched {} OpenAI vectors, {} MiniLM vectors", openai.len(), minilm.len());
+        Ok((openai, minilm))
+    }
+
+    /// Every stored vector in one space. Only that column is downloaded.
+    pub async fn fetch_vectors(column: EmbeddingColumn) -> Result<Vec<(String, Vec<f32>)>, VectorSearchError> {
+        let supabase = crate::supabase::get_client();
+
+        let mut vectors = Vec::new();
+        let page_size = 1000;
+        let mut current_page = 0;
+
+        loop {
+            let start = current_page * page_size;
+            let end = start + page_size - 1;
+
+            debug!("Fetching {} page {}: range {}-{}", column.vector_column(), current_page + 1, start, end);
+
+            let response = supabase
+                .from("post_embeddings")
+                .select(format!("link,{}", column.vector_column()))
+                .not("is", column.vector_column(), "null")
+                .order("link")
+                .range(start, end)
+                .execute()
+                .await
+                .map_err(|e| VectorSearchError::Supabase(e.to_string()))?;
+
+            let response_text = response.text()
+                .await
+                .map_err(|e| VectorSearchError::Supabase(e.to_string()))?;
+
+            let embeddings_value: serde_json::Value = serde_json::from_str(&response_text)?;
+
+            let serde_json::Value::Array(arr) = embeddings_value else {
+                error!("Expected array response from Supabase, got: {}", &response_text[..response_text.len().min(500)]);
+                break;
+            };
+
+            if arr.is_empty() {
+                break;
+            }
+
+            for value in arr {
+                match serde_json::from_value::<PostEmbedding>(value) {
+                    Ok(post) => {
+                        let vector = match column {
+                            EmbeddingColumn::OpenAI => post.embedding,
+                            EmbeddingColumn::MiniLm => post.minilm,
+                        };
+                        if !vector.is_empty() {
+                            vectors.push((post.link, vector));
+                        }
+                    }
+                    Err(e) => error!("Failed to parse embedding: {}", e),
+                }
+            }
+
+            current_page += 1;
+        }
+
+        Ok(vectors)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn links(items: &[&str]) -> Vec<String> {
+            items.iter().map(|s| s.to_string()).collect()
+        }
+
+        #[test]
+        fn test_reciprocal_rank_fusion() {
+            let fused = reciprocal_rank_fusion(&[links(&["a", "b", "c"]), links(&["b", "d"])], 60.0);
+            let order: Vec<&str> = fused.iter().map(|(link, _)| link.as_str()).collect();
+
+            // "b" is in both lists, so it beats "a" despite ranking second lexically
+            assert_eq!(order, ["b", "a", "d", "c"]);
+            assert!((fused[0].1 - (1.0 / 62.0 + 1.0 / 61.0)).abs() < 1e-6);
+            assert!((fused[1].1 - 1.0 / 61.0).abs() < 1e-6);
+        }
+
+        #[test]
+        fn test_reciprocal_rank_fusion_ties_and_empty() {
+            assert!(reciprocal_rank_fusion(&[], 60.0).is_empty());
+            assert!(reciprocal_rank_fusion(&[Vec::new(), Vec::new()], 60.0).is_empty());
+
+            // equal scores fall back to link order so results are deterministic
+            let fused = reciprocal_rank_fusion(&[links(&["y"]), links(&["x"])], 60.0);
+            assert_eq!(fused[0].0, "x");
+            assert_eq!(fused[1].0, "y");
+        }
+    }
+}
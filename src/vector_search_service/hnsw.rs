@@ -0,0 +1,660 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+const FILE_MAGIC: &[u8; 8] = b"BBHNSW01";
+const NO_ENTRY_POINT: u32 = u32::MAX;
+/// Layers beyond this can't come from `random_level` with any sane `m`, so a file claiming more
+/// is corrupt.
+const MAX_LAYERS: usize = 64;
+/// Longest key accepted from a saved file.
+const MAX_KEY_BYTES: usize = 64 * 1024;
+/// The graph is rebuilt from its live nodes once tombstones reach this many and this share
+/// of all nodes, so repeated upserts and deletes don't grow it forever.
+const COMPACT_MIN_TOMBSTONES: usize = 64;
+const COMPACT_TOMBSTONE_RATIO: f64 = 0.2;
+
+/// Hierarchical navigable small world graph over cosine similarity.
+///
+/// Vectors are normalized on insert so distance is `1 - dot`. Keys are post links; inserting an
+/// existing key tombstones the old node, and tombstoned nodes are still walked but never returned
+/// until enough pile up to rebuild the graph without them.
+#[derive(Clone)]
+pub struct Hnsw {
+    dim: usize,
+    m: usize,
+    m_max0: usize,
+    ef_construction: usize,
+    level_mult: f64,
+    nodes: Vec<Node>,
+    keys: HashMap<String, u32>,
+    entry_point: Option<u32>,
+    max_level: usize,
+    rng_state: u64,
+}
+
+#[derive(Clone)]
+struct Node {
+    key: String,
+    vector: Vec<f32>,
+    neighbors: Vec<Vec<u32>>,
+    deleted: bool,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+struct Candidate {
+    distance: f32,
+    id: u32,
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance
+            .partial_cmp(&other.distance)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| self.id.cmp(&other.id))
+    }
+}
+
+fn normalize(vector: &[f32]) -> Vec<f32> {
+    let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm == 0.0 {
+        return vector.to_vec();
+    }
+    vector.iter().map(|x| x / norm).collect()
+}
+
+fn distance(a: &[f32], b: &[f32]) -> f32 {
+    1.0 - a.iter().zip(b.iter()).map(|(x, y)| x * y).sum::<f32>()
+}
+
+impl Hnsw {
+    pub fn new(dim: usize, m: usize, ef_construction: usize) -> Self {
+        Self::with_seed(dim, m, ef_construction, 0x2545_f491_4f6c_dd1d)
+    }
+
+    pub fn with_seed(dim: usize, m: usize, ef_construction: usize, seed: u64) -> Self {
+        Self {
+            dim,
+            m,
+            m_max0: m * 2,
+            ef_construction,
+            level_mult: 1.0 / (m as f64).ln(),
+            nodes: Vec::new(),
+            keys: HashMap::new(),
+            entry_point: None,
+            max_level: 0,
+            rng_state: seed.max(1),
+        }
+    }
+
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    /// Number of live (non-tombstoned) vectors.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.keys.contains_key(key)
+    }
+
+    /// Keys of the live vectors.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.keys.keys().map(String::as_str)
+    }
+
+    fn next_random(&mut self) -> f64 {
+        // xorshift64*
+        let mut x = self.rng_state;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.rng_state = x;
+        (x.wrapping_mul(0x2545_f491_4f6c_dd1d) >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn random_level(&mut self) -> usize {
+        let uniform = self.next_random().max(f64::MIN_POSITIVE);
+        (-uniform.ln() * self.level_mult).floor() as usize
+    }
+
+    pub fn remove(&mut self, key: &str) -> bool {
+        match self.keys.remove(key) {
+            Some(id) => {
+                self.nodes[id as usize].deleted = true;
+                let tombstones = self.nodes.len() - self.keys.len();
+                if tombstones >= COMPACT_MIN_TOMBSTONES
+                    && tombstones as f64 >= self.nodes.len() as f64 * COMPACT_TOMBSTONE_RATIO
+                {
+                    self.compact();
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Rebuild the graph from the live nodes only.
+    fn compact(&mut self) {
+        let mut rebuilt = Self::with_seed(self.dim, self.m, self.ef_construction, self.rng_state);
+        rebuilt.m_max0 = self.m_max0;
+        rebuilt.level_mult = self.level_mult;
+
+        for node in std::mem::take(&mut self.nodes).into_iter().filter(|node| !node.deleted) {
+            rebuilt.insert(node.key, &node.vector);
+        }
+        *self = rebuilt;
+    }
+
+    /// Insert or replace the vector stored under `key`. Vectors of the wrong dimension are ignored.
+    pub fn insert(&mut self, key: String, vector: &[f32]) -> bool {
+        if vector.len() != self.dim {
+            return false;
+        }
+
+        self.remove(&key);
+
+        let vector = normalize(vector);
+        let level = self.random_level();
+        let id = self.nodes.len() as u32;
+
+        self.nodes.push(Node {
+            key: key.clone(),
+            vector,
+            neighbors: vec![Vec::new(); level + 1],
+            deleted: false,
+        });
+        self.keys.insert(key, id);
+
+        let Some(entry_point) = self.entry_point else {
+            self.entry_point = Some(id);
+            self.max_level = level;
+            return true;
+        };
+
+        let query = self.nodes[id as usize].vector.clone();
+        let mut current = entry_point;
+
+        for layer in (level + 1..=self.max_level).rev() {
+            current = self.greedy_closest(&query, current, layer);
+        }
+
+        let mut entry_points = vec![current];
+        for layer in (0..=level.min(self.max_level)).rev() {
+            let candidates = self.search_layer(&query, &entry_points, self.ef_construction, layer);
+            let max_neighbors = if layer == 0 { self.m_max0 } else { self.m };
+            let selected: Vec<u32> = candidates
+                .iter()
+                .filter(|c| c.id != id)
+                .take(self.m)
+                .map(|c| c.id)
+                .collect();
+
+            self.nodes[id as usize].neighbors[layer] = selected.clone();
+
+            for neighbor in selected {
+                self.nodes[neighbor as usize].neighbors[layer].push(id);
+                if self.nodes[neighbor as usize].neighbors[layer].len() > max_neighbors {
+                    self.shrink_neighbors(neighbor, layer, max_neighbors);
+                }
+            }
+
+            entry_points = candidates.into_iter().map(|c| c.id).collect();
+        }
+
+        if level > self.max_level {
+            self.max_level = level;
+            self.entry_point = Some(id);
+        }
+
+        true
+    }
+
+    fn shrink_neighbors(&mut self, id: u32, layer: usize, max_neighbors: usize) {
+        let base = self.nodes[id as usize].vector.clone();
+        let mut scored: Vec<Candidate> = self.nodes[id as usize].neighbors[layer]
+            .iter()
+            .map(|&n| Candidate {
+                distance: distance(&base, &self.nodes[n as usize].vector),
+                id: n,
+            })
+            .collect();
+        scored.sort();
+        scored.truncate(max_neighbors);
+        self.nodes[id as usize].neighbors[layer] = scored.into_iter().map(|c| c.id).collect();
+    }
+
+    fn greedy_closest(&self, query: &[f32], start: u32, layer: usize) -> u32 {
+        let mut current = start;
+        let mut current_distance = distance(query, &self.nodes[current as usize].vector);
+
+        loop {
+            let mut changed = false;
+            if let Some(neighbors) = self.nodes[current as usize].neighbors.get(layer) {
+                for &neighbor in neighbors {
+                    let d = distance(query, &self.nodes[neighbor as usize].vector);
+                    if d < current_distance {
+                        current_distance = d;
+                        current = neighbor;
+                        changed = true;
+                    }
+                }
+            }
+            if !changed {
+                return current;
+            }
+        }
+    }
+
+    /// Best-first search on one layer. Returns up to `ef` candidates, closest first.
+    fn search_layer(&self, query: &[f32], entry_points: &[u32], ef: usize, layer: usize) -> Vec<Candidate> {
+        let mut visited: HashSet<u32> = HashSet::new();
+        let mut candidates: BinaryHeap<std::cmp::Reverse<Candidate>> = BinaryHeap::new();
+        let mut results: BinaryHeap<Candidate> = BinaryHeap::new();
+
+        for &entry in entry_points {
+            if visited.insert(entry) {
+                let candidate = Candidate {
+                    distance: distance(query, &self.nodes[entry as usize].vector),
+                    id: entry,
+                };
+                candidates.push(std::cmp::Reverse(candidate));
+                results.push(candidate);
+            }
+        }
+
+        while let Some(std::cmp::Reverse(closest)) = candidates.pop() {
+            let furthest = results.peek().map(|c| c.distance).unwrap_or(f32::MAX);
+            if closest.distance > furthest && results.len() >= ef {
+                break;
+            }
+
+            let Some(neighbors) = self.nodes[closest.id as usize].neighbors.get(layer) else {
+                continue;
+            };
+
+            for &neighbor in neighbors {
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+                let d = distance(query, &self.nodes[neighbor as usize].vector);
+                let furthest = results.peek().map(|c| c.distance).unwrap_or(f32::MAX);
+                if results.len() < ef || d < furthest {
+                    let candidate = Candidate { distance: d, id: neighbor };
+                    candidates.push(std::cmp::Reverse(candidate));
+                    results.push(candidate);
+                    if results.len() > ef {
+                        results.pop();
+                    }
+                }
+            }
+        }
+
+        results.into_sorted_vec()
+    }
+
+    /// Approximate `k` nearest keys with their cosine similarity, best first.
+    pub fn search(&self, query: &[f32], k: usize, ef: usize) -> Vec<(String, f32)> {
+        let Some(entry_point) = self.entry_point else {
+            return Vec::new();
+        };
+        if query.len() != self.dim || k == 0 {
+            return Vec::new();
+        }
+
+        let query = normalize(query);
+        let mut current = entry_point;
+        for layer in (1..=self.max_level).rev() {
+            current = self.greedy_closest(&query, current, layer);
+        }
+
+        // over-fetch a little so tombstoned nodes don't eat into k
+        let ef = ef.max(k) + (self.nodes.len() - self.keys.len()).min(k);
+        self.search_layer(&query, &[current], ef, 0)
+            .into_iter()
+            .filter(|c| !self.nodes[c.id as usize].deleted)
+            .take(k)
+            .map(|c| (self.nodes[c.id as usize].key.clone(), 1.0 - c.distance))
+            .collect()
+    }
+
+    /// Exact `k` nearest keys by scanning every live vector.
+    pub fn brute_force(&self, query: &[f32], k: usize) -> Vec<(String, f32)> {
+        let query = normalize(query);
+        let mut scored: Vec<Candidate> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| !node.deleted)
+            .map(|(id, node)| Candidate {
+                distance: distance(&query, &node.vector),
+                id: id as u32,
+            })
+            .collect();
+        scored.sort();
+        scored
+            .into_iter()
+            .take(k)
+            .map(|c| (self.nodes[c.id as usize].key.clone(), 1.0 - c.distance))
+            .collect()
+    }
+
+    /// Fraction of the exact top-`k` that `search` also returns, averaged over `queries`.
+    pub fn recall_at_k(&self, queries: &[Vec<f32>], k: usize, ef: usize) -> f32 {
+        if queries.is_empty() {
+            return 1.0;
+        }
+
+        let total: f32 = queries
+            .iter()
+            .map(|query| {
+                let exact: HashSet<String> = self.brute_force(query, k).into_iter().map(|(key, _)| key).collect();
+                if exact.is_empty() {
+                    return 1.0;
+                }
+                let found = self
+                    .search(query, k, ef)
+                    .into_iter()
+                    .filter(|(key, _)| exact.contains(key))
+                    .count();
+                found as f32 / exact.len() as f32
+            })
+            .sum();
+
+        total / queries.len() as f32
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let tmp_path = path.with_extension("tmp");
+        let mut w = BufWriter::new(File::create(&tmp_path)?);
+
+        w.write_all(FILE_MAGIC)?;
+        for value in [self.dim, self.m, self.m_max0, self.ef_construction] {
+            w.write_all(&(value as u32).to_le_bytes())?;
+        }
+        w.write_all(&self.level_mult.to_le_bytes())?;
+        w.write_all(&self.rng_state.to_le_bytes())?;
+        w.write_all(&self.entry_point.unwrap_or(NO_ENTRY_POINT).to_le_bytes())?;
+        w.write_all(&(self.max_level as u32).to_le_bytes())?;
+        w.write_all(&(self.nodes.len() as u32).to_le_bytes())?;
+
+        for node in &self.nodes {
+            w.write_all(&(node.key.len() as u32).to_le_bytes())?;
+            w.write_all(node.key.as_bytes())?;
+            w.write_all(&[node.deleted as u8])?;
+            for value in &node.vector {
+                w.write_all(&value.to_le_bytes())?;
+            }
+            w.write_all(&(node.neighbors.len() as u32).to_le_bytes())?;
+            for layer in &node.neighbors {
+                w.write_all(&(layer.len() as u32).to_le_bytes())?;
+                for neighbor in layer {
+                    w.write_all(&neighbor.to_le_bytes())?;
+                }
+            }
+        }
+
+        w.flush()?;
+        drop(w);
+        std::fs::rename(tmp_path, path)
+    }
+
+    /// Read an index saved by `save`. Anything that would make a later search panic, like a
+    /// different dimension than `expected_dim` or ids and layers pointing nowhere, is `InvalidData`.
+    pub fn load(path: &Path, expected_dim: usize) -> io::Result<Self> {
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+        let mut r = BufReader::new(File::open(path)?);
+
+        let mut magic = [0u8; 8];
+        r.read_exact(&mut magic)?;
+        if &magic != FILE_MAGIC {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "not an hnsw index file"));
+        }
+
+        let dim = read_u32(&mut r)? as usize;
+        if dim != expected_dim {
+            return Err(invalid("index dimension doesn't match the model"));
+        }
+        let m = read_u32(&mut r)? as usize;
+        let m_max0 = read_u32(&mut r)? as usize;
+        let ef_construction = read_u32(&mut r)? as usize;
+        let level_mult = f64::from_le_bytes(read_array(&mut r)?);
+        let rng_state = u64::from_le_bytes(read_array(&mut r)?);
+        let entry_point = match read_u32(&mut r)? {
+            NO_ENTRY_POINT => None,
+            id => Some(id),
+        };
+        let max_level = read_u32(&mut r)? as usize;
+        let node_count = read_u32(&mut r)? as usize;
+        if m < 2 || m_max0 < m || max_level >= MAX_LAYERS {
+            return Err(invalid("invalid graph parameters"));
+        }
+        match entry_point {
+            Some(id) if id as usize >= node_count => return Err(invalid("entry point out of range")),
+            None if node_count > 0 => return Err(invalid("missing entry point")),
+            _ => {}
+        }
+
+        // capped so a corrupt count fails on the read instead of on the allocation
+        let mut nodes = Vec::with_capacity(node_count.min(1 << 16));
+        let mut keys = HashMap::with_capacity(node_count.min(1 << 16));
+
+        for id in 0..node_count {
+            let key_len = read_u32(&mut r)? as usize;
+            if key_len > MAX_KEY_BYTES {
+                return Err(invalid("key too long"));
+            }
+            let mut key_bytes = vec![0u8; key_len];
+            r.read_exact(&mut key_bytes)?;
+            let key = String::from_utf8(key_bytes)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+            let [deleted] = read_array::<1>(&mut r)?;
+
+            let mut vector = Vec::with_capacity(dim);
+            for _ in 0..dim {
+                vector.push(f32::from_le_bytes(read_array(&mut r)?));
+            }
+
+            let layer_count = read_u32(&mut r)? as usize;
+            if layer_count == 0 || layer_count > max_level + 1 {
+                return Err(invalid("layer count out of range"));
+            }
+            let mut neighbors = Vec::with_capacity(layer_count);
+            for _ in 0..layer_count {
+                let count = read_u32(&mut r)? as usize;
+                if count > m_max0 {
+                    return Err(invalid("too many neighbors"));
+                }
+                let mut layer = Vec::with_capacity(count);
+                for _ in 0..count {
+                    let neighbor = read_u32(&mut r)?;
+                    if neighbor as usize >= node_count {
+                        return Err(invalid("neighbor id out of range"));
+                    }
+                    layer.push(neighbor);
+                }
+                neighbors.push(layer);
+            }
+
+            if deleted == 0 {
+                keys.insert(key.clone(), id as u32);
+            }
+            nodes.push(Node {
+                key,
+                vector,
+                neighbors,
+                deleted: deleted != 0,
+            });
+        }
+
+        // searches start at the entry point on the top layer and only follow a neighbor on
+        // layers that neighbor has
+        if let Some(id) = entry_point {
+            if nodes[id as usize].neighbors.len() != max_level + 1 {
+                return Err(invalid("entry point isn't on the top layer"));
+            }
+        }
+        for node in &nodes {
+            for (layer, neighbors) in node.neighbors.iter().enumerate() {
+                if neighbors.iter().any(|&n| nodes[n as usize].neighbors.len() <= layer) {
+                    return Err(invalid("neighbor missing from its layer"));
+                }
+            }
+        }
+
+        Ok(Self {
+            dim,
+            m,
+            m_max0,
+            ef_construction,
+            level_mult,
+            nodes,
+            keys,
+            entry_point,
+            max_level,
+            rng_state,
+        })
+    }
+}
+
+fn read_array<const N: usize>(r: &mut impl Read) -> io::Result<[u8; N]> {
+    let mut buf = [0u8; N];
+    r.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+fn read_u32(r: &mut impl Read) -> io::Result<u32> {
+    Ok(u32::from_le_bytes(read_array(r)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn random_vectors(count: usize, dim: usize, seed: u64) -> Vec<Vec<f32>> {
+        let mut state = seed;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % 2000) as f32 / 1000.0 - 1.0
+        };
+        (0..count).map(|_| (0..dim).map(|_| next()).collect()).collect()
+    }
+
+    fn build_index(vectors: &[Vec<f32>]) -> Hnsw {
+        let mut index = Hnsw::with_seed(vectors[0].len(), 16, 100, 42);
+        for (i, vector) in vectors.iter().enumerate() {
+            index.insert(format!("post-{}", i), vector);
+        }
+        index
+    }
+
+    #[test]
+    fn test_recall_against_brute_force() {
+        let vectors = random_vectors(2000, 32, 7);
+        let queries = random_vectors(50, 32, 99);
+        let index = build_index(&vectors);
+
+        let recall = index.recall_at_k(&queries, 10, 64);
+        assert!(recall >= 0.9, "recall@10 was {}", recall);
+    }
+
+    #[test]
+    fn test_upsert_replaces_vector() {
+        let vectors = random_vectors(200, 16, 3);
+        let mut index = build_index(&vectors);
+
+        index.insert("post-0".to_string(), &vectors[1]);
+        assert_eq!(index.len(), 200);
+
+        let results = index.search(&vectors[1], 2, 32);
+        let keys: Vec<&str> = results.iter().map(|(key, _)| key.as_str()).collect();
+        assert!(keys.contains(&"post-0"));
+        assert!(keys.contains(&"post-1"));
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let vectors = random_vectors(300, 16, 11);
+        let index = build_index(&vectors);
+
+        let path = std::env::temp_dir().join(format!("bb-hnsw-test-{}.bin", std::process::id()));
+        index.save(&path).unwrap();
+        let loaded = Hnsw::load(&path, 16).unwrap();
+        let wrong_dim = Hnsw::load(&path, 32);
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(loaded.len(), index.len());
+        assert_eq!(index.search(&vectors[5], 5, 32), loaded.search(&vectors[5], 5, 32));
+        assert_eq!(wrong_dim.err().map(|e| e.kind()), Some(io::ErrorKind::InvalidData));
+    }
+
+    #[test]
+    fn test_load_rejects_corrupt_files() {
+        let vectors = random_vectors(100, 8, 5);
+        let index = build_index(&vectors);
+
+        let path = std::env::temp_dir().join(format!("bb-hnsw-corrupt-{}.bin", std::process::id()));
+        index.save(&path).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+
+        // entry point sits after the magic, four u32 params, level_mult and rng_state
+        let entry_offset = 8 + 4 * 4 + 8 + 8;
+        let mut bad_entry = bytes.clone();
+        bad_entry[entry_offset..entry_offset + 4].copy_from_slice(&1_000_000u32.to_le_bytes());
+        std::fs::write(&path, &bad_entry).unwrap();
+        let bad_entry = Hnsw::load(&path, 8);
+
+        let mut bad_level = bytes.clone();
+        bad_level[entry_offset + 4..entry_offset + 8].copy_from_slice(&1_000u32.to_le_bytes());
+        std::fs::write(&path, &bad_level).unwrap();
+        let bad_level = Hnsw::load(&path, 8);
+
+        std::fs::write(&path, &bytes[..bytes.len() / 2]).unwrap();
+        let truncated = Hnsw::load(&path, 8);
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(bad_entry.err().map(|e| e.kind()), Some(io::ErrorKind::InvalidData));
+        assert_eq!(bad_level.err().map(|e| e.kind()), Some(io::ErrorKind::InvalidData));
+        assert!(truncated.is_err());
+    }
+
+    #[test]
+    fn test_repeated_upserts_stay_bounded() {
+        let vectors = random_vectors(50, 8, 13);
+        let mut index = build_index(&vectors);
+        let replacements = random_vectors(1000, 8, 17);
+
+        for vector in &replacements {
+            index.insert("post-0".to_string(), vector);
+            assert!(index.nodes.len() <= index.len() + COMPACT_MIN_TOMBSTONES);
+        }
+
+        assert_eq!(index.len(), 50);
+        let results = index.search(&replacements[999], 1, 32);
+        assert_eq!(results[0].0, "post-0");
+    }
+}
@@ -0,0 +1,430 @@
+#[cfg(feature = "ssr")]
+pub mod webhooks {
+    use futures::stream::{self, StreamExt};
+    use hmac::{Hmac, Mac};
+    use log::{error, info, warn};
+    use rand::Rng;
+    use serde::Serialize;
+    use serde_json::json;
+    use sha2::Sha256;
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+    use std::time::Duration;
+    use thiserror::Error;
+
+    use crate::database::db::DbPool;
+    use crate::embeddings_service::embeddings_local::LocalEmbeddingService;
+    use crate::models::{SavedSearch, WebhookDelivery};
+    use crate::vector_search_service::vector_search::cosine_similarity;
+
+    /// Attempts per delivery, including the first.
+    const MAX_ATTEMPTS: i32 = 4;
+    /// Wait before the first retry; each later retry waits four times longer.
+    const RETRY_BASE_DELAY: Duration = Duration::from_secs(2);
+    const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+    const CONCURRENT_DELIVERIES: usize = 8;
+    /// Longest response body kept in the delivery log.
+    const ERROR_MAX_CHARS: usize = 500;
+
+    #[derive(Error, Debug)]
+    pub enum WebhookError {
+        #[error("Database error: {0}")]
+        Database(#[from] diesel::result::Error),
+
+        #[error("Pool error: {0}")]
+        Pool(String),
+    }
+
+    /// A post that was just ingested, as sent in webhook payloads.
+    #[derive(Debug, Clone, Serialize)]
+    pub struct IngestedPost {
+        pub title: String,
+        pub company: String,
+        pub link: String,
+        pub published_at: String,
+        pub summary: Option<String>,
+        pub buzzwords: Vec<String>,
+    }
+
+    impl IngestedPost {
+        fn embedding_text(&self) -> String {
+            format!("{}\n{}", self.title, self.summary.as_deref().unwrap_or(""))
+        }
+    }
+
+    /// A fresh signing secret for a new saved search.
+    pub fn generate_secret() -> String {
+        let bytes: [u8; 32] = rand::thread_rng().gen();
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    /// Only public https endpoints may receive webhooks. Hostnames are checked again when
+    /// resolved at delivery time, see `pinned_client`.
+    pub fn validate_webhook_url(url: &str) -> Result<(), String> {
+        let parsed = reqwest::Url::parse(url).map_err(|_| "Webhook URL is not a valid URL".to_string())?;
+        if parsed.scheme() != "https" {
+            return Err("Webhook URL must use https".to_string());
+        }
+        let host = parsed.host_str().unwrap_or_default().trim_matches(['[', ']']);
+        if host.is_empty() || host == "localhost" || host.ends_with(".localhost") || host.ends_with(".internal") {
+            return Err("Webhook URL must be a public host".to_string());
+        }
+        if host.parse::<IpAddr>().is_ok_and(|ip| !is_public_ip(ip)) {
+            return Err("Webhook URL must be a public host".to_string());
+        }
+        Ok(())
+    }
+
+    /// False for loopback, private, link-local, shared, reserved and other special-purpose
+    /// ranges, including IPv4 addresses embedded in IPv6 ones.
+    pub fn is_public_ip(ip: IpAddr) -> bool {
+        match ip {
+            IpAddr::V4(v4) => is_public_ipv4(v4),
+            IpAddr::V6(v6) => {
+                let segments = v6.segments();
+                // ::ffff:a.b.c.d (mapped), ::a.b.c.d (compatible) and 64:ff9b::a.b.c.d (NAT64)
+                let embedded = match segments {
+                    [0, 0, 0, 0, 0, 0xffff, ..] | [0, 0, 0, 0, 0, 0, ..] | [0x64, 0xff9b, 0, 0, 0, 0, ..] => {
+                        Some(Ipv4Addr::from(((segments[6] as u32) << 16) | segments[7] as u32))
+                    }
+                    _ => None,
+                };
+                if let Some(v4) = embedded {
+                    return !v6.is_loopback() && !v6.is_unspecified() && is_public_ipv4(v4);
+                }
+                !(v6.is_loopback()
+                    || v6.is_unspecified()
+                    || v6.is_multicast()
+                    // unique local fc00::/7
+                    || (segments[0] & 0xfe00) == 0xfc00
+                    // link-local fe80::/10 and the deprecated site-local fec0::/10
+                    || (segments[0] & 0xffc0) == 0xfe80
+                    || (segments[0] & 0xffc0) == 0xfec0
+                    // documentation 2001:db8::/32
+                    || (segments[0] == 0x2001 && segments[1] == 0x0db8))
+            }
+        }
+    }
+
+    fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+        let [a, b, c, _] = ip.octets();
+        !(ip.is_private()
+            || ip.is_loopback()
+            || ip.is_link_local()
+            || ip.is_unspecified()
+            || ip.is_broadcast()
+            || ip.is_multicast()
+            || ip.is_documentation()
+            // "this network" 0.0.0.0/8
+            || a == 0
+            // shared address space 100.64.0.0/10
+            || (a == 100 && (b & 0xc0) == 64)
+            // IETF protocol assignments 192.0.0.0/24
+            || (a == 192 && b == 0 && c == 0)
+            // benchmarking 198.18.0.0/15
+            || (a == 198 && (b & 0xfe) == 18)
+            // reserved 240.0.0.0/4
+            || a >= 240)
+    }
+
+    enum TargetError {
+        /// Resolves to an address webhooks may not reach; retrying won't change that.
+        Blocked(String),
+        Unresolved(String),
+    }
+
+    /// A client that connects to `url`'s host only at an address resolved and checked here, so
+    /// the host can't be re-resolved to an internal address for the request itself. Redirects
+    /// are not followed.
+    async fn pinned_client(url: &str) -> Result<reqwest::Client, TargetError> {
+        let parsed = reqwest::Url::parse(url).map_err(|e| TargetError::Blocked(format!("Invalid webhook URL: {}", e)))?;
+        validate_webhook_url(url).map_err(TargetError::Blocked)?;
+        let host = parsed.host_str().unwrap_or_default().trim_matches(['[', ']']).to_string();
+        let port = parsed.port_or_known_default().unwrap_or(443);
+
+        let addresses: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), port))
+            .await
+            .map_err(|e| TargetError::Unresolved(format!("Could not resolve {}: {}", host, e)))?
+            .collect();
+        if addresses.is_empty() {
+            return Err(TargetError::Unresolved(format!("{} has no addresses", host)));
+        }
+        // every address is checked, not just the one used, so a mixed record set is refused
+        if let Some(blocked) = addresses.iter().find(|address| !is_public_ip(address.ip())) {
+            return Err(TargetError::Blocked(format!("{} resolves to non-public address {}", host, blocked.ip())));
+        }
+
+        reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .user_agent("bb-webhooks/1.0")
+            .redirect(reqwest::redirect::Policy::none())
+            .resolve(&host, addresses[0])
+            .build()
+            .map_err(|e| TargetError::Unresolved(e.to_string()))
+    }
+
+    /// `sha256=<hex>` HMAC of `"{timestamp}.{body}"`, sent as `X-BB-Signature`.
+    pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+        mac.update(timestamp.to_string().as_bytes());
+        mac.update(b".");
+        mac.update(body.as_bytes());
+        format!("sha256={:x}", mac.finalize().into_bytes())
+    }
+
+    /// Similarity percentage of every semantic search against every post, or `None` when the
+    /// embedding model can't be loaded.
+    fn semantic_scores(searches: &[SavedSearch], posts: &[IngestedPost]) -> Option<Vec<Vec<i32>>> {
+        let service = LocalEmbeddingService::init()
+            .and_then(|_| LocalEmbeddingService::get_instance())
+            .map_err(|e| warn!("Semantic saved searches skipped, embeddings unavailable: {}", e))
+            .ok()?;
+
+        let post_embeddings: Vec<Option<Vec<f32>>> = posts
+            .iter()
+            .map(|post| service.generate_embedding(&post.embedding_text()).ok())
+            .collect();
+
+        Some(
+            searches
+                .iter()
+                .map(|search| {
+                    if search.kind != "semantic" {
+                        return Vec::new();
+                    }
+                    let query = service.generate_embedding(&search.query).ok();
+                    post_embeddings
+                        .iter()
+                        .map(|post| match (&query, post) {
+                            (Some(query), Some(post)) => (cosine_similarity(query, post) * 100.0).round() as i32,
+                            _ => 0,
+                        })
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    fn keyword_matches(query: &str, post: &IngestedPost) -> bool {
+        let haystack = format!(
+            "{} {} {}",
+            post.title,
+            post.summary.as_deref().unwrap_or(""),
+            post.buzzwords.join(" ")
+        )
+        .to_lowercase();
+        let mut terms = query.split_whitespace().peekable();
+        terms.peek().is_some() && terms.all(|term| haystack.contains(&term.to_lowercase()))
+    }
+
+    /// Check freshly ingested posts against every enabled saved search and POST a signed payload
+    /// to the webhook of each match. Returns the number of deliveries that succeeded.
+    pub async fn notify_saved_searches(posts: Vec<IngestedPost>, pool: DbPool) -> Result<usize, WebhookError> {
+        if posts.is_empty() {
+            return Ok(0);
+        }
+
+        let searches = {
+            let mut conn = pool.get().await.map_err(|e| WebhookError::Pool(e.to_string()))?;
+            SavedSearch::list_enabled(&mut conn).await?
+        };
+        if searches.is_empty() {
+            return Ok(0);
+        }
+
+        let (searches, posts, scores) = if searches.iter().any(|s| s.kind == "semantic") {
+            tokio::task::spawn_blocking(move || {
+                let scores = semantic_scores(&searches, &posts);
+                (searches, posts, scores)
+            })
+            .await
+            .map_err(|e| WebhookError::Pool(format!("embedding task failed: {}", e)))?
+        } else {
+            (searches, posts, None)
+        };
+
+        let mut matches = Vec::new();
+        for (i, search) in searches.iter().enumerate() {
+            for (j, post) in posts.iter().enumerate() {
+                let similarity = match search.kind.as_str() {
+                    "company" => search.query.trim().eq_ignore_ascii_case(post.company.trim()).then_some(None),
+                    "keyword" => keyword_matches(&search.query, post).then_some(None),
+                    "semantic" => scores
+                        .as_ref()
+                        .map(|scores| scores[i][j])
+                        .filter(|score| *score >= search.min_similarity)
+                        .map(Some),
+                    _ => None,
+                };
+                if let Some(similarity) = similarity {
+                    matches.push((search, post, similarity));
+                }
+            }
+        }
+        info!("{} new posts matched saved searches {} times", posts.len(), matches.len());
+
+        let delivered = stream::iter(matches)
+            .map(|(search, post, similarity)| {
+                let pool = pool.clone();
+                async move {
+                    match deliver(&pool, search, post, similarity).await {
+                        Ok(delivered) => delivered,
+                        Err(e) => {
+                            error!("Webhook delivery for saved search {} failed: {}", search.id, e);
+                            false
+                        }
+                    }
+                }
+            })
+            .buffer_unordered(CONCURRENT_DELIVERIES)
+            .filter(|delivered| futures::future::ready(*delivered))
+            .count()
+            .await;
+
+        Ok(delivered)
+    }
+
+    /// Send one match, retrying transient failures, and record the outcome. `false` if the
+    /// post was already delivered to this search or every attempt failed.
+    async fn deliver(
+        pool: &DbPool,
+        search: &SavedSearch,
+        post: &IngestedPost,
+        similarity: Option<i32>,
+    ) -> Result<bool, WebhookError> {
+        let payload = json!({
+            "event": "saved_search.match",
+            "saved_search": {
+                "id": search.id,
+                "name": search.name,
+                "kind": search.kind,
+                "query": search.query,
+            },
+            "similarity": similarity,
+            "post": post,
+        });
+
+        let delivery = {
+            let mut conn = pool.get().await.map_err(|e| WebhookError::Pool(e.to_string()))?;
+            match WebhookDelivery::claim(search.id, &post.link, payload.clone(), &mut conn).await? {
+                Some(delivery) => delivery,
+                None => return Ok(false),
+            }
+        };
+        let body = json!({ "delivery_id": delivery.id, "data": payload }).to_string();
+
+        let mut attempts = 0;
+        let mut response_status = None;
+        let mut last_error = None;
+        let mut delivered = false;
+        while attempts < MAX_ATTEMPTS {
+            if attempts > 0 {
+                tokio::time::sleep(RETRY_BASE_DELAY * 4u32.pow(attempts as u32 - 1)).await;
+            }
+            attempts += 1;
+
+            // resolved on every attempt, since DNS may have changed since the last one
+            let client = match pinned_client(&search.webhook_url).await {
+                Ok(client) => client,
+                Err(TargetError::Blocked(reason)) => {
+                    response_status = None;
+                    last_error = Some(reason);
+                    break;
+                }
+                Err(TargetError::Unresolved(reason)) => {
+                    response_status = None;
+                    warn!("Webhook attempt {} for saved search {} failed: {}", attempts, search.id, reason);
+                    last_error = Some(reason);
+                    continue;
+                }
+            };
+
+            let timestamp = chrono::Utc::now().timestamp();
+            let result = client
+                .post(&search.webhook_url)
+                .header("Content-Type", "application/json")
+                .header("X-BB-Event", "saved_search.match")
+                .header("X-BB-Delivery", delivery.id.to_string())
+                .header("X-BB-Timestamp", timestamp.to_string())
+                .header("X-BB-Signature", sign(&search.webhook_secret, timestamp, &body))
+                .body(body.clone())
+                .send()
+                .await;
+
+            match result {
+                Ok(response) if response.status().is_success() => {
+                    response_status = Some(response.status().as_u16() as i32);
+                    last_error = None;
+                    delivered = true;
+                    break;
+                }
+                Ok(response) => {
+                    let status = response.status();
+                    response_status = Some(status.as_u16() as i32);
+                    let text = response.text().await.unwrap_or_default();
+                    last_error = Some(format!("HTTP {}: {}", status, text.chars().take(ERROR_MAX_CHARS).collect::<String>()));
+                    // the receiver rejected it, or redirected it elsewhere; sending the same thing
+                    // again won't help
+                    if status.is_redirection()
+                        || (status.is_client_error() && status != reqwest::StatusCode::TOO_MANY_REQUESTS)
+                    {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    response_status = None;
+                    last_error = Some(e.to_string());
+                }
+            }
+            warn!(
+                "Webhook attempt {} for saved search {} failed: {}",
+                attempts,
+                search.id,
+                last_error.as_deref().unwrap_or_default()
+            );
+        }
+
+        let mut conn = pool.get().await.map_err(|e| WebhookError::Pool(e.to_string()))?;
+        let status = if delivered { "delivered" } else { "failed" };
+        delivery.record(status, attempts, response_status, last_error, &mut conn).await?;
+        if delivered {
+            search.mark_matched(&mut conn).await?;
+        }
+        Ok(delivered)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn public(ip: &str) -> bool {
+            is_public_ip(ip.parse().unwrap())
+        }
+
+        #[test]
+        fn test_is_public_ip() {
+            for ip in ["93.184.216.34", "8.8.8.8", "2606:4700:4700::1111", "::ffff:8.8.8.8"] {
+                assert!(public(ip), "{}", ip);
+            }
+            for ip in [
+                "127.0.0.1", "10.1.2.3", "172.16.0.1", "192.168.1.1", "169.254.169.254", "0.0.0.0",
+                "0.1.2.3", "100.64.0.1", "192.0.0.1", "198.18.0.1", "224.0.0.1", "240.0.0.1",
+                "255.255.255.255", "::1", "::", "fc00::1", "fd12:3456::1", "fe80::1", "febf::1",
+                "fec0::1", "ff02::1", "2001:db8::1", "::ffff:127.0.0.1", "::ffff:169.254.169.254",
+                "::ffff:10.0.0.1", "::127.0.0.1", "64:ff9b::a9fe:a9fe",
+            ] {
+                assert!(!public(ip), "{}", ip);
+            }
+        }
+
+        #[test]
+        fn test_validate_webhook_url() {
+            assert!(validate_webhook_url("https://hooks.example.com/bb").is_ok());
+            assert!(validate_webhook_url("http://hooks.example.com/bb").is_err());
+            assert!(validate_webhook_url("https://localhost/bb").is_err());
+            assert!(validate_webhook_url("https://127.0.0.1/bb").is_err());
+            assert!(validate_webhook_url("https://[::ffff:127.0.0.1]/bb").is_err());
+            assert!(validate_webhook_url("https://[fe80::1]/bb").is_err());
+            assert!(validate_webhook_url("not a url").is_err());
+        }
+    }
+}